@@ -0,0 +1,275 @@
+//! Attestation bundles, where a participant signs off on their contribution with an Ed25519 key,
+//! in place of pasting its hash to a gist.
+//!
+//! A bundle is JSON holding a `Statement` of what was contributed, along with the Ed25519 public key
+//! of the participant and their signature over the statement, as serialized in the bundle.
+
+use blake2::{Blake2b, Digest};
+use ed25519_dalek::{Keypair, PublicKey, SecretKey, Signature, Signer, Verifier};
+use memmap::MmapOptions;
+use rand::{OsRng, Rng};
+use serde::{Deserialize, Serialize};
+use serde_json::value::RawValue;
+use std::convert::TryFrom;
+use std::fmt;
+use std::fs::{self, File, OpenOptions};
+use std::io::{self, Write};
+use std::path::Path;
+
+use super::coordinator::State;
+
+/// Domain separation of hash of entropy, so that it tells nothing about the seed it's made from.
+const ENTROPY_PERSONALIZATION: &[u8] = b"powersoftau attestation entropy";
+
+/// What a participant attests to.
+#[derive(Clone, Debug, PartialEq, Serialize, Deserialize)]
+pub struct Statement {
+    /// name and version of software which computed contribution
+    pub tool: String,
+    pub curve: String,
+    pub power: usize,
+    /// who contributed, if they wish to say
+    pub participant: Option<String>,
+    /// hex encoded BLAKE2b hashes
+    pub challenge_hash: String,
+    pub response_hash: String,
+    /// hex encoded `PublicKey` of contribution, as `PublicKey::serialize` writes it
+    pub public_key: String,
+    /// hex encoded hash of seed contribution was sampled from, see `entropy_hash`
+    pub entropy_hash: String,
+}
+
+/// Signed statement.
+#[derive(Debug, Serialize, Deserialize)]
+pub struct Attestation {
+    /// statement exactly as it was signed
+    pub statement: Box<RawValue>,
+    /// hex encoded Ed25519 public key of participant
+    pub signer: String,
+    /// hex encoded Ed25519 signature over `statement`
+    pub signature: String,
+}
+
+#[derive(Debug)]
+pub enum AttestationError {
+    IoError(io::Error),
+    Json(serde_json::Error),
+    /// signing key, signer or signature isn't valid
+    Key(String),
+    BadSignature,
+    /// bundle doesn't match response file
+    Response(&'static str),
+    /// no contribution in transcript matches bundle
+    NotInTranscript,
+}
+
+impl fmt::Display for AttestationError {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        match *self {
+            AttestationError::IoError(ref e) => write!(f, "Disk IO error: {}", e),
+            AttestationError::Json(ref e) => write!(f, "Invalid attestation: {}", e),
+            AttestationError::Key(ref e) => write!(f, "Invalid key: {}", e),
+            AttestationError::BadSignature => write!(f, "Signature doesn't match statement"),
+            AttestationError::Response(e) => write!(f, "Response doesn't match attestation: {}", e),
+            AttestationError::NotInTranscript => {
+                write!(f, "No contribution in transcript matches attestation")
+            }
+        }
+    }
+}
+
+impl From<io::Error> for AttestationError {
+    fn from(err: io::Error) -> AttestationError {
+        AttestationError::IoError(err)
+    }
+}
+
+impl From<serde_json::Error> for AttestationError {
+    fn from(err: serde_json::Error) -> AttestationError {
+        AttestationError::Json(err)
+    }
+}
+
+/// Hash of `seed`, which can be published without revealing it, committing to randomness the
+/// contribution was sampled from.
+pub fn entropy_hash(seed: &[u8]) -> Vec<u8> {
+    let mut hasher = Blake2b::default();
+    hasher.input(ENTROPY_PERSONALIZATION);
+    hasher.input(seed);
+    hasher.result().to_vec()
+}
+
+/// Reads hex encoded Ed25519 secret key of participant from `path`, or creates one there if it
+/// doesn't exist yet.
+pub fn signing_key(path: &Path) -> Result<Keypair, AttestationError> {
+    let secret = if path.exists() {
+        let bytes = hex::decode(fs::read_to_string(path)?.trim())
+            .map_err(|e| AttestationError::Key(e.to_string()))?;
+        SecretKey::from_bytes(&bytes).map_err(|e| AttestationError::Key(e.to_string()))?
+    } else {
+        let mut bytes = [0u8; 32];
+        OsRng::new()
+            .expect("unable to access system randomness")
+            .fill_bytes(&mut bytes);
+        let secret = SecretKey::from_bytes(&bytes).expect("any 32 bytes are a secret key");
+
+        let mut options = OpenOptions::new();
+        options.write(true).create_new(true);
+        #[cfg(unix)]
+        {
+            use std::os::unix::fs::OpenOptionsExt;
+            options.mode(0o600);
+        }
+        let mut file = options.open(path)?;
+        writeln!(file, "{}", hex::encode(secret.as_bytes()))?;
+        secret
+    };
+    let public = PublicKey::from(&secret);
+
+    Ok(Keypair { secret, public })
+}
+
+impl Attestation {
+    pub fn sign(statement: &Statement, key: &Keypair) -> Attestation {
+        let statement = serde_json::to_string(statement).expect("statements always serialize");
+        let signature = key.sign(statement.as_bytes());
+
+        Attestation {
+            statement: RawValue::from_string(statement).expect("serialized statement is JSON"),
+            signer: hex::encode(key.public.as_bytes()),
+            signature: hex::encode(&signature.to_bytes()[..]),
+        }
+    }
+
+    pub fn read(path: &Path) -> Result<Attestation, AttestationError> {
+        Ok(serde_json::from_slice(&fs::read(path)?)?)
+    }
+
+    pub fn write(&self, path: &Path) -> Result<(), AttestationError> {
+        let mut file = File::create(path)?;
+        serde_json::to_writer_pretty(&mut file, self)?;
+        writeln!(file)?;
+        Ok(())
+    }
+
+    /// Checks signature of `signer` over statement, returning statement.
+    pub fn verify(&self) -> Result<Statement, AttestationError> {
+        let decode =
+            |value: &str| hex::decode(value).map_err(|e| AttestationError::Key(e.to_string()));
+        let signer = PublicKey::from_bytes(&decode(&self.signer)?)
+            .map_err(|e| AttestationError::Key(e.to_string()))?;
+        let signature = Signature::try_from(&decode(&self.signature)?[..])
+            .map_err(|e| AttestationError::Key(e.to_string()))?;
+        signer
+            .verify(self.statement.get().as_bytes(), &signature)
+            .map_err(|_| AttestationError::BadSignature)?;
+
+        Ok(serde_json::from_str(self.statement.get())?)
+    }
+}
+
+impl Statement {
+    /// Checks `response` file is the one statement is about.
+    pub fn check_response(&self, response: &Path) -> Result<(), AttestationError> {
+        let file = File::open(response)?;
+        let map = unsafe { MmapOptions::new().map(&file)? };
+
+        let mut hasher = Blake2b::default();
+        for chunk in map.chunks(1 << 30) {
+            hasher.input(chunk);
+        }
+        if hex::encode(hasher.result().as_slice()) != self.response_hash {
+            return Err(AttestationError::Response("hash differs"));
+        }
+        if map.get(0..64).map(hex::encode) != Some(self.challenge_hash.clone()) {
+            return Err(AttestationError::Response(
+                "it's based on another challenge",
+            ));
+        }
+        // public key is written uncompressed at the very end of response
+        let public_key = hex::decode(&self.public_key)
+            .map_err(|_| AttestationError::Response("public key isn't hex"))?;
+        if public_key.is_empty() || !map.ends_with(&public_key) {
+            return Err(AttestationError::Response("public key differs"));
+        }
+
+        Ok(())
+    }
+
+    /// Finds round of contribution statement is about, among those accepted by coordinator.
+    pub fn find_in(&self, state: &State) -> Result<usize, AttestationError> {
+        state
+            .contributions
+            .iter()
+            .position(|accepted| {
+                accepted.challenge_hash == self.challenge_hash
+                    && accepted.response_hash == self.response_hash
+            })
+            .ok_or(AttestationError::NotInTranscript)
+    }
+}
+
+#[test]
+fn test_attestation() {
+    use super::batched_accumulator::test_contribution;
+    use super::coordinator::Accepted;
+    use super::parameters::UseCompression;
+
+    let dir = std::env::temp_dir().join(format!("attestation-{}", std::process::id()));
+    fs::create_dir_all(&dir).unwrap();
+
+    // public key as `PublicKey::write` leaves it at the end of a response
+    let contribution = test_contribution(None, UseCompression::Yes);
+    let response = contribution.response.to_vec();
+    let challenge_hash = response[..64].to_vec();
+    let mut public_key = vec![];
+    contribution.public_key.serialize(&mut public_key).unwrap();
+    fs::write(dir.join("response"), &response).unwrap();
+
+    let statement = Statement {
+        tool: "powersoftau 0.2.0".to_owned(),
+        curve: "bn256".to_owned(),
+        power: contribution.parameters.required_power,
+        participant: Some("alice".to_owned()),
+        challenge_hash: hex::encode(&challenge_hash),
+        response_hash: hex::encode(Blake2b::digest(&response).as_slice()),
+        public_key: hex::encode(&public_key),
+        entropy_hash: hex::encode(entropy_hash(b"seed")),
+    };
+
+    // key is created once, then read back
+    let key_path = dir.join("signing_key");
+    let key = signing_key(&key_path).unwrap();
+    assert_eq!(signing_key(&key_path).unwrap().public, key.public);
+
+    let bundle = dir.join("attestation.json");
+    Attestation::sign(&statement, &key).write(&bundle).unwrap();
+    let attestation = Attestation::read(&bundle).unwrap();
+    assert_eq!(attestation.verify().unwrap(), statement);
+    statement.check_response(&dir.join("response")).unwrap();
+
+    let mut state = State::default();
+    assert!(statement.find_in(&state).is_err());
+    state.contributions.push(Accepted {
+        participant: "alice".to_owned(),
+        challenge_hash: statement.challenge_hash.clone(),
+        response_hash: statement.response_hash.clone(),
+    });
+    assert_eq!(statement.find_in(&state).unwrap(), 0);
+
+    // any change to statement breaks signature
+    let tampered = fs::read_to_string(&bundle)
+        .unwrap()
+        .replace("alice", "mallory");
+    fs::write(&bundle, tampered).unwrap();
+    match Attestation::read(&bundle).unwrap().verify() {
+        Err(AttestationError::BadSignature) => {}
+        _ => panic!("tampered statement must not verify"),
+    }
+
+    // as does a response other than the one attested
+    fs::write(dir.join("response"), &response[1..]).unwrap();
+    assert!(statement.check_response(&dir.join("response")).is_err());
+
+    fs::remove_dir_all(dir).unwrap();
+}
@@ -0,0 +1,544 @@
+//! Append-only audit log of a ceremony, one JSON entry per line for every contribution verified.
+//!
+//! Each entry holds BLAKE2b hash of the one before it, and its own hash over all of its fields, so
+//! that entries can't be dropped, reordered or modified without it showing. Publishing hash of the
+//! last entry pins down the whole log.
+//!
+//! Entries name challenge & response files, relative to a directory `audit` resolves them in, so
+//! every file must keep a name of its own, e.g. `challenge_<n>` & `response_<n>` of round `n`,
+//! rather than being renamed into `challenge` & `response` for each round. A challenge is either
+//! recomputed from the last verified response, carrying its hash in place of its own header, or
+//! that response copied as is, when it's left compressed.
+
+use blake2::{Blake2b, Digest};
+use memmap::{Mmap, MmapOptions};
+use serde::{Deserialize, Serialize};
+use std::fmt;
+use std::fs::{self, File, OpenOptions};
+use std::io::{self, BufRead, Write};
+use std::path::{Path, PathBuf};
+
+use super::coordinator::unix_now;
+use super::utils::blank_hash;
+
+/// What got verified, and how it went.
+#[derive(Clone, Debug, PartialEq, Serialize, Deserialize)]
+pub struct Contribution {
+    pub challenge: String,
+    /// hex encoded BLAKE2b hash of challenge file
+    pub challenge_hash: String,
+    pub response: String,
+    /// hex encoded BLAKE2b hash of response file
+    pub response_hash: String,
+    /// hex encoded public key, unless response got rejected before it was read
+    pub public_key: Option<String>,
+    pub verified: bool,
+    pub error: Option<String>,
+}
+
+#[derive(Clone, Debug, PartialEq, Serialize, Deserialize)]
+pub struct Entry {
+    /// position in log, from 0
+    pub index: u64,
+    /// hash of previous entry, or blank hash for first one
+    pub previous: String,
+    /// seconds since Unix epoch
+    pub timestamp: u64,
+    #[serde(flatten)]
+    pub contribution: Contribution,
+    /// hash over every field above
+    pub hash: String,
+}
+
+impl Entry {
+    fn digest(&self) -> String {
+        let fields = (
+            self.index,
+            &self.previous,
+            self.timestamp,
+            &self.contribution,
+        );
+        let bytes = serde_json::to_vec(&fields).expect("entry fields always serialize");
+        let mut hasher = Blake2b::default();
+        hasher.input(&bytes);
+        hex::encode(hasher.result().as_slice())
+    }
+}
+
+#[derive(Debug)]
+pub enum AuditError {
+    IoError(io::Error),
+    /// line of log isn't an entry
+    Parse {
+        line: usize,
+        error: serde_json::Error,
+    },
+    /// path already names another file, or the same response, in an entry
+    Name {
+        path: String,
+        index: u64,
+    },
+}
+
+impl fmt::Display for AuditError {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        match *self {
+            AuditError::IoError(ref e) => write!(f, "Disk IO error: {}", e),
+            AuditError::Parse { line, ref error } => {
+                write!(f, "Line {} isn't a log entry: {}", line, error)
+            }
+            AuditError::Name { ref path, index } => write!(
+                f,
+                "`{}` is already named in entry #{}, every challenge & response must keep a name \
+                 of its own, e.g. `challenge_<n>` & `response_<n>`",
+                path, index
+            ),
+        }
+    }
+}
+
+impl From<io::Error> for AuditError {
+    fn from(err: io::Error) -> AuditError {
+        AuditError::IoError(err)
+    }
+}
+
+/// Writer appending to a log.
+pub struct AuditLog {
+    path: PathBuf,
+    /// index and hash next entry links to
+    next: (u64, String),
+}
+
+impl AuditLog {
+    /// Opens log at `path`, created if it doesn't exist, to append after its last entry. A line
+    /// left half written by an interruption is cut off first.
+    pub fn open(path: &Path) -> Result<AuditLog, AuditError> {
+        let mut next = (0, hex::encode(blank_hash().as_slice()));
+        if path.exists() {
+            let complete = complete_lines(&fs::read(path)?).len() as u64;
+            let file = OpenOptions::new().write(true).open(path)?;
+            if file.metadata()?.len() != complete {
+                file.set_len(complete)?;
+                file.sync_all()?;
+            }
+            if let Some(last) = read_entries(path)?.pop() {
+                next = (last.index + 1, last.hash);
+            }
+        }
+
+        Ok(AuditLog {
+            path: path.to_path_buf(),
+            next,
+        })
+    }
+
+    /// Number of entries in log.
+    pub fn len(&self) -> u64 {
+        self.next.0
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.len() == 0
+    }
+
+    /// Entries of log, oldest first.
+    pub fn entries(&self) -> Result<Vec<Entry>, AuditError> {
+        if !self.path.exists() {
+            return Ok(vec![]);
+        }
+        read_entries(&self.path)
+    }
+
+    /// Last entry of log, if there's one.
+    pub fn last(&self) -> Result<Option<Entry>, AuditError> {
+        Ok(self.entries()?.pop())
+    }
+
+    /// Checks that `(path, hex encoded hash)` of challenge & response can be logged so that
+    /// `audit` finds them: neither path may name another file in an earlier entry, and response
+    /// mustn't have been logged already.
+    pub fn check_names(
+        &self,
+        challenge: (&str, &str),
+        response: (&str, &str),
+    ) -> Result<(), AuditError> {
+        for entry in self.entries()? {
+            let c = &entry.contribution;
+            let named = [
+                (&c.challenge, &c.challenge_hash),
+                (&c.response, &c.response_hash),
+            ];
+            for &(path, hash) in [challenge, response].iter() {
+                let other = named
+                    .iter()
+                    .any(|(p, h)| p.as_str() == path && h.as_str() != hash);
+                if other || (path == response.0 && c.response == path) {
+                    return Err(AuditError::Name {
+                        path: path.to_owned(),
+                        index: entry.index,
+                    });
+                }
+            }
+        }
+        Ok(())
+    }
+
+    pub fn append(&mut self, contribution: Contribution) -> Result<Entry, AuditError> {
+        let mut entry = Entry {
+            index: self.next.0,
+            previous: self.next.1.clone(),
+            timestamp: unix_now(),
+            contribution,
+            hash: String::new(),
+        };
+        entry.hash = entry.digest();
+
+        let mut line = serde_json::to_vec(&entry).expect("entries always serialize");
+        line.push(b'\n');
+        let mut file = OpenOptions::new()
+            .create(true)
+            .append(true)
+            .open(&self.path)?;
+        file.write_all(&line)?;
+        file.sync_all()?;
+
+        self.next = (entry.index + 1, entry.hash.clone());
+        Ok(entry)
+    }
+}
+
+/// Entries of log, leaving out a line left half written by an interruption.
+fn read_entries(path: &Path) -> Result<Vec<Entry>, AuditError> {
+    let bytes = fs::read(path)?;
+    let mut entries = vec![];
+    for (i, line) in complete_lines(&bytes).lines().enumerate() {
+        let line = line?;
+        if line.trim().is_empty() {
+            continue;
+        }
+        let entry = serde_json::from_str(&line)
+            .map_err(|error| AuditError::Parse { line: i + 1, error })?;
+        entries.push(entry);
+    }
+    Ok(entries)
+}
+
+/// Something wrong with an entry, or with files it refers to.
+#[derive(Clone, Debug, PartialEq)]
+pub enum Problem {
+    /// entry isn't next one in log, so entries are missing or reordered
+    Index {
+        expected: u64,
+        found: u64,
+    },
+    /// entry doesn't link to hash of previous one
+    BrokenLink,
+    /// entry doesn't hash to its recorded hash, so it was modified
+    Modified,
+    Missing {
+        path: String,
+    },
+    /// file doesn't hash to what entry recorded
+    FileHash {
+        path: String,
+    },
+    /// response isn't based on recorded challenge
+    ResponseChallenge,
+    /// challenge isn't the one recomputed from last verified response
+    ChallengeChain,
+    /// response doesn't end with recorded public key
+    PublicKey,
+}
+
+impl fmt::Display for Problem {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        match *self {
+            Problem::Index { expected, found } => write!(
+                f,
+                "entry #{} found where #{} was expected, entries are missing or reordered",
+                found, expected
+            ),
+            Problem::BrokenLink => write!(f, "entry doesn't link to hash of previous entry"),
+            Problem::Modified => write!(f, "entry doesn't match its hash, it was modified"),
+            Problem::Missing { ref path } => write!(f, "`{}` is missing", path),
+            Problem::FileHash { ref path } => write!(f, "`{}` doesn't match its hash", path),
+            Problem::ResponseChallenge => write!(f, "response isn't based on its challenge"),
+            Problem::ChallengeChain => {
+                write!(f, "challenge doesn't follow last verified response")
+            }
+            Problem::PublicKey => write!(f, "response doesn't hold recorded public key"),
+        }
+    }
+}
+
+#[derive(Clone, Debug, PartialEq)]
+pub struct Finding {
+    /// position of entry in log, from 1
+    pub line: usize,
+    pub problem: Problem,
+}
+
+/// Outcome of auditing a log.
+pub struct Audit {
+    pub entries: Vec<Entry>,
+    pub findings: Vec<Finding>,
+}
+
+/// Replays log at `path` against transcript files it names, resolved relative to `dir`, finding
+/// every problem rather than stopping at first one.
+pub fn audit(path: &Path, dir: &Path) -> Result<Audit, AuditError> {
+    let entries = read_entries(path)?;
+    let mut findings = vec![];
+
+    let mut previous = hex::encode(blank_hash().as_slice());
+    let mut last_verified: Option<String> = None;
+    for (i, entry) in entries.iter().enumerate() {
+        let mut found = |problem| {
+            findings.push(Finding {
+                line: i + 1,
+                problem,
+            })
+        };
+
+        if entry.index != i as u64 {
+            found(Problem::Index {
+                expected: i as u64,
+                found: entry.index,
+            });
+        }
+        if entry.previous != previous {
+            found(Problem::BrokenLink);
+        }
+        if entry.digest() != entry.hash {
+            found(Problem::Modified);
+        }
+        previous = entry.hash.clone();
+
+        let contribution = &entry.contribution;
+        let challenge = match read_file(dir, &contribution.challenge) {
+            Some(challenge) => challenge,
+            None => {
+                found(Problem::Missing {
+                    path: contribution.challenge.clone(),
+                });
+                continue;
+            }
+        };
+        let response = match read_file(dir, &contribution.response) {
+            Some(response) => response,
+            None => {
+                found(Problem::Missing {
+                    path: contribution.response.clone(),
+                });
+                continue;
+            }
+        };
+
+        if hash_hex(&challenge) != contribution.challenge_hash {
+            found(Problem::FileHash {
+                path: contribution.challenge.clone(),
+            });
+        }
+        if hash_hex(&response) != contribution.response_hash {
+            found(Problem::FileHash {
+                path: contribution.response.clone(),
+            });
+        }
+        if header_hex(&response) != Some(contribution.challenge_hash.clone()) {
+            found(Problem::ResponseChallenge);
+        }
+        // challenge recomputed from a response carries its hash in place of its own header, while
+        // a response left compressed is copied as challenge
+        if let Some(ref response_hash) = last_verified {
+            if header_hex(&challenge).as_ref() != Some(response_hash)
+                && contribution.challenge_hash != *response_hash
+            {
+                found(Problem::ChallengeChain);
+            }
+        }
+        if let Some(ref public_key) = contribution.public_key {
+            let key = hex::decode(public_key).unwrap_or_default();
+            if key.is_empty() || !response.ends_with(&key) {
+                found(Problem::PublicKey);
+            }
+        }
+
+        if contribution.verified {
+            last_verified = Some(contribution.response_hash.clone());
+        }
+    }
+
+    Ok(Audit { entries, findings })
+}
+
+/// Transcript files may be far larger than memory, so they're mapped rather than read.
+fn read_file(dir: &Path, path: &str) -> Option<Mmap> {
+    let file = File::open(dir.join(path)).ok()?;
+    unsafe { MmapOptions::new().map(&file).ok() }
+}
+
+/// Lines up to last newline, every one of them fully written.
+fn complete_lines(bytes: &[u8]) -> &[u8] {
+    let end = bytes
+        .iter()
+        .rposition(|b| *b == b'\n')
+        .map_or(0, |end| end + 1);
+    &bytes[..end]
+}
+
+fn hash_hex(bytes: &[u8]) -> String {
+    let mut hasher = Blake2b::default();
+    for chunk in bytes.chunks(1 << 30) {
+        hasher.input(chunk);
+    }
+    hex::encode(hasher.result().as_slice())
+}
+
+fn header_hex(bytes: &[u8]) -> Option<String> {
+    bytes.get(0..64).map(hex::encode)
+}
+
+#[test]
+fn test_audit_log() {
+    let dir = std::env::temp_dir().join(format!("audit-{}", std::process::id()));
+    fs::create_dir_all(&dir).unwrap();
+    let log = dir.join("audit.jsonl");
+
+    // chain of files linked as challenges & responses are, public key ending each response
+    let public_key = vec![7u8; 16];
+    let mut header = blank_hash().as_slice().to_vec();
+    let mut contributions = vec![];
+    for i in 0..3 {
+        let challenge = [header.clone(), vec![i; 32]].concat();
+        let response = [
+            hex::decode(hash_hex(&challenge)).unwrap(),
+            public_key.clone(),
+        ]
+        .concat();
+        fs::write(dir.join(format!("challenge_{}", i)), &challenge).unwrap();
+        fs::write(dir.join(format!("response_{}", i)), &response).unwrap();
+        header = hex::decode(hash_hex(&response)).unwrap();
+
+        contributions.push(Contribution {
+            challenge: format!("challenge_{}", i),
+            challenge_hash: hash_hex(&challenge),
+            response: format!("response_{}", i),
+            response_hash: hash_hex(&response),
+            public_key: Some(hex::encode(&public_key)),
+            verified: true,
+            error: None,
+        });
+    }
+
+    {
+        let mut writer = AuditLog::open(&log).unwrap();
+        writer.append(contributions[0].clone()).unwrap();
+    }
+    // reopened writer carries on after last entry
+    let mut writer = AuditLog::open(&log).unwrap();
+    writer.append(contributions[1].clone()).unwrap();
+    writer.append(contributions[2].clone()).unwrap();
+
+    let clean = audit(&log, &dir).unwrap();
+    assert_eq!(clean.entries.len(), 3);
+    assert_eq!(clean.findings, vec![]);
+
+    // files renamed into place round after round can't be logged
+    let next = ("challenge_3", hex::encode(&header));
+    assert!(writer
+        .check_names((next.0, &next.1), ("response_3", "00"))
+        .is_ok());
+    let renamed = writer.check_names(("challenge_0", &next.1), ("response_3", "00"));
+    assert!(matches!(renamed, Err(AuditError::Name { index: 0, .. })));
+    let reused = writer.check_names(
+        (next.0, &next.1),
+        ("response_2", &contributions[2].response_hash),
+    );
+    assert!(matches!(reused, Err(AuditError::Name { index: 2, .. })));
+
+    // interrupted while appending a fourth entry, half written line is left out, then cut off
+    let text = fs::read_to_string(&log).unwrap();
+    fs::write(&log, format!("{}{{\"index\":3,\"prev", text)).unwrap();
+    assert_eq!(audit(&log, &dir).unwrap().entries.len(), 3);
+    let mut writer = AuditLog::open(&log).unwrap();
+    assert_eq!(writer.len(), 3);
+    assert_eq!(fs::read_to_string(&log).unwrap(), text);
+
+    // response left compressed is copied as next challenge
+    let challenge = fs::read(dir.join("response_2")).unwrap();
+    let response = [
+        hex::decode(hash_hex(&challenge)).unwrap(),
+        public_key.clone(),
+    ]
+    .concat();
+    fs::write(dir.join("challenge_3"), &challenge).unwrap();
+    fs::write(dir.join("response_3"), &response).unwrap();
+    writer
+        .append(Contribution {
+            challenge: "challenge_3".to_owned(),
+            challenge_hash: hash_hex(&challenge),
+            response: "response_3".to_owned(),
+            response_hash: hash_hex(&response),
+            public_key: Some(hex::encode(&public_key)),
+            verified: true,
+            error: None,
+        })
+        .unwrap();
+    assert_eq!(audit(&log, &dir).unwrap().findings, vec![]);
+
+    let lines: Vec<String> = fs::read_to_string(&log)
+        .unwrap()
+        .lines()
+        .map(str::to_owned)
+        .collect();
+    let findings = |lines: &[&String]| {
+        let tampered = dir.join("tampered.jsonl");
+        let text: Vec<&str> = lines.iter().map(|l| l.as_str()).collect();
+        fs::write(&tampered, text.join("\n")).unwrap();
+        audit(&tampered, &dir).unwrap().findings
+    };
+
+    // gap
+    let gap = findings(&[&lines[0], &lines[2]]);
+    assert!(gap.contains(&Finding {
+        line: 2,
+        problem: Problem::Index {
+            expected: 1,
+            found: 2
+        }
+    }));
+    assert!(gap.contains(&Finding {
+        line: 2,
+        problem: Problem::ChallengeChain
+    }));
+
+    // reordering
+    let reordered = findings(&[&lines[1], &lines[0], &lines[2]]);
+    assert!(reordered.contains(&Finding {
+        line: 1,
+        problem: Problem::BrokenLink
+    }));
+
+    // modification
+    let modified = lines[1].replace("\"error\":null", "\"error\":\"none\"");
+    assert_eq!(
+        findings(&[&lines[0], &modified, &lines[2]]),
+        vec![Finding {
+            line: 2,
+            problem: Problem::Modified
+        }]
+    );
+
+    // transcript files are checked too
+    fs::write(dir.join("response_2"), b"forged").unwrap();
+    let forged = audit(&log, &dir).unwrap().findings;
+    assert!(forged.contains(&Finding {
+        line: 3,
+        problem: Problem::FileHash {
+            path: "response_2".to_owned()
+        }
+    }));
+
+    fs::remove_dir_all(dir).unwrap();
+}
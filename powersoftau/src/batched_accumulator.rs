@@ -42,7 +42,7 @@ pub enum AccumulatorState {
 ///
 /// * (τ, τ<sup>2</sup>, ..., τ<sup>2<sup>22</sup> - 2</sup>, α, ατ, ατ<sup>2</sup>, ..., ατ<sup>2<sup>21</sup> - 1</sup>, β, βτ, βτ<sup>2</sup>, ..., βτ<sup>2<sup>21</sup> - 1</sup>)<sub>1</sub>
 /// * (β, τ, τ<sup>2</sup>, ..., τ<sup>2<sup>21</sup> - 1</sup>)<sub>2</sub>
-pub struct BachedAccumulator<E: Engine, P: PowersOfTauParameters> {
+pub struct BachedAccumulator<E: Engine> {
     /// tau^0, tau^1, tau^2, ..., tau^{TAU_POWERS_G1_LENGTH - 1}
     pub tau_powers_g1: Vec<E::G1Affine>,
     /// tau^0, tau^1, tau^2, ..., tau^{TAU_POWERS_LENGTH - 1}
@@ -55,11 +55,11 @@ pub struct BachedAccumulator<E: Engine, P: PowersOfTauParameters> {
     pub beta_g2: E::G2Affine,
     /// Hash chain hash
     pub hash: GenericArray<u8, U64>,
-    /// Keep parameters here as a marker
-    marker: std::marker::PhantomData<P>,
+    /// Sizes of the ceremony
+    pub parameters: CeremonyParameters,
 }
 
-impl<E: Engine, P: PowersOfTauParameters> BachedAccumulator<E, P> {
+impl<E: Engine> BachedAccumulator<E> {
     /// Calcualte the contibution hash from the resulting file. Original powers of tau implementaiton
     /// used a specially formed writer to write to the file and calculate a hash on the fly, but memory-constrained
     /// implementation now writes without a particular order, so plain recalculation at the end
@@ -75,8 +75,8 @@ impl<E: Engine, P: PowersOfTauParameters> BachedAccumulator<E, P> {
     }
 }
 
-impl<E: Engine, P: PowersOfTauParameters> BachedAccumulator<E, P> {
-    pub fn empty() -> Self {
+impl<E: Engine> BachedAccumulator<E> {
+    pub fn empty(parameters: &CeremonyParameters) -> Self {
         Self {
             tau_powers_g1: vec![],
             tau_powers_g2: vec![],
@@ -84,40 +84,40 @@ impl<E: Engine, P: PowersOfTauParameters> BachedAccumulator<E, P> {
             beta_tau_powers_g1: vec![],
             beta_g2: E::G2Affine::zero(),
             hash: blank_hash(),
-            marker: std::marker::PhantomData::<P> {},
+            parameters: *parameters,
         }
     }
 }
 
-impl<E: Engine, P: PowersOfTauParameters> BachedAccumulator<E, P> {
-    fn g1_size(compression: UseCompression) -> usize {
+impl<E: Engine> BachedAccumulator<E> {
+    fn g1_size(&self, compression: UseCompression) -> usize {
         match compression {
             UseCompression::Yes => {
-                return P::G1_COMPRESSED_BYTE_SIZE;
+                return self.parameters.g1_compressed_byte_size;
             }
             UseCompression::No => {
-                return P::G1_UNCOMPRESSED_BYTE_SIZE;
+                return self.parameters.g1_uncompressed_byte_size;
             }
         }
     }
 
-    fn g2_size(compression: UseCompression) -> usize {
+    fn g2_size(&self, compression: UseCompression) -> usize {
         match compression {
             UseCompression::Yes => {
-                return P::G2_COMPRESSED_BYTE_SIZE;
+                return self.parameters.g2_compressed_byte_size;
             }
             UseCompression::No => {
-                return P::G2_UNCOMPRESSED_BYTE_SIZE;
+                return self.parameters.g2_uncompressed_byte_size;
             }
         }
     }
 
-    fn get_size(element_type: ElementType, compression: UseCompression) -> usize {
+    fn get_size(&self, element_type: ElementType, compression: UseCompression) -> usize {
         let size = match element_type {
             ElementType::AlphaG1 | ElementType::BetaG1 | ElementType::TauG1 => {
-                Self::g1_size(compression)
+                self.g1_size(compression)
             }
-            ElementType::BetaG2 | ElementType::TauG2 => Self::g2_size(compression),
+            ElementType::BetaG2 | ElementType::TauG2 => self.g2_size(compression),
         };
 
         size
@@ -133,22 +133,23 @@ impl<E: Engine, P: PowersOfTauParameters> BachedAccumulator<E, P> {
     /// Public key appended to the end of file, but it's irrelevant for an accumulator itself
 
     fn calculate_mmap_position(
+        &self,
         index: usize,
         element_type: ElementType,
         compression: UseCompression,
     ) -> usize {
-        let g1_size = Self::g1_size(compression);
-        let g2_size = Self::g2_size(compression);
-        let required_tau_g1_power = P::TAU_POWERS_G1_LENGTH;
-        let required_power = P::TAU_POWERS_LENGTH;
+        let g1_size = self.g1_size(compression);
+        let g2_size = self.g2_size(compression);
+        let required_tau_g1_power = self.parameters.tau_powers_g1_length();
+        let required_power = self.parameters.tau_powers_length();
         let position = match element_type {
             ElementType::TauG1 => {
                 let mut position = 0;
                 position += g1_size * index;
                 assert!(
-                    index < P::TAU_POWERS_G1_LENGTH,
+                    index < self.parameters.tau_powers_g1_length(),
                     "index of TauG1 element written must not exceed {}, while it's {}",
-                    P::TAU_POWERS_G1_LENGTH,
+                    self.parameters.tau_powers_g1_length(),
                     index
                 );
 
@@ -158,9 +159,9 @@ impl<E: Engine, P: PowersOfTauParameters> BachedAccumulator<E, P> {
                 let mut position = 0;
                 position += g1_size * required_tau_g1_power;
                 assert!(
-                    index < P::TAU_POWERS_LENGTH,
+                    index < self.parameters.tau_powers_length(),
                     "index of TauG2 element written must not exceed {}, while it's {}",
-                    P::TAU_POWERS_LENGTH,
+                    self.parameters.tau_powers_length(),
                     index
                 );
                 position += g2_size * index;
@@ -172,9 +173,9 @@ impl<E: Engine, P: PowersOfTauParameters> BachedAccumulator<E, P> {
                 position += g1_size * required_tau_g1_power;
                 position += g2_size * required_power;
                 assert!(
-                    index < P::TAU_POWERS_LENGTH,
+                    index < self.parameters.tau_powers_length(),
                     "index of AlphaG1 element written must not exceed {}, while it's {}",
-                    P::TAU_POWERS_LENGTH,
+                    self.parameters.tau_powers_length(),
                     index
                 );
                 position += g1_size * index;
@@ -187,9 +188,9 @@ impl<E: Engine, P: PowersOfTauParameters> BachedAccumulator<E, P> {
                 position += g2_size * required_power;
                 position += g1_size * required_power;
                 assert!(
-                    index < P::TAU_POWERS_LENGTH,
+                    index < self.parameters.tau_powers_length(),
                     "index of BetaG1 element written must not exceed {}, while it's {}",
-                    P::TAU_POWERS_LENGTH,
+                    self.parameters.tau_powers_length(),
                     index
                 );
                 position += g1_size * index;
@@ -207,12 +208,13 @@ impl<E: Engine, P: PowersOfTauParameters> BachedAccumulator<E, P> {
             }
         };
 
-        position + P::HASH_SIZE
+        position + self.parameters.hash_size()
     }
 }
 
-impl<E: Engine, P: PowersOfTauParameters> BachedAccumulator<E, P> {
+impl<E: Engine> BachedAccumulator<E> {
     /// Verifies a transformation of the `Accumulator` with the `PublicKey`, given a 64-byte transcript `digest`.
+    /// Reports every check performed, or the first one that failed.
     pub fn verify_transformation(
         input_map: &Mmap,
         output_map: &Mmap,
@@ -222,10 +224,48 @@ impl<E: Engine, P: PowersOfTauParameters> BachedAccumulator<E, P> {
         output_is_compressed: UseCompression,
         check_input_for_correctness: CheckForCorrectness,
         check_output_for_correctness: CheckForCorrectness,
-    ) -> bool {
+        parameters: &CeremonyParameters,
+    ) -> Result<VerificationReport, VerificationError> {
+        let mut report = VerificationReport::default();
+        Self::verify_transformation_with_report(
+            input_map,
+            output_map,
+            key,
+            digest,
+            input_is_compressed,
+            output_is_compressed,
+            check_input_for_correctness,
+            check_output_for_correctness,
+            parameters,
+            &mut report,
+        )?;
+
+        Ok(report)
+    }
+
+    /// Same as `verify_transformation`, but fills `report` as it goes, so checks performed before
+    /// a failure are kept too.
+    pub fn verify_transformation_with_report(
+        input_map: &Mmap,
+        output_map: &Mmap,
+        key: &PublicKey<E>,
+        digest: &[u8],
+        input_is_compressed: UseCompression,
+        output_is_compressed: UseCompression,
+        check_input_for_correctness: CheckForCorrectness,
+        check_output_for_correctness: CheckForCorrectness,
+        parameters: &CeremonyParameters,
+        report: &mut VerificationReport,
+    ) -> Result<(), VerificationError> {
         use itertools::MinMaxResult::MinMax;
         assert_eq!(digest.len(), 64);
 
+        let scope = |check, element, chunk| CheckScope {
+            check,
+            element,
+            chunk,
+        };
+
         let tau_g2_s = compute_g2_s::<E>(&digest, &key.tau_g1.0, &key.tau_g1.1, 0);
         let alpha_g2_s = compute_g2_s::<E>(&digest, &key.alpha_g1.0, &key.alpha_g1.1, 1);
         let beta_g2_s = compute_g2_s::<E>(&digest, &key.beta_g1.0, &key.beta_g1.1, 2);
@@ -233,90 +273,137 @@ impl<E: Engine, P: PowersOfTauParameters> BachedAccumulator<E, P> {
         // Check the proofs-of-knowledge for tau/alpha/beta
 
         // g1^s / g1^(s*x) = g2^s / g2^(s*x)
-        if !same_ratio(key.tau_g1, (tau_g2_s, key.tau_g2)) {
-            println!("Invalid ratio key.tau_g1, (tau_g2_s, key.tau_g2)");
-            return false;
-        }
-        if !same_ratio(key.alpha_g1, (alpha_g2_s, key.alpha_g2)) {
-            println!("Invalid ratio key.alpha_g1, (alpha_g2_s, key.alpha_g2)");
-            return false;
-        }
-        if !same_ratio(key.beta_g1, (beta_g2_s, key.beta_g2)) {
-            println!("Invalid ratio key.beta_g1, (beta_g2_s, key.beta_g2)");
-            return false;
-        }
+        report.require(
+            || same_ratio(key.tau_g1, (tau_g2_s, key.tau_g2)),
+            scope(
+                VerificationCheck::TauProofOfKnowledge,
+                ElementType::TauG1,
+                None,
+            ),
+        )?;
+        report.require(
+            || same_ratio(key.alpha_g1, (alpha_g2_s, key.alpha_g2)),
+            scope(
+                VerificationCheck::AlphaProofOfKnowledge,
+                ElementType::AlphaG1,
+                None,
+            ),
+        )?;
+        report.require(
+            || same_ratio(key.beta_g1, (beta_g2_s, key.beta_g2)),
+            scope(
+                VerificationCheck::BetaProofOfKnowledge,
+                ElementType::BetaG1,
+                None,
+            ),
+        )?;
 
         // Load accumulators AND perform computations
 
-        let mut before = Self::empty();
-        let mut after = Self::empty();
+        let mut before = Self::empty(parameters);
+        let mut after = Self::empty(parameters);
 
-        // these checks only touch a part of the accumulator, so read two elements
-
-        {
-            let chunk_size = 2;
+        let read_chunks = |before: &mut Self,
+                           after: &mut Self,
+                           start: usize,
+                           size: usize|
+         -> Result<(), VerificationError> {
+            let chunk = start..start + size;
             before
                 .read_chunk(
-                    0,
-                    chunk_size,
+                    start,
+                    size,
                     input_is_compressed,
                     check_input_for_correctness,
                     &input_map,
                 )
-                .expect("must read a first chunk from `challenge`");
+                .map_err(|error| VerificationError::Challenge {
+                    chunk: chunk.clone(),
+                    error,
+                })?;
             after
                 .read_chunk(
-                    0,
-                    chunk_size,
+                    start,
+                    size,
                     output_is_compressed,
                     check_output_for_correctness,
                     &output_map,
                 )
-                .expect("must read a first chunk from `response`");
+                .map_err(|error| VerificationError::Response { chunk, error })
+        };
+
+        // these checks only touch a part of the accumulator, so read two elements
+
+        {
+            read_chunks(&mut before, &mut after, 0, 2)?;
 
             // Check the correctness of the generators for tau powers
-            if after.tau_powers_g1[0] != E::G1Affine::one() {
-                println!("tau_powers_g1[0] != 1");
-                return false;
-            }
-            if after.tau_powers_g2[0] != E::G2Affine::one() {
-                println!("tau_powers_g2[0] != 1");
-                return false;
-            }
+            report.require(
+                || after.tau_powers_g1[0] == E::G1Affine::one(),
+                scope(VerificationCheck::Generator, ElementType::TauG1, Some(0..1)),
+            )?;
+            report.require(
+                || after.tau_powers_g2[0] == E::G2Affine::one(),
+                scope(VerificationCheck::Generator, ElementType::TauG2, Some(0..1)),
+            )?;
 
             // Did the participant multiply the previous tau by the new one?
-            if !same_ratio(
-                (before.tau_powers_g1[1], after.tau_powers_g1[1]),
-                (tau_g2_s, key.tau_g2),
-            ) {
-                println!("Invalid ratio (before.tau_powers_g1[1], after.tau_powers_g1[1]), (tau_g2_s, key.tau_g2)");
-                return false;
-            }
+            report.require(
+                || {
+                    same_ratio(
+                        (before.tau_powers_g1[1], after.tau_powers_g1[1]),
+                        (tau_g2_s, key.tau_g2),
+                    )
+                },
+                scope(
+                    VerificationCheck::ContributionRatio,
+                    ElementType::TauG1,
+                    Some(1..2),
+                ),
+            )?;
 
             // Did the participant multiply the previous alpha by the new one?
-            if !same_ratio(
-                (before.alpha_tau_powers_g1[0], after.alpha_tau_powers_g1[0]),
-                (alpha_g2_s, key.alpha_g2),
-            ) {
-                println!("Invalid ratio (before.alpha_tau_powers_g1[0], after.alpha_tau_powers_g1[0]), (alpha_g2_s, key.alpha_g2)");
-                return false;
-            }
+            report.require(
+                || {
+                    same_ratio(
+                        (before.alpha_tau_powers_g1[0], after.alpha_tau_powers_g1[0]),
+                        (alpha_g2_s, key.alpha_g2),
+                    )
+                },
+                scope(
+                    VerificationCheck::ContributionRatio,
+                    ElementType::AlphaG1,
+                    Some(0..1),
+                ),
+            )?;
 
             // Did the participant multiply the previous beta by the new one?
-            if !same_ratio(
-                (before.beta_tau_powers_g1[0], after.beta_tau_powers_g1[0]),
-                (beta_g2_s, key.beta_g2),
-            ) {
-                println!("Invalid ratio (before.beta_tau_powers_g1[0], after.beta_tau_powers_g1[0]), (beta_g2_s, key.beta_g2)");
-                return false;
-            }
-            if !same_ratio(
-                (before.beta_tau_powers_g1[0], after.beta_tau_powers_g1[0]),
-                (before.beta_g2, after.beta_g2),
-            ) {
-                println!("Invalid ratio (before.beta_tau_powers_g1[0], after.beta_tau_powers_g1[0]), (before.beta_g2, after.beta_g2)");
-                return false;
-            }
+            report.require(
+                || {
+                    same_ratio(
+                        (before.beta_tau_powers_g1[0], after.beta_tau_powers_g1[0]),
+                        (beta_g2_s, key.beta_g2),
+                    )
+                },
+                scope(
+                    VerificationCheck::ContributionRatio,
+                    ElementType::BetaG1,
+                    Some(0..1),
+                ),
+            )?;
+            report.require(
+                || {
+                    same_ratio(
+                        (before.beta_tau_powers_g1[0], after.beta_tau_powers_g1[0]),
+                        (before.beta_g2, after.beta_g2),
+                    )
+                },
+                scope(
+                    VerificationCheck::ContributionRatio,
+                    ElementType::BetaG2,
+                    Some(0..1),
+                ),
+            )?;
         }
 
         let tau_powers_g2_0 = after.tau_powers_g2[0].clone();
@@ -328,74 +415,73 @@ impl<E: Engine, P: PowersOfTauParameters> BachedAccumulator<E, P> {
         // one does not need to care about some overlapping
 
         let mut tau_powers_last_first_chunks = vec![E::G1Affine::zero(); 2];
-        for chunk in &(0..P::TAU_POWERS_LENGTH)
+        for chunk in &(0..parameters.tau_powers_length())
             .into_iter()
-            .chunks(P::EMPIRICAL_BATCH_SIZE)
+            .chunks(parameters.empirical_batch_size)
         {
             if let MinMax(start, end) = chunk.minmax() {
                 // extra 1 to ensure intersection between chunks and ensure we don't overflow
                 let size = end - start
                     + 1
-                    + if end == P::TAU_POWERS_LENGTH - 1 {
+                    + if end == parameters.tau_powers_length() - 1 {
                         0
                     } else {
                         1
                     };
-                before
-                    .read_chunk(
-                        start,
-                        size,
-                        input_is_compressed,
-                        check_input_for_correctness,
-                        &input_map,
-                    )
-                    .expect(&format!(
-                        "must read a chunk from {} to {} from `challenge`",
-                        start, end
-                    ));
-                after
-                    .read_chunk(
-                        start,
-                        size,
-                        output_is_compressed,
-                        check_output_for_correctness,
-                        &output_map,
-                    )
-                    .expect(&format!(
-                        "must read a chunk from {} to {} from `response`",
-                        start, end
-                    ));
+                read_chunks(&mut before, &mut after, start, size)?;
+                report.chunks += 1;
+                let chunk = Some(start..start + size);
 
                 // Are the powers of tau correct?
-                if !same_ratio(
-                    power_pairs(&after.tau_powers_g1),
-                    (tau_powers_g2_0, tau_powers_g2_1),
-                ) {
-                    println!("Invalid ratio power_pairs(&after.tau_powers_g1), (tau_powers_g2_0, tau_powers_g2_1)");
-                    return false;
-                }
-                if !same_ratio(
-                    power_pairs(&after.tau_powers_g2),
-                    (tau_powers_g1_0, tau_powers_g1_1),
-                ) {
-                    println!("Invalid ratio power_pairs(&after.tau_powers_g2), (tau_powers_g1_0, tau_powers_g1_1)");
-                    return false;
-                }
-                if !same_ratio(
-                    power_pairs(&after.alpha_tau_powers_g1),
-                    (tau_powers_g2_0, tau_powers_g2_1),
-                ) {
-                    println!("Invalid ratio power_pairs(&after.alpha_tau_powers_g1), (tau_powers_g2_0, tau_powers_g2_1)");
-                    return false;
-                }
-                if !same_ratio(
-                    power_pairs(&after.beta_tau_powers_g1),
-                    (tau_powers_g2_0, tau_powers_g2_1),
-                ) {
-                    println!("Invalid ratio power_pairs(&after.beta_tau_powers_g1), (tau_powers_g2_0, tau_powers_g2_1)");
-                    return false;
-                }
-                if end == P::TAU_POWERS_LENGTH - 1 {
+                report.require(
+                    || {
+                        same_ratio(
+                            power_pairs(&after.tau_powers_g1),
+                            (tau_powers_g2_0, tau_powers_g2_1),
+                        )
+                    },
+                    scope(
+                        VerificationCheck::PowersRatio,
+                        ElementType::TauG1,
+                        chunk.clone(),
+                    ),
+                )?;
+                report.require(
+                    || {
+                        same_ratio(
+                            power_pairs(&after.tau_powers_g2),
+                            (tau_powers_g1_0, tau_powers_g1_1),
+                        )
+                    },
+                    scope(
+                        VerificationCheck::PowersRatio,
+                        ElementType::TauG2,
+                        chunk.clone(),
+                    ),
+                )?;
+                report.require(
+                    || {
+                        same_ratio(
+                            power_pairs(&after.alpha_tau_powers_g1),
+                            (tau_powers_g2_0, tau_powers_g2_1),
+                        )
+                    },
+                    scope(
+                        VerificationCheck::PowersRatio,
+                        ElementType::AlphaG1,
+                        chunk.clone(),
+                    ),
+                )?;
+                report.require(
+                    || {
+                        same_ratio(
+                            power_pairs(&after.beta_tau_powers_g1),
+                            (tau_powers_g2_0, tau_powers_g2_1),
+                        )
+                    },
+                    scope(VerificationCheck::PowersRatio, ElementType::BetaG1, chunk),
+                )?;
+                if end == parameters.tau_powers_length() - 1 {
                     tau_powers_last_first_chunks[0] = after.tau_powers_g1[size - 1];
                 }
                 println!("Done processing {} powers of tau", end);
@@ -404,43 +490,21 @@ impl<E: Engine, P: PowersOfTauParameters> BachedAccumulator<E, P> {
             }
         }
 
-        for chunk in &(P::TAU_POWERS_LENGTH..P::TAU_POWERS_G1_LENGTH)
+        for chunk in &(parameters.tau_powers_length()..parameters.tau_powers_g1_length())
             .into_iter()
-            .chunks(P::EMPIRICAL_BATCH_SIZE)
+            .chunks(parameters.empirical_batch_size)
         {
             if let MinMax(start, end) = chunk.minmax() {
                 // extra 1 to ensure intersection between chunks and ensure we don't overflow
                 let size = end - start
                     + 1
-                    + if end == P::TAU_POWERS_G1_LENGTH - 1 {
+                    + if end == parameters.tau_powers_g1_length() - 1 {
                         0
                     } else {
                         1
                     };
-                before
-                    .read_chunk(
-                        start,
-                        size,
-                        input_is_compressed,
-                        check_input_for_correctness,
-                        &input_map,
-                    )
-                    .expect(&format!(
-                        "must read a chunk from {} to {} from `challenge`",
-                        start, end
-                    ));
-                after
-                    .read_chunk(
-                        start,
-                        size,
-                        output_is_compressed,
-                        check_output_for_correctness,
-                        &output_map,
-                    )
-                    .expect(&format!(
-                        "must read a chunk from {} to {} from `response`",
-                        start, end
-                    ));
+                read_chunks(&mut before, &mut after, start, size)?;
+                report.chunks += 1;
 
                 assert_eq!(
                     before.tau_powers_g2.len(),
@@ -454,14 +518,20 @@ impl<E: Engine, P: PowersOfTauParameters> BachedAccumulator<E, P> {
                 );
 
                 // Are the powers of tau correct?
-                if !same_ratio(
-                    power_pairs(&after.tau_powers_g1),
-                    (tau_powers_g2_0, tau_powers_g2_1),
-                ) {
-                    println!("Invalid ratio power_pairs(&after.tau_powers_g1), (tau_powers_g2_0, tau_powers_g2_1) in extra TauG1 contribution");
-                    return false;
-                }
-                if start == P::TAU_POWERS_LENGTH {
+                report.require(
+                    || {
+                        same_ratio(
+                            power_pairs(&after.tau_powers_g1),
+                            (tau_powers_g2_0, tau_powers_g2_1),
+                        )
+                    },
+                    scope(
+                        VerificationCheck::PowersRatio,
+                        ElementType::TauG1,
+                        Some(start..start + size),
+                    ),
+                )?;
+                if start == parameters.tau_powers_length() {
                     tau_powers_last_first_chunks[1] = after.tau_powers_g1[0];
                 }
                 println!("Done processing {} powers of tau", end);
@@ -469,27 +539,40 @@ impl<E: Engine, P: PowersOfTauParameters> BachedAccumulator<E, P> {
                 panic!("Chunk does not have a min and max");
             }
         }
-        if !same_ratio(
-            power_pairs(&tau_powers_last_first_chunks),
-            (tau_powers_g2_0, tau_powers_g2_1),
-        ) {
-            println!("Invalid ratio power_pairs(&after.tau_powers_g1), (tau_powers_g2_0, tau_powers_g2_1) in TauG1 contribution intersection");
-        }
-        true
+
+        // both loops above check TauG1 powers within their own range, so ratio across boundary
+        // between them is left to check
+        let boundary = parameters.tau_powers_length();
+        report.require(
+            || {
+                same_ratio(
+                    power_pairs(&tau_powers_last_first_chunks),
+                    (tau_powers_g2_0, tau_powers_g2_1),
+                )
+            },
+            scope(
+                VerificationCheck::PowersRatio,
+                ElementType::TauG1,
+                Some(boundary - 1..boundary + 1),
+            ),
+        )?;
+
+        Ok(())
     }
 
     pub fn decompress(
         input_map: &Mmap,
         output_map: &mut MmapMut,
         check_input_for_correctness: CheckForCorrectness,
+        parameters: &CeremonyParameters,
     ) -> io::Result<()> {
         use itertools::MinMaxResult::MinMax;
 
-        let mut accumulator = Self::empty();
+        let mut accumulator = Self::empty(parameters);
 
-        for chunk in &(0..P::TAU_POWERS_LENGTH)
+        for chunk in &(0..parameters.tau_powers_length())
             .into_iter()
-            .chunks(P::EMPIRICAL_BATCH_SIZE)
+            .chunks(parameters.empirical_batch_size)
         {
             if let MinMax(start, end) = chunk.minmax() {
                 let size = end - start + 1;
@@ -511,9 +594,9 @@ impl<E: Engine, P: PowersOfTauParameters> BachedAccumulator<E, P> {
             }
         }
 
-        for chunk in &(P::TAU_POWERS_LENGTH..P::TAU_POWERS_G1_LENGTH)
+        for chunk in &(parameters.tau_powers_length()..parameters.tau_powers_g1_length())
             .into_iter()
-            .chunks(P::EMPIRICAL_BATCH_SIZE)
+            .chunks(parameters.empirical_batch_size)
         {
             if let MinMax(start, end) = chunk.minmax() {
                 let size = end - start + 1;
@@ -555,7 +638,7 @@ impl<E: Engine, P: PowersOfTauParameters> BachedAccumulator<E, P> {
     }
 }
 
-impl<E: Engine, P: PowersOfTauParameters> BachedAccumulator<E, P> {
+impl<E: Engine> BachedAccumulator<E> {
     pub fn read_chunk(
         &mut self,
         from: usize,
@@ -694,7 +777,7 @@ impl<E: Engine, P: PowersOfTauParameters> BachedAccumulator<E, P> {
             let index = from + i;
             match element_type {
                 ElementType::TauG1 => {
-                    if index >= P::TAU_POWERS_G1_LENGTH {
+                    if index >= self.parameters.tau_powers_g1_length() {
                         return Ok(vec![]);
                     }
                 }
@@ -702,13 +785,13 @@ impl<E: Engine, P: PowersOfTauParameters> BachedAccumulator<E, P> {
                 | ElementType::BetaG1
                 | ElementType::BetaG2
                 | ElementType::TauG2 => {
-                    if index >= P::TAU_POWERS_LENGTH {
+                    if index >= self.parameters.tau_powers_length() {
                         return Ok(vec![]);
                     }
                 }
             };
-            let position = Self::calculate_mmap_position(index, element_type, compression);
-            let element_size = Self::get_size(element_type, compression);
+            let position = self.calculate_mmap_position(index, element_type, compression);
+            let element_size = self.get_size(element_type, compression);
             let mut memory_slice = input_map
                 .get(position..position + element_size)
                 .expect("must read point data from file");
@@ -794,7 +877,7 @@ impl<E: Engine, P: PowersOfTauParameters> BachedAccumulator<E, P> {
     }
 }
 
-impl<E: Engine, P: PowersOfTauParameters> BachedAccumulator<E, P> {
+impl<E: Engine> BachedAccumulator<E> {
     fn write_all(
         &mut self,
         chunk_start: usize,
@@ -857,7 +940,7 @@ impl<E: Engine, P: PowersOfTauParameters> BachedAccumulator<E, P> {
     {
         match element_type {
             ElementType::TauG1 => {
-                if index >= P::TAU_POWERS_G1_LENGTH {
+                if index >= self.parameters.tau_powers_g1_length() {
                     return Ok(());
                 }
             }
@@ -865,7 +948,7 @@ impl<E: Engine, P: PowersOfTauParameters> BachedAccumulator<E, P> {
             | ElementType::BetaG1
             | ElementType::BetaG2
             | ElementType::TauG2 => {
-                if index >= P::TAU_POWERS_LENGTH {
+                if index >= self.parameters.tau_powers_length() {
                     return Ok(());
                 }
             }
@@ -873,12 +956,12 @@ impl<E: Engine, P: PowersOfTauParameters> BachedAccumulator<E, P> {
 
         match compression {
             UseCompression::Yes => {
-                let position = Self::calculate_mmap_position(index, element_type, compression);
+                let position = self.calculate_mmap_position(index, element_type, compression);
                 // let size = self.get_size(element_type, compression);
                 (&mut output_map[position..]).write(p.into_compressed().as_ref())?;
             }
             UseCompression::No => {
-                let position = Self::calculate_mmap_position(index, element_type, compression);
+                let position = self.calculate_mmap_position(index, element_type, compression);
                 // let size = self.get_size(element_type, compression);
                 (&mut output_map[position..]).write(p.into_uncompressed().as_ref())?;
             }
@@ -895,7 +978,7 @@ impl<E: Engine, P: PowersOfTauParameters> BachedAccumulator<E, P> {
         output_map: &mut MmapMut,
     ) -> io::Result<()> {
         self.write_all(chunk_start, compression, ElementType::TauG1, output_map)?;
-        if chunk_start < P::TAU_POWERS_LENGTH {
+        if chunk_start < self.parameters.tau_powers_length() {
             self.write_all(chunk_start, compression, ElementType::TauG2, output_map)?;
             self.write_all(chunk_start, compression, ElementType::AlphaG1, output_map)?;
             self.write_all(chunk_start, compression, ElementType::BetaG1, output_map)?;
@@ -906,7 +989,7 @@ impl<E: Engine, P: PowersOfTauParameters> BachedAccumulator<E, P> {
     }
 }
 
-impl<E: Engine, P: PowersOfTauParameters> BachedAccumulator<E, P> {
+impl<E: Engine> BachedAccumulator<E> {
     /// Transforms the accumulator with a private key.
     /// Due to large amount of data in a previous accumulator even in the compressed form
     /// this function can now work on compressed input. Output can be made in any form
@@ -920,6 +1003,7 @@ impl<E: Engine, P: PowersOfTauParameters> BachedAccumulator<E, P> {
         compress_the_output: UseCompression,
         check_input_for_correctness: CheckForCorrectness,
         key: &PrivateKey<E>,
+        parameters: &CeremonyParameters,
     ) -> io::Result<()> {
         /// Exponentiate a large number of points, with an optional coefficient to be applied to the
         /// exponent.
@@ -978,13 +1062,13 @@ impl<E: Engine, P: PowersOfTauParameters> BachedAccumulator<E, P> {
             }
         }
 
-        let mut accumulator = Self::empty();
+        let mut accumulator = Self::empty(parameters);
 
         use itertools::MinMaxResult::MinMax;
 
-        for chunk in &(0..P::TAU_POWERS_LENGTH)
+        for chunk in &(0..parameters.tau_powers_length())
             .into_iter()
-            .chunks(P::EMPIRICAL_BATCH_SIZE)
+            .chunks(parameters.empirical_batch_size)
         {
             if let MinMax(start, end) = chunk.minmax() {
                 let size = end - start + 1;
@@ -1042,9 +1126,9 @@ impl<E: Engine, P: PowersOfTauParameters> BachedAccumulator<E, P> {
             }
         }
 
-        for chunk in &(P::TAU_POWERS_LENGTH..P::TAU_POWERS_G1_LENGTH)
+        for chunk in &(parameters.tau_powers_length()..parameters.tau_powers_g1_length())
             .into_iter()
-            .chunks(P::EMPIRICAL_BATCH_SIZE)
+            .chunks(parameters.empirical_batch_size)
         {
             if let MinMax(start, end) = chunk.minmax() {
                 let size = end - start + 1;
@@ -1100,17 +1184,18 @@ impl<E: Engine, P: PowersOfTauParameters> BachedAccumulator<E, P> {
     }
 }
 
-impl<E: Engine, P: PowersOfTauParameters> BachedAccumulator<E, P> {
+impl<E: Engine> BachedAccumulator<E> {
     /// Transforms the accumulator with a private key.
     pub fn generate_initial(
         output_map: &mut MmapMut,
         compress_the_output: UseCompression,
+        parameters: &CeremonyParameters,
     ) -> io::Result<()> {
         use itertools::MinMaxResult::MinMax;
 
-        for chunk in &(0..P::TAU_POWERS_LENGTH)
+        for chunk in &(0..parameters.tau_powers_length())
             .into_iter()
-            .chunks(P::EMPIRICAL_BATCH_SIZE)
+            .chunks(parameters.empirical_batch_size)
         {
             if let MinMax(start, end) = chunk.minmax() {
                 let size = end - start + 1;
@@ -1121,7 +1206,7 @@ impl<E: Engine, P: PowersOfTauParameters> BachedAccumulator<E, P> {
                     beta_tau_powers_g1: vec![E::G1Affine::one(); size],
                     beta_g2: E::G2Affine::one(),
                     hash: blank_hash(),
-                    marker: std::marker::PhantomData::<P> {},
+                    parameters: *parameters,
                 };
 
                 accumulator.write_chunk(start, compress_the_output, output_map)?;
@@ -1131,9 +1216,9 @@ impl<E: Engine, P: PowersOfTauParameters> BachedAccumulator<E, P> {
             }
         }
 
-        for chunk in &(P::TAU_POWERS_LENGTH..P::TAU_POWERS_G1_LENGTH)
+        for chunk in &(parameters.tau_powers_length()..parameters.tau_powers_g1_length())
             .into_iter()
-            .chunks(P::EMPIRICAL_BATCH_SIZE)
+            .chunks(parameters.empirical_batch_size)
         {
             if let MinMax(start, end) = chunk.minmax() {
                 let size = end - start + 1;
@@ -1144,7 +1229,7 @@ impl<E: Engine, P: PowersOfTauParameters> BachedAccumulator<E, P> {
                     beta_tau_powers_g1: vec![],
                     beta_g2: E::G2Affine::one(),
                     hash: blank_hash(),
-                    marker: std::marker::PhantomData::<P> {},
+                    parameters: *parameters,
                 };
 
                 accumulator.write_chunk(start, compress_the_output, output_map)?;
@@ -1157,3 +1242,224 @@ impl<E: Engine, P: PowersOfTauParameters> BachedAccumulator<E, P> {
         Ok(())
     }
 }
+
+/// Same functions taking sizes from a `PowersOfTauParameters` type, as they did before sizes
+/// became a runtime value. Callers written against it must switch to these `*_with` names, or
+/// pass `&P::parameters()` to the functions above.
+impl<E: Engine> BachedAccumulator<E> {
+    pub fn empty_with<P: PowersOfTauParameters>() -> Self {
+        Self::empty(&P::parameters())
+    }
+
+    pub fn verify_transformation_with<P: PowersOfTauParameters>(
+        input_map: &Mmap,
+        output_map: &Mmap,
+        key: &PublicKey<E>,
+        digest: &[u8],
+        input_is_compressed: UseCompression,
+        output_is_compressed: UseCompression,
+        check_input_for_correctness: CheckForCorrectness,
+        check_output_for_correctness: CheckForCorrectness,
+    ) -> Result<VerificationReport, VerificationError> {
+        Self::verify_transformation(
+            input_map,
+            output_map,
+            key,
+            digest,
+            input_is_compressed,
+            output_is_compressed,
+            check_input_for_correctness,
+            check_output_for_correctness,
+            &P::parameters(),
+        )
+    }
+
+    pub fn decompress_with<P: PowersOfTauParameters>(
+        input_map: &Mmap,
+        output_map: &mut MmapMut,
+        check_input_for_correctness: CheckForCorrectness,
+    ) -> io::Result<()> {
+        Self::decompress(
+            input_map,
+            output_map,
+            check_input_for_correctness,
+            &P::parameters(),
+        )
+    }
+
+    pub fn transform_with<P: PowersOfTauParameters>(
+        input_map: &Mmap,
+        output_map: &mut MmapMut,
+        input_is_compressed: UseCompression,
+        compress_the_output: UseCompression,
+        check_input_for_correctness: CheckForCorrectness,
+        key: &PrivateKey<E>,
+    ) -> io::Result<()> {
+        Self::transform(
+            input_map,
+            output_map,
+            input_is_compressed,
+            compress_the_output,
+            check_input_for_correctness,
+            key,
+            &P::parameters(),
+        )
+    }
+
+    pub fn generate_initial_with<P: PowersOfTauParameters>(
+        output_map: &mut MmapMut,
+        compress_the_output: UseCompression,
+    ) -> io::Result<()> {
+        Self::generate_initial(output_map, compress_the_output, &P::parameters())
+    }
+}
+
+/// Contribution made by tests: `challenge`, or an initial one if it's `None`, along with response
+/// of a fresh keypair to it, public key included.
+#[cfg(test)]
+pub(crate) struct TestContribution {
+    pub parameters: CeremonyParameters,
+    pub challenge: Mmap,
+    pub response: Mmap,
+    pub public_key: PublicKey<Bn256>,
+}
+
+/// Sizes of ceremony tests run, large enough for every core to get a share of each batch.
+#[cfg(test)]
+pub(crate) fn test_parameters() -> CeremonyParameters {
+    CeremonyParameters::bn256(8)
+}
+
+#[cfg(test)]
+pub(crate) fn test_contribution(
+    challenge: Option<Mmap>,
+    compress_the_output: UseCompression,
+) -> TestContribution {
+    use memmap::MmapOptions;
+    use rand::thread_rng;
+
+    let parameters = test_parameters();
+    let challenge = challenge.unwrap_or_else(|| {
+        let mut initial = MmapOptions::new()
+            .len(parameters.accumulator_byte_size())
+            .map_anon()
+            .unwrap();
+        (&mut initial[0..]).write(blank_hash().as_slice()).unwrap();
+        BachedAccumulator::<Bn256>::generate_initial(&mut initial, UseCompression::No, &parameters)
+            .unwrap();
+        initial.make_read_only().unwrap()
+    });
+    let digest = BachedAccumulator::<Bn256>::calculate_hash(&challenge);
+
+    let mut response = MmapOptions::new()
+        .len(parameters.response_size(compress_the_output))
+        .map_anon()
+        .unwrap();
+    (&mut response[0..]).write(digest.as_slice()).unwrap();
+    let (public_key, sk) = keypair::<_, Bn256>(&mut thread_rng(), digest.as_slice());
+    BachedAccumulator::<Bn256>::transform(
+        &challenge,
+        &mut response,
+        UseCompression::No,
+        compress_the_output,
+        CheckForCorrectness::No,
+        &sk,
+        &parameters,
+    )
+    .unwrap();
+    public_key
+        .write(&mut response, compress_the_output, &parameters)
+        .unwrap();
+
+    TestContribution {
+        parameters,
+        challenge,
+        response: response.make_read_only().unwrap(),
+        public_key,
+    }
+}
+
+#[test]
+fn test_verify_transformation_report() {
+    let TestContribution {
+        parameters,
+        challenge,
+        response,
+        public_key: pk,
+    } = test_contribution(None, UseCompression::No);
+    let digest = BachedAccumulator::<Bn256>::calculate_hash(&challenge);
+
+    let verify = |digest: &[u8]| {
+        BachedAccumulator::<Bn256>::verify_transformation(
+            &challenge,
+            &response,
+            &pk,
+            digest,
+            UseCompression::No,
+            UseCompression::No,
+            CheckForCorrectness::Yes,
+            CheckForCorrectness::Yes,
+            &parameters,
+        )
+    };
+
+    let report = verify(digest.as_slice()).unwrap();
+    assert!(report.chunks > 0);
+    assert!(report.checks.iter().all(|c| c.passed));
+    // ratio across the end of G2 powers is checked last
+    let boundary = parameters.tau_powers_length();
+    assert_eq!(
+        report.checks.last().map(|c| &c.scope),
+        Some(&CheckScope {
+            check: VerificationCheck::PowersRatio,
+            element: ElementType::TauG1,
+            chunk: Some(boundary - 1..boundary + 1),
+        })
+    );
+
+    // public key is bound to the digest it was made for
+    match verify(blank_hash().as_slice()) {
+        Err(VerificationError::Failed(scope)) => {
+            assert_eq!(scope.check, VerificationCheck::TauProofOfKnowledge);
+            assert_eq!(scope.chunk, None);
+        }
+        _ => panic!("public key must not verify against another digest"),
+    }
+
+    // G1 powers past those in G2, all scaled by one constant, keep their ratios within each
+    // chunk, so only ratio across the boundary catches them
+    use bellman::pairing::bn256::{Fr, G1Affine};
+    use memmap::MmapOptions;
+
+    let mut scaled = MmapOptions::new().len(response.len()).map_anon().unwrap();
+    scaled.copy_from_slice(&response);
+    let constant = Fr::from_str("5").unwrap().into_repr();
+    let size = parameters.g1_uncompressed_byte_size;
+    for index in boundary..parameters.tau_powers_g1_length() {
+        let position = parameters.hash_size() + index * size;
+        let mut repr = <G1Affine as CurveAffine>::Uncompressed::empty();
+        repr.as_mut()
+            .copy_from_slice(&scaled[position..position + size]);
+        let point = repr.into_affine().unwrap().mul(constant).into_affine();
+        scaled[position..position + size].copy_from_slice(point.into_uncompressed().as_ref());
+    }
+    let scaled = scaled.make_read_only().unwrap();
+    match BachedAccumulator::<Bn256>::verify_transformation(
+        &challenge,
+        &scaled,
+        &pk,
+        digest.as_slice(),
+        UseCompression::No,
+        UseCompression::No,
+        CheckForCorrectness::Yes,
+        CheckForCorrectness::Yes,
+        &parameters,
+    ) {
+        Err(VerificationError::Failed(scope)) => {
+            assert_eq!(scope.check, VerificationCheck::PowersRatio);
+            assert_eq!(scope.element, ElementType::TauG1);
+            assert_eq!(scope.chunk, Some(boundary - 1..boundary + 1));
+        }
+        _ => panic!("scaled G1 powers past the boundary must not verify"),
+    }
+}
@@ -0,0 +1,722 @@
+//! Sources of the random beacon the ceremony ends with, each checked before its value is used.
+//!
+//! * `hex`: a value as given, i.e. a block hash someone vouches for
+//! * `bitcoin`: an 80 byte Bitcoin block header, whose proof of work is checked, and whose hash,
+//!   in the byte order block explorers show it in, is the value. Headers of made up low
+//!   difficulty are cheap to mine, so hash of the announced block must be given too
+//! * `drand`: a drand round as its HTTP API serves it, whose BLS signature is checked against
+//!   public key of the drand network, and whose randomness is the value
+//!
+//! Value is then hashed `2^n` times over a `HashChain`, which keeps state at start of each of its
+//! `SEGMENTS` in a file, so that an interrupted beacon resumes, and anyone can check segments in
+//! parallel.
+
+use bls12_381::hash_to_curve::{ExpandMsgXmd, HashToCurve};
+use bls12_381::{pairing, G1Affine, G1Projective, G2Affine, G2Projective};
+use serde::{Deserialize, Serialize};
+use sha2::{Digest, Sha256};
+use std::fmt;
+use std::fs::{self, File, OpenOptions};
+use std::io::{self, Write};
+use std::path::Path;
+use std::sync::Mutex;
+
+/// Number of checkpointed segments of a hash chain.
+pub const SEGMENTS: u64 = 1024;
+
+const BITCOIN_HEADER_LENGTH: usize = 80;
+/// Compact target of minimum difficulty of Bitcoin, no block of its main chain has an easier one.
+const BITCOIN_MAX_TARGET_BITS: u32 = 0x1d00_ffff;
+/// Domain separation tags of drand schemes, signing on G2 and on G1 respectively.
+const DRAND_G2_DST: &[u8] = b"BLS_SIG_BLS12381G2_XMD:SHA-256_SSWU_RO_NUL_";
+const DRAND_G1_DST: &[u8] = b"BLS_SIG_BLS12381G1_XMD:SHA-256_SSWU_RO_NUL_";
+
+#[derive(Clone, Copy, Debug, PartialEq, Deserialize, Serialize)]
+#[serde(rename_all = "lowercase")]
+pub enum BeaconSource {
+    Hex,
+    Bitcoin,
+    Drand,
+}
+
+/// Beacon value, along with whatever vouches for it.
+#[derive(Clone, Debug, PartialEq, Serialize)]
+#[serde(tag = "source", rename_all = "lowercase")]
+pub enum Beacon {
+    Hex {
+        value: String,
+    },
+    Bitcoin {
+        /// hex encoded block hash
+        value: String,
+        /// hex encoded header
+        header: String,
+        /// compact encoding of proof of work target
+        bits: u32,
+        /// seconds since Unix epoch, as claimed by miner
+        timestamp: u32,
+    },
+    Drand {
+        /// hex encoded randomness of round
+        value: String,
+        round: u64,
+        signature: String,
+        /// absent for unchained drand networks
+        previous_signature: Option<String>,
+        public_key: String,
+    },
+}
+
+#[derive(Debug)]
+pub enum BeaconError {
+    Hex(hex::FromHexError),
+    Length {
+        what: &'static str,
+        expected: usize,
+        found: usize,
+    },
+    /// beacon isn't the value expected of it
+    Mismatch {
+        expected: String,
+        found: String,
+    },
+    ProofOfWork,
+    /// target is easier than any of Bitcoin's main chain
+    Difficulty(u32),
+    Json(serde_json::Error),
+    /// point of drand public key or signature isn't on its curve
+    Point(&'static str),
+    /// randomness of drand round isn't hash of its signature
+    Randomness,
+    Signature,
+}
+
+impl fmt::Display for BeaconError {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        match *self {
+            BeaconError::Hex(ref e) => write!(f, "Invalid hex: {}", e),
+            BeaconError::Length {
+                what,
+                expected,
+                found,
+            } => write!(f, "{} must be {} bytes, not {}", what, expected, found),
+            BeaconError::Mismatch {
+                ref expected,
+                ref found,
+            } => write!(f, "Beacon is {}, expected {}", found, expected),
+            BeaconError::ProofOfWork => write!(f, "Block hash doesn't meet its target"),
+            BeaconError::Difficulty(bits) => write!(
+                f,
+                "Target {:#010x} is easier than Bitcoin's minimum difficulty",
+                bits
+            ),
+            BeaconError::Json(ref e) => write!(f, "Invalid drand round: {}", e),
+            BeaconError::Point(what) => write!(f, "Invalid {}", what),
+            BeaconError::Randomness => write!(f, "Randomness isn't hash of signature"),
+            BeaconError::Signature => write!(f, "Signature doesn't verify with public key"),
+        }
+    }
+}
+
+impl From<hex::FromHexError> for BeaconError {
+    fn from(err: hex::FromHexError) -> BeaconError {
+        BeaconError::Hex(err)
+    }
+}
+
+impl From<serde_json::Error> for BeaconError {
+    fn from(err: serde_json::Error) -> BeaconError {
+        BeaconError::Json(err)
+    }
+}
+
+/// drand round, as served by `/public/<round>`.
+#[derive(Deserialize)]
+struct DrandRound {
+    round: u64,
+    randomness: String,
+    signature: String,
+    previous_signature: Option<String>,
+}
+
+impl Beacon {
+    pub fn hex(value: &str) -> Result<Beacon, BeaconError> {
+        decode(value, "beacon", 32)?;
+        Ok(Beacon::Hex {
+            value: value.trim_start_matches("0x").to_lowercase(),
+        })
+    }
+
+    /// Checks proof of work of `header`, given raw or hex encoded, and that its hash is
+    /// `expected`. Work alone doesn't vouch for a header, as one of Bitcoin's minimum difficulty
+    /// takes minutes to mine, so it's the expected hash, that of a block announced in advance,
+    /// which does.
+    pub fn bitcoin(header: &[u8], expected: &str) -> Result<Beacon, BeaconError> {
+        let header = if header.len() == BITCOIN_HEADER_LENGTH {
+            header.to_vec()
+        } else {
+            decode(
+                String::from_utf8_lossy(header).trim(),
+                "Bitcoin block header",
+                BITCOIN_HEADER_LENGTH,
+            )?
+        };
+
+        // block hash is double SHA-256 of header, read as a little endian number
+        let mut hash = Sha256::digest(&Sha256::digest(&header)).to_vec();
+        hash.reverse();
+        let bits = u32::from_le_bytes([header[72], header[73], header[74], header[75]]);
+        let easiest = target(BITCOIN_MAX_TARGET_BITS).expect("maximum target is valid");
+        match target(bits) {
+            Some(target) if target > easiest => return Err(BeaconError::Difficulty(bits)),
+            Some(target) if hash.as_slice() <= &target[..] => {}
+            _ => return Err(BeaconError::ProofOfWork),
+        }
+
+        let beacon = Beacon::Bitcoin {
+            value: hex::encode(&hash),
+            header: hex::encode(&header),
+            bits,
+            timestamp: u32::from_le_bytes([header[68], header[69], header[70], header[71]]),
+        };
+        beacon.expect(Some(expected))
+    }
+
+    /// Checks signature of drand `round` JSON with hex encoded `public_key` of its network, and
+    /// that its randomness is `expected`, if set. Rounds of chained networks are signed along
+    /// with previous signature, rounds of unchained ones alone.
+    pub fn drand(
+        round: &[u8],
+        public_key: &str,
+        expected: Option<&str>,
+    ) -> Result<Beacon, BeaconError> {
+        let round: DrandRound = serde_json::from_slice(round)?;
+        let randomness = hex::decode(&round.randomness)?;
+        let signature = hex::decode(&round.signature)?;
+        let key = hex::decode(public_key.trim_start_matches("0x"))?;
+
+        if Sha256::digest(&signature).as_slice() != randomness.as_slice() {
+            return Err(BeaconError::Randomness);
+        }
+
+        let mut message = Sha256::new();
+        if let Some(ref previous) = round.previous_signature {
+            message.update(hex::decode(previous)?);
+        }
+        message.update(round.round.to_be_bytes());
+        let message = message.finalize();
+
+        // networks either have keys on G1 and sign on G2, or the other way around
+        let verified = match key.len() {
+            48 => {
+                let key = g1(&key, "drand public key")?;
+                let signature = g2(&signature, "drand signature")?;
+                let hashed = <G2Projective as HashToCurve<ExpandMsgXmd<Sha256>>>::hash_to_curve(
+                    message,
+                    DRAND_G2_DST,
+                );
+                pairing(&G1Affine::generator(), &signature)
+                    == pairing(&key, &G2Affine::from(hashed))
+            }
+            96 => {
+                let key = g2(&key, "drand public key")?;
+                let signature = g1(&signature, "drand signature")?;
+                let hashed = <G1Projective as HashToCurve<ExpandMsgXmd<Sha256>>>::hash_to_curve(
+                    message,
+                    DRAND_G1_DST,
+                );
+                pairing(&signature, &G2Affine::generator())
+                    == pairing(&G1Affine::from(hashed), &key)
+            }
+            found => {
+                return Err(BeaconError::Length {
+                    what: "drand public key",
+                    expected: 48,
+                    found,
+                })
+            }
+        };
+        if !verified {
+            return Err(BeaconError::Signature);
+        }
+
+        let beacon = Beacon::Drand {
+            value: hex::encode(&randomness),
+            round: round.round,
+            signature: round.signature.to_lowercase(),
+            previous_signature: round.previous_signature.map(|s| s.to_lowercase()),
+            public_key: hex::encode(&key),
+        };
+        beacon.expect(expected)
+    }
+
+    /// 32 byte value of beacon.
+    pub fn value(&self) -> [u8; 32] {
+        let value = match *self {
+            Beacon::Hex { ref value }
+            | Beacon::Bitcoin { ref value, .. }
+            | Beacon::Drand { ref value, .. } => value,
+        };
+        let mut bytes = [0u8; 32];
+        bytes.copy_from_slice(&hex::decode(value).expect("beacon values are checked hex"));
+        bytes
+    }
+
+    fn expect(self, expected: Option<&str>) -> Result<Beacon, BeaconError> {
+        if let Some(expected) = expected {
+            let expected = hex::encode(decode(expected, "beacon", 32)?);
+            let found = hex::encode(self.value());
+            if expected != found {
+                return Err(BeaconError::Mismatch { expected, found });
+            }
+        }
+        Ok(self)
+    }
+}
+
+fn decode(value: &str, what: &'static str, length: usize) -> Result<Vec<u8>, BeaconError> {
+    let bytes = hex::decode(value.trim_start_matches("0x"))?;
+    if bytes.len() != length {
+        return Err(BeaconError::Length {
+            what,
+            expected: length,
+            found: bytes.len(),
+        });
+    }
+    Ok(bytes)
+}
+
+/// Big endian target compact `bits` stand for, unless they're negative, zero or overflow.
+fn target(bits: u32) -> Option<[u8; 32]> {
+    let exponent = (bits >> 24) as usize;
+    let mut mantissa = bits & 0x007f_ffff;
+    if bits & 0x0080_0000 != 0 || exponent > 32 {
+        return None;
+    }
+
+    let mut target = [0u8; 32];
+    if exponent <= 3 {
+        mantissa >>= 8 * (3 - exponent);
+        target[29..].copy_from_slice(&mantissa.to_be_bytes()[1..]);
+    } else {
+        target[32 - exponent..35 - exponent].copy_from_slice(&mantissa.to_be_bytes()[1..]);
+    }
+
+    if target.iter().all(|b| *b == 0) {
+        None
+    } else {
+        Some(target)
+    }
+}
+
+fn g1(bytes: &[u8], what: &'static str) -> Result<G1Affine, BeaconError> {
+    let mut compressed = [0u8; 48];
+    if bytes.len() != compressed.len() {
+        return Err(BeaconError::Length {
+            what,
+            expected: compressed.len(),
+            found: bytes.len(),
+        });
+    }
+    compressed.copy_from_slice(bytes);
+    Option::from(G1Affine::from_compressed(&compressed)).ok_or(BeaconError::Point(what))
+}
+
+fn g2(bytes: &[u8], what: &'static str) -> Result<G2Affine, BeaconError> {
+    let mut compressed = [0u8; 96];
+    if bytes.len() != compressed.len() {
+        return Err(BeaconError::Length {
+            what,
+            expected: compressed.len(),
+            found: bytes.len(),
+        });
+    }
+    compressed.copy_from_slice(bytes);
+    Option::from(G2Affine::from_compressed(&compressed)).ok_or(BeaconError::Point(what))
+}
+
+/// `2^iterations` rounds of SHA-256 over a beacon value.
+#[derive(Clone, Debug, PartialEq)]
+pub struct HashChain {
+    pub iterations: usize,
+    /// state at start of every segment reached so far, followed by final state once complete
+    pub checkpoints: Vec<[u8; 32]>,
+}
+
+#[derive(Debug)]
+pub enum ChainError {
+    IoError(io::Error),
+    /// line of checkpoint file isn't `<round>: <hex state>`
+    Parse {
+        line: usize,
+    },
+    /// checkpoint isn't the one expected of chain over this value and number of rounds
+    Mismatch {
+        line: usize,
+    },
+    Incomplete {
+        checkpoints: usize,
+    },
+    /// segments whose last state doesn't hash to next checkpoint
+    Segments(Vec<usize>),
+}
+
+impl fmt::Display for ChainError {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        match *self {
+            ChainError::IoError(ref e) => write!(f, "Disk IO error: {}", e),
+            ChainError::Parse { line } => write!(f, "Line {} isn't a checkpoint", line),
+            ChainError::Mismatch { line } => {
+                write!(f, "Checkpoint on line {} belongs to another chain", line)
+            }
+            ChainError::Incomplete { checkpoints } => write!(
+                f,
+                "Chain has {} of {} checkpoints",
+                checkpoints,
+                SEGMENTS + 1
+            ),
+            ChainError::Segments(ref segments) => {
+                write!(
+                    f,
+                    "Segments {:?} don't hash to their next checkpoint",
+                    segments
+                )
+            }
+        }
+    }
+}
+
+impl From<io::Error> for ChainError {
+    fn from(err: io::Error) -> ChainError {
+        ChainError::IoError(err)
+    }
+}
+
+impl HashChain {
+    pub fn new(start: [u8; 32], iterations: usize) -> HashChain {
+        assert!(
+            (10..64).contains(&iterations),
+            "beacon must be hashed 2^n times, for 10 <= n < 64"
+        );
+        HashChain {
+            iterations,
+            checkpoints: vec![start],
+        }
+    }
+
+    /// Reads checkpoints of chain starting at `start` from `path`, dropping a line left
+    /// half written by an interruption.
+    pub fn load(path: &Path, start: [u8; 32], iterations: usize) -> Result<HashChain, ChainError> {
+        let mut chain = HashChain::new(start, iterations);
+        let text = fs::read_to_string(path)?;
+        let complete = &text[..text.rfind('\n').map_or(0, |end| end + 1)];
+
+        for (i, line) in complete.lines().enumerate() {
+            let (round, state) = parse_checkpoint(line).ok_or(ChainError::Parse { line: i + 1 })?;
+            let expected = i as u64 * chain.segment_length();
+            if i as u64 > SEGMENTS || round != expected {
+                return Err(ChainError::Mismatch { line: i + 1 });
+            }
+            if i == 0 {
+                if state != start {
+                    return Err(ChainError::Mismatch { line: 1 });
+                }
+            } else {
+                chain.checkpoints.push(state);
+            }
+        }
+
+        Ok(chain)
+    }
+
+    /// Rounds between checkpoints.
+    pub fn segment_length(&self) -> u64 {
+        1u64 << (self.iterations - 10)
+    }
+
+    /// Final state, once every segment is computed.
+    pub fn result(&self) -> Option<[u8; 32]> {
+        if self.checkpoints.len() as u64 == SEGMENTS + 1 {
+            self.checkpoints.last().cloned()
+        } else {
+            None
+        }
+    }
+
+    /// Computes remaining segments, appending each checkpoint to `path` as it's reached, and
+    /// calling `progress` with it. Segments of a resumed chain are checked first, so that a
+    /// corrupted or tampered checkpoint file isn't carried on from.
+    pub fn run<F>(&mut self, path: &Path, mut progress: F) -> Result<[u8; 32], ChainError>
+    where
+        F: FnMut(u64, &[u8; 32]),
+    {
+        let failed = self.failed_segments();
+        if !failed.is_empty() {
+            return Err(ChainError::Segments(failed));
+        }
+
+        // rewritten whole, to drop whatever an interruption left half written
+        self.save(path)?;
+        let mut file = OpenOptions::new().append(true).open(path)?;
+
+        let mut state = *self
+            .checkpoints
+            .last()
+            .expect("chain starts with its value");
+        while self.result().is_none() {
+            for _ in 0..self.segment_length() {
+                state = sha256(&state);
+            }
+            self.checkpoints.push(state);
+
+            let round = (self.checkpoints.len() as u64 - 1) * self.segment_length();
+            writeln!(file, "{}: {}", round, hex::encode(state))?;
+            file.sync_data()?;
+            progress(round, &state);
+        }
+
+        Ok(state)
+    }
+
+    /// Checks every segment, spread over all cores, returning final state.
+    pub fn verify(&self) -> Result<[u8; 32], ChainError> {
+        let result = self.result().ok_or(ChainError::Incomplete {
+            checkpoints: self.checkpoints.len(),
+        })?;
+
+        let failed = self.failed_segments();
+        if failed.is_empty() {
+            Ok(result)
+        } else {
+            Err(ChainError::Segments(failed))
+        }
+    }
+
+    /// Segments computed so far whose last state doesn't hash to next checkpoint, in order.
+    fn failed_segments(&self) -> Vec<usize> {
+        let segments: Vec<(usize, &[[u8; 32]])> = self.checkpoints.windows(2).enumerate().collect();
+        let failed = Mutex::new(vec![]);
+        let chunk = (segments.len() / num_cpus::get()) + 1;
+        crossbeam::scope(|scope| {
+            for chunk in segments.chunks(chunk) {
+                let failed = &failed;
+                scope.spawn(move |_| {
+                    for &(i, segment) in chunk {
+                        let mut state = segment[0];
+                        for _ in 0..self.segment_length() {
+                            state = sha256(&state);
+                        }
+                        if state != segment[1] {
+                            failed.lock().unwrap().push(i);
+                        }
+                    }
+                });
+            }
+        })
+        .expect("must verify segments");
+
+        let mut failed = failed.into_inner().unwrap();
+        failed.sort_unstable();
+        failed
+    }
+
+    fn save(&self, path: &Path) -> Result<(), ChainError> {
+        let temporary = path.with_extension("tmp");
+        {
+            let mut file = File::create(&temporary)?;
+            for (i, state) in self.checkpoints.iter().enumerate() {
+                writeln!(
+                    file,
+                    "{}: {}",
+                    i as u64 * self.segment_length(),
+                    hex::encode(state)
+                )?;
+            }
+            file.sync_all()?;
+        }
+        fs::rename(temporary, path)?;
+        Ok(())
+    }
+}
+
+fn parse_checkpoint(line: &str) -> Option<(u64, [u8; 32])> {
+    let mut parts = line.splitn(2, ": ");
+    let round = parts.next()?.parse().ok()?;
+    let bytes = hex::decode(parts.next()?).ok()?;
+    let mut state = [0u8; 32];
+    if bytes.len() != state.len() {
+        return None;
+    }
+    state.copy_from_slice(&bytes);
+    Some((round, state))
+}
+
+fn sha256(state: &[u8; 32]) -> [u8; 32] {
+    let mut next = [0u8; 32];
+    next.copy_from_slice(&Sha256::digest(state));
+    next
+}
+
+#[test]
+fn test_bitcoin_beacon() {
+    // genesis block
+    let header = "0100000000000000000000000000000000000000000000000000000000000000000000003ba3edfd7a7b12b27ac72c3e67768f617fc81bc3888a51323a9fb8aa4b1e5e4a29ab5f49ffff001d1dac2b7c";
+    let hash = "000000000019d6689c085ae165831e934ff763ae46a2a6c172b3f1b60a8ce26f";
+
+    let beacon = Beacon::bitcoin(header.as_bytes(), hash).unwrap();
+    assert_eq!(hex::encode(beacon.value()), hash);
+    assert_eq!(
+        Beacon::bitcoin(&hex::decode(header).unwrap(), hash).unwrap(),
+        beacon
+    );
+
+    match Beacon::bitcoin(header.as_bytes(), &"00".repeat(32)) {
+        Err(BeaconError::Mismatch { .. }) => {}
+        _ => panic!("beacon must be expected hash"),
+    }
+
+    // another nonce won't meet target
+    let forged = format!("{}00000000", &header[..152]);
+    match Beacon::bitcoin(forged.as_bytes(), hash) {
+        Err(BeaconError::ProofOfWork) => {}
+        _ => panic!("forged header must fail proof of work"),
+    }
+
+    // header of regtest difficulty, mined in a few tries, is refused even as expected hash
+    let mut forged = hex::decode(header).unwrap();
+    forged[72..76].copy_from_slice(&0x207f_ffffu32.to_le_bytes());
+    let easy = target(0x207f_ffff).unwrap();
+    let forged_hash = (0u32..)
+        .find_map(|nonce| {
+            forged[76..].copy_from_slice(&nonce.to_le_bytes());
+            let mut hash = Sha256::digest(&Sha256::digest(&forged)).to_vec();
+            hash.reverse();
+            if hash.as_slice() <= &easy[..] {
+                Some(hex::encode(hash))
+            } else {
+                None
+            }
+        })
+        .unwrap();
+    match Beacon::bitcoin(&forged, &forged_hash) {
+        Err(BeaconError::Difficulty(0x207f_ffff)) => {}
+        _ => panic!("low difficulty header must be refused"),
+    }
+
+    let mut expected = [0u8; 32];
+    expected[4..6].copy_from_slice(&[0xff, 0xff]);
+    assert_eq!(target(0x1d00ffff), Some(expected));
+    assert_eq!(target(0x1d80ffff), None);
+    assert_eq!(target(0), None);
+}
+
+#[test]
+fn test_drand_beacon() {
+    use bls12_381::Scalar;
+
+    // network of a single signer, signing on G2
+    let secret = Scalar::from(0x5eed_u64);
+    let public_key = G1Affine::from(G1Affine::generator() * secret).to_compressed();
+    let previous = [7u8; 96];
+
+    let mut message = Sha256::new();
+    message.update(&previous[..]);
+    message.update(42u64.to_be_bytes());
+    let hashed = <G2Projective as HashToCurve<ExpandMsgXmd<Sha256>>>::hash_to_curve(
+        message.finalize(),
+        DRAND_G2_DST,
+    );
+    let signature = G2Affine::from(hashed * secret).to_compressed();
+    let randomness = Sha256::digest(&signature[..]);
+
+    let round = |randomness: &[u8], previous: &[u8]| {
+        serde_json::to_vec(&serde_json::json!({
+            "round": 42,
+            "randomness": hex::encode(randomness),
+            "signature": hex::encode(&signature[..]),
+            "previous_signature": hex::encode(previous),
+        }))
+        .unwrap()
+    };
+
+    let beacon = Beacon::drand(
+        &round(&randomness, &previous),
+        &hex::encode(&public_key[..]),
+        Some(&hex::encode(&randomness)),
+    )
+    .unwrap();
+    assert_eq!(&beacon.value()[..], randomness.as_slice());
+
+    match Beacon::drand(
+        &round(&randomness, &[8u8; 96]),
+        &hex::encode(&public_key[..]),
+        None,
+    ) {
+        Err(BeaconError::Signature) => {}
+        _ => panic!("round signed over another previous signature must not verify"),
+    }
+    match Beacon::drand(
+        &round(&[0u8; 32], &previous),
+        &hex::encode(&public_key[..]),
+        None,
+    ) {
+        Err(BeaconError::Randomness) => {}
+        _ => panic!("randomness must be hash of signature"),
+    }
+}
+
+#[test]
+fn test_hash_chain() {
+    let dir = std::env::temp_dir().join(format!("beacon-{}", std::process::id()));
+    fs::create_dir_all(&dir).unwrap();
+    let path = dir.join("beacon_checkpoints");
+
+    let start = [5u8; 32];
+    let mut chain = HashChain::new(start, 10);
+    let mut reached = 0;
+    let result = chain.run(&path, |round, _| reached = round).unwrap();
+    assert_eq!(reached, 1 << 10);
+    assert_eq!(chain.result(), Some(result));
+    assert_eq!(chain.verify().unwrap(), result);
+
+    let mut expected = start;
+    for _ in 0..(1 << 10) {
+        expected = sha256(&expected);
+    }
+    assert_eq!(result, expected);
+
+    // interrupted halfway, in the middle of writing a checkpoint
+    let text = fs::read_to_string(&path).unwrap();
+    let lines: Vec<&str> = text.lines().collect();
+    fs::write(
+        &path,
+        format!("{}\n{}", lines[..500].join("\n"), &lines[500][..10]),
+    )
+    .unwrap();
+    let mut resumed = HashChain::load(&path, start, 10).unwrap();
+    assert_eq!(resumed.checkpoints.len(), 500);
+    assert_eq!(resumed.run(&path, |_, _| {}).unwrap(), result);
+    assert_eq!(fs::read_to_string(&path).unwrap(), text);
+
+    // checkpoints of another beacon aren't resumed
+    match HashChain::load(&path, [6u8; 32], 10) {
+        Err(ChainError::Mismatch { line: 1 }) => {}
+        _ => panic!("chain must start with its beacon"),
+    }
+
+    let mut forged = HashChain::load(&path, start, 10).unwrap();
+    forged.checkpoints[300] = [0u8; 32];
+    match forged.verify() {
+        Err(ChainError::Segments(segments)) => assert_eq!(segments, vec![299, 300]),
+        _ => panic!("forged checkpoint must not verify"),
+    }
+
+    // nor is an interrupted chain resumed from one
+    forged.checkpoints.truncate(500);
+    match forged.run(&path, |_, _| {}) {
+        Err(ChainError::Segments(segments)) => assert_eq!(segments, vec![299, 300]),
+        _ => panic!("forged checkpoint must not be resumed"),
+    }
+
+    fs::remove_dir_all(dir).unwrap();
+}
@@ -0,0 +1,44 @@
+use crate::config::{self, Config};
+use powersoftau::audit::audit;
+use std::path::Path;
+
+/// `powersoftau audit [--audit-log <path>] [--dir <path>]`
+///
+/// Replays audit log against transcript files, resolved relative to `--dir`.
+pub fn run(config: &Config) {
+    let log = config::path(&config.audit_log, "audit.jsonl");
+    let dir = config::path(&config.dir, ".");
+
+    let audit = audit(Path::new(log), Path::new(dir))
+        .unwrap_or_else(|e| panic!("unable to read audit log `{}`: {}", log, e));
+
+    for entry in audit.entries.iter() {
+        let contribution = &entry.contribution;
+        println!(
+            "#{}: `{}` on top of `{}`, {}",
+            entry.index,
+            contribution.response,
+            contribution.challenge,
+            if contribution.verified {
+                "verified".to_owned()
+            } else {
+                format!(
+                    "rejected: {}",
+                    contribution.error.as_deref().unwrap_or("unknown error")
+                )
+            }
+        );
+    }
+    if let Some(last) = audit.entries.last() {
+        println!("Hash of last entry: {}", last.hash);
+    }
+
+    if audit.findings.is_empty() {
+        println!("Audit log of {} entries is intact!", audit.entries.len());
+    } else {
+        for finding in audit.findings.iter() {
+            println!("line {}: {}", finding.line, finding.problem);
+        }
+        panic!("AUDIT FAILED, {} problems found", audit.findings.len());
+    }
+}
@@ -0,0 +1,150 @@
+use crate::compute;
+use crate::config::{self, Config};
+use crate::report::Report;
+use bellman::pairing::Engine;
+use powersoftau::beacon::{Beacon, BeaconSource, HashChain, SEGMENTS};
+use rand::chacha::ChaChaRng;
+use std::fs;
+use std::path::Path;
+
+pub const DEFAULT_BEACON_ITERATIONS: usize = 31;
+pub const DEFAULT_CHECKPOINTS: &str = "beacon_checkpoints";
+
+/// `powersoftau beacon [--beacon-source hex|bitcoin|drand] [--beacon <hex>]
+/// [--beacon-input <path>] [--drand-public-key <hex>] [--beacon-iterations <n>]
+/// [--beacon-checkpoints <path>]`, along with flags of `powersoftau compute`
+///
+/// With `hex` source, `--beacon` is the beacon. With `bitcoin` or `drand` source, beacon is read
+/// from `--beacon-input` and checked, and `--beacon` is the value it must yield, if set. A Bitcoin
+/// beacon requires it. Hashing resumes from `--beacon-checkpoints`, if an earlier run over same
+/// beacon left any there.
+pub fn run<E: Engine>(config: &Config) {
+    let parameters = compute::challenge_parameters(config);
+    let mut report = Report::start("beacon", config, &parameters);
+
+    println!(
+        "Will contribute a random beacon to accumulator for 2^{} powers of tau",
+        parameters.required_power
+    );
+    println!(
+        "In total will generate up to {} powers",
+        parameters.tau_powers_g1_length()
+    );
+
+    let beacon = match read_beacon(config) {
+        Ok(beacon) => beacon,
+        Err(e) => report.fail(config, e),
+    };
+    println!("Beacon value: {}", hex::encode(beacon.value()));
+
+    // Performs 2^n hash iterations over it
+    let n = config
+        .beacon_iterations
+        .unwrap_or(DEFAULT_BEACON_ITERATIONS);
+    assert!(
+        (10..64).contains(&n),
+        "beacon must be hashed 2^n times, for 10 <= n < 64"
+    );
+
+    // Checkpoint 1024 of the interstitial states, so that an interrupted beacon can resume, and
+    // verification can be parallelized
+    let checkpoints = config::path(&config.beacon_checkpoints, DEFAULT_CHECKPOINTS);
+    let mut chain = if Path::new(checkpoints).exists() {
+        match HashChain::load(Path::new(checkpoints), beacon.value(), n) {
+            Ok(chain) => chain,
+            Err(e) => report.fail(
+                config,
+                format!("unable to resume beacon from `{}`: {}", checkpoints, e),
+            ),
+        }
+    } else {
+        HashChain::new(beacon.value(), n)
+    };
+    if chain.checkpoints.len() > 1 {
+        println!(
+            "Resuming beacon from {} of {} checkpoints in `{}`",
+            chain.checkpoints.len() - 1,
+            SEGMENTS,
+            checkpoints
+        );
+    }
+
+    let result = match chain.run(Path::new(checkpoints), |i, state| {
+        println!("{}: {}", i, hex::encode(state))
+    }) {
+        Ok(result) => result,
+        Err(e) => report.fail(
+            config,
+            format!(
+                "unable to hash beacon, checkpoints `{}`: {}",
+                checkpoints, e
+            ),
+        ),
+    };
+
+    println!("Final result of beacon: {}", hex::encode(result));
+    report.beacon(&beacon, n, &result);
+
+    // Create an RNG based on the outcome of the random beacon
+    let mut rng = rng(&result);
+    println!("Done creating a beacon RNG");
+
+    compute::contribute::<E, _>(config, &parameters, &mut rng, &result, &mut report);
+    report.finish(config, None);
+
+    println!("Thank you for your participation, much appreciated! :)");
+}
+
+/// RNG a contribution is sampled from, seeded with final state of beacon hash chain.
+pub fn rng(result: &[u8; 32]) -> ChaChaRng {
+    use byteorder::{BigEndian, ReadBytesExt};
+    use rand::SeedableRng;
+
+    let mut digest = &result[..];
+
+    let mut seed = [0u32; 8];
+    for i in 0..8 {
+        seed[i] = digest
+            .read_u32::<BigEndian>()
+            .expect("digest is large enough for this to work");
+    }
+
+    ChaChaRng::from_seed(&seed)
+}
+
+/// Beacon of `--beacon-source`, checked against whatever vouches for it.
+pub fn read_beacon(config: &Config) -> Result<Beacon, String> {
+    let expected = config.beacon.as_deref();
+    let input = |default: &'static str| {
+        let path = config::path(&config.beacon_input, default);
+        fs::read(path)
+            .map(|bytes| (path, bytes))
+            .map_err(|e| format!("unable to read `{}`: {}", path, e))
+    };
+
+    match config.beacon_source.unwrap_or(BeaconSource::Hex) {
+        BeaconSource::Hex => {
+            let value = expected.ok_or_else(|| {
+                "`--beacon` must be set to the value announced in advance".to_owned()
+            })?;
+            Beacon::hex(value).map_err(|e| format!("invalid beacon: {}", e))
+        }
+        BeaconSource::Bitcoin => {
+            let expected = expected.ok_or_else(|| {
+                "`--beacon` must be set to hash of announced block for a Bitcoin beacon".to_owned()
+            })?;
+            let (path, header) = input("header")?;
+            Beacon::bitcoin(&header, expected)
+                .map_err(|e| format!("BEACON REJECTED, block header `{}`: {}", path, e))
+        }
+        BeaconSource::Drand => {
+            let public_key = config
+                .drand_public_key
+                .as_deref()
+                .ok_or_else(|| "`--drand-public-key` must be set for a drand beacon".to_owned())?;
+            let (path, round) = input("drand.json")?;
+            Beacon::drand(&round, public_key, expected)
+                .map_err(|e| format!("BEACON REJECTED, drand round `{}`: {}", path, e))
+        }
+    }
+}
@@ -0,0 +1,269 @@
+use crate::config::{self, Config};
+use crate::print_hash;
+use crate::report::{public_key_hex, Report};
+use bellman::pairing::Engine;
+use memmap::*;
+use powersoftau::attestation::{self, Attestation, Statement};
+use powersoftau::batched_accumulator::BachedAccumulator;
+use powersoftau::keypair::keypair;
+use powersoftau::parameters::{CeremonyParameters, CheckForCorrectness, UseCompression};
+use rand::Rng;
+use std::fs::OpenOptions;
+use std::io::{Read, Write};
+use std::path::Path;
+
+/// `powersoftau compute [--challenge <path>] [--response <path>] [--challenge-compressed yes|no]
+/// [--response-compressed yes|no] [--check-challenge yes|no] [--report <path>]
+/// [--attestation <path> [--signing-key <path>] [--participant <name>]]`
+pub fn run<E: Engine>(config: &Config) {
+    let parameters = challenge_parameters(config);
+    let mut report = Report::start("compute", config, &parameters);
+
+    println!(
+        "Will contribute to accumulator for 2^{} powers of tau",
+        parameters.required_power
+    );
+    println!(
+        "In total will generate up to {} powers",
+        parameters.tau_powers_g1_length()
+    );
+
+    // Create an RNG based on a mixture of system randomness and user provided randomness
+    let (mut rng, entropy) = {
+        use blake2::{Blake2b, Digest};
+        use byteorder::{BigEndian, ReadBytesExt};
+        use rand::chacha::ChaChaRng;
+        use rand::{OsRng, SeedableRng};
+
+        let h = {
+            let mut system_rng = OsRng::new().unwrap();
+            let mut h = Blake2b::default();
+
+            // Gather 1024 bytes of entropy from the system
+            for _ in 0..1024 {
+                let r: u8 = system_rng.gen();
+                h.input(&[r]);
+            }
+
+            // Ask the user to provide some information for additional entropy
+            let mut user_input = String::new();
+            println!("Type some random text and press [ENTER] to provide additional entropy...");
+            std::io::stdin()
+                .read_line(&mut user_input)
+                .expect("expected to read some random text from the user");
+
+            // Hash it all up to make a seed
+            h.input(&user_input.as_bytes());
+            h.result()
+        };
+
+        let mut digest = &h[..];
+
+        // Interpret the first 32 bytes of the digest as 8 32-bit words
+        let mut seed = [0u32; 8];
+        for i in 0..8 {
+            seed[i] = digest
+                .read_u32::<BigEndian>()
+                .expect("digest is large enough for this to work");
+        }
+
+        (ChaChaRng::from_seed(&seed), h.to_vec())
+    };
+
+    contribute::<E, _>(config, &parameters, &mut rng, &entropy, &mut report);
+    report.finish(config, None);
+
+    println!("Thank you for your participation, much appreciated! :)");
+}
+
+/// Ceremony of `--power`, or of whichever power challenge is of.
+pub fn challenge_parameters(config: &Config) -> CeremonyParameters {
+    let challenge = config::path(&config.challenge, "challenge");
+    let input_is_compressed = config::compression(config.challenge_compressed, UseCompression::No);
+    config.parameters(Some(challenge), |p| p.challenge_size(input_is_compressed))
+}
+
+/// Transforms challenge into response, using a keypair sampled from `rng` seeded with `entropy`,
+/// and records it in `report`, and in a signed attestation if `--attestation` is set.
+pub fn contribute<E: Engine, R: Rng>(
+    config: &Config,
+    parameters: &CeremonyParameters,
+    rng: &mut R,
+    entropy: &[u8],
+    report: &mut Report,
+) {
+    let challenge = config::path(&config.challenge, "challenge");
+    let response = config::path(&config.response, "response");
+    let input_is_compressed = config::compression(config.challenge_compressed, UseCompression::No);
+    let compress_the_output = config::compression(config.response_compressed, UseCompression::Yes);
+    let check_input_correctness = config::check(config.check_challenge, CheckForCorrectness::No);
+
+    // Try to load challenge from disk.
+    let reader = OpenOptions::new()
+        .read(true)
+        .open(challenge)
+        .unwrap_or_else(|e| panic!("unable open `{}`: {}", challenge, e));
+
+    {
+        let metadata = reader.metadata().unwrap_or_else(|e| {
+            panic!(
+                "unable to get filesystem metadata for `{}`: {}",
+                challenge, e
+            )
+        });
+        let expected_challenge_length = parameters.challenge_size(input_is_compressed);
+
+        if metadata.len() != (expected_challenge_length as u64) {
+            panic!(
+                "The size of `{}` should be {}, but it's {}, so something isn't right.",
+                challenge,
+                expected_challenge_length,
+                metadata.len()
+            );
+        }
+    }
+
+    let readable_map = unsafe {
+        MmapOptions::new()
+            .map(&reader)
+            .expect("unable to create a memory map for input")
+    };
+
+    let writer = OpenOptions::new()
+        .read(true)
+        .write(true)
+        .create_new(true)
+        .open(response)
+        .unwrap_or_else(|e| panic!("unable to create `{}`: {}", response, e));
+
+    let required_output_length = parameters.response_size(compress_the_output);
+
+    writer
+        .set_len(required_output_length as u64)
+        .expect("must make output file large enough");
+
+    let mut writable_map = unsafe {
+        MmapOptions::new()
+            .map_mut(&writer)
+            .expect("unable to create a memory map for output")
+    };
+
+    println!("Calculating previous contribution hash...");
+
+    assert!(
+        UseCompression::No == input_is_compressed,
+        "Hashing the compressed file in not yet defined"
+    );
+    let current_accumulator_hash = BachedAccumulator::<E>::calculate_hash(&readable_map);
+
+    {
+        println!(
+            "`{}` file contains decompressed points and has a hash:",
+            challenge
+        );
+        print_hash(current_accumulator_hash.as_slice());
+
+        (&mut writable_map[0..])
+            .write(current_accumulator_hash.as_slice())
+            .expect("unable to write a challenge hash to mmap");
+
+        writable_map
+            .flush()
+            .unwrap_or_else(|e| panic!("unable to write hash to `{}`: {}", response, e));
+    }
+
+    {
+        let mut challenge_hash = [0; 64];
+        let mut memory_slice = readable_map
+            .get(0..64)
+            .expect("must read point data from file");
+        memory_slice
+            .read_exact(&mut challenge_hash)
+            .expect("couldn't read hash of challenge file from response file");
+
+        println!(
+            "`{}` file claims (!!! Must not be blindly trusted) that it was based on the original contribution with a hash:",
+            challenge
+        );
+        print_hash(&challenge_hash);
+    }
+
+    // Construct our keypair using the RNG we created above
+    let (pubkey, privkey) = keypair(rng, current_accumulator_hash.as_ref());
+
+    // Perform the transformation
+    println!("Computing and writing your contribution, this could take a while...");
+
+    // this computes a transformation and writes it
+    BachedAccumulator::<E>::transform(
+        &readable_map,
+        &mut writable_map,
+        input_is_compressed,
+        compress_the_output,
+        check_input_correctness,
+        &privkey,
+        parameters,
+    )
+    .expect("must transform with the key");
+
+    println!("Finishing writing your contribution to `{}`...", response);
+
+    // Write the public key
+    pubkey
+        .write(&mut writable_map, compress_the_output, parameters)
+        .expect("unable to write public key");
+
+    writable_map.flush().expect("must flush a memory map");
+
+    // Get the hash of the contribution, so the user can compare later
+    let output_readonly = writable_map
+        .make_read_only()
+        .expect("must make a map readonly");
+    let contribution_hash = BachedAccumulator::<E>::calculate_hash(&output_readonly);
+
+    print!(
+        "Done!\n\n\
+              Your contribution has been written to `{}`\n\n\
+              The BLAKE2b hash of `{}` is:\n",
+        response, response
+    );
+    print_hash(contribution_hash.as_slice());
+
+    report.input(challenge, current_accumulator_hash.as_slice());
+    report.output(response, contribution_hash.as_slice());
+    report.public_key(&pubkey);
+    report.batches(parameters);
+
+    if let Some(ref path) = config.attestation {
+        let statement = Statement {
+            tool: format!("powersoftau {}", env!("CARGO_PKG_VERSION")),
+            curve: config.curve().name().to_owned(),
+            power: parameters.required_power,
+            participant: config.participant.clone(),
+            challenge_hash: hex::encode(current_accumulator_hash.as_slice()),
+            response_hash: hex::encode(contribution_hash.as_slice()),
+            public_key: public_key_hex(&pubkey),
+            entropy_hash: hex::encode(attestation::entropy_hash(entropy)),
+        };
+        attest(config, path, &statement);
+    }
+}
+
+/// Signs `statement` with key of `--signing-key`, created if there's none yet, and writes the
+/// bundle to `path`.
+fn attest(config: &Config, path: &str, statement: &Statement) {
+    let key_path = config::path(&config.signing_key, "signing_key");
+    let key = attestation::signing_key(Path::new(key_path))
+        .unwrap_or_else(|e| panic!("unable to load signing key `{}`: {}", key_path, e));
+
+    Attestation::sign(statement, &key)
+        .write(Path::new(path))
+        .unwrap_or_else(|e| panic!("unable to write attestation `{}`: {}", path, e));
+
+    println!(
+        "Your attestation has been written to `{}`, signed with key `{}` kept in `{}`",
+        path,
+        hex::encode(key.public.as_bytes()),
+        key_path
+    );
+}
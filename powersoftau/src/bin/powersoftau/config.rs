@@ -0,0 +1,225 @@
+use powersoftau::beacon::BeaconSource;
+use powersoftau::parameters::{CeremonyParameters, CheckForCorrectness, UseCompression, MAX_POWER};
+use serde::{Deserialize, Serialize};
+use std::fs;
+
+/// Ceremony of 2^27 powers of tau, unless `--power` is set or it's read off an input file.
+pub const DEFAULT_POWER: usize = 27;
+
+// keys holding booleans or integers, every other key holds a string
+const BOOL_KEYS: &[&str] = &[
+    "challenge_compressed",
+    "response_compressed",
+    "new_challenge_compressed",
+    "check_challenge",
+    "check_response",
+];
+const INTEGER_KEYS: &[&str] = &["power", "points", "beacon_iterations", "lock_timeout"];
+// keys holding lists, given as comma separated flags
+const LIST_KEYS: &[&str] = &["responses"];
+
+#[derive(Clone, Copy, Debug, PartialEq, Deserialize, Serialize)]
+pub enum Curve {
+    #[serde(rename = "bls12-381")]
+    Bls12_381,
+    #[serde(rename = "bn256")]
+    Bn256,
+}
+
+/// Settings of a subcommand, read from an optional TOML file given by `--config`, then
+/// overridden by `--<key> <value>` flags, where `-` in a flag stands for `_` in a key.
+///
+/// ```toml
+/// curve = "bn256"
+/// power = 10
+/// challenge = "challenge"
+/// response = "response"
+/// response_compressed = true
+/// check_challenge = false
+/// ```
+///
+/// Unset settings fall back to defaults of each subcommand.
+#[derive(Clone, Debug, Default, Deserialize)]
+#[serde(deny_unknown_fields)]
+pub struct Config {
+    pub curve: Option<Curve>,
+    pub power: Option<usize>,
+
+    pub challenge: Option<String>,
+    pub response: Option<String>,
+    pub new_challenge: Option<String>,
+    pub output: Option<String>,
+    /// ordered responses of a whole ceremony, for `transcript`
+    pub responses: Option<Vec<String>>,
+    /// directory for recomputed challenges, kept in memory if unset
+    pub scratch: Option<String>,
+    /// where a JSON report of the operation goes, none is written if unset
+    pub report: Option<String>,
+    /// transcript directory of `coordinate`, or where `audit` finds files log refers to
+    pub dir: Option<String>,
+    /// address `coordinate` listens on
+    pub bind: Option<String>,
+    /// seconds a participant may hold the challenge for
+    pub lock_timeout: Option<u64>,
+    /// append-only log `verify` records contributions in
+    pub audit_log: Option<String>,
+    /// signed attestation bundle `compute` and `beacon` write, none is written if unset
+    pub attestation: Option<String>,
+    /// file holding Ed25519 secret key attestations are signed with, created on first use
+    pub signing_key: Option<String>,
+    /// identity of participant, as stated in attestation
+    pub participant: Option<String>,
+
+    pub challenge_compressed: Option<bool>,
+    pub response_compressed: Option<bool>,
+    pub new_challenge_compressed: Option<bool>,
+    pub check_challenge: Option<bool>,
+    pub check_response: Option<bool>,
+
+    /// number of powers `extract` takes
+    pub points: Option<usize>,
+    /// hex encoded beacon value, i.e. a block hash, or the value a checked source must yield
+    pub beacon: Option<String>,
+    /// where beacon comes from, `hex`, `bitcoin` or `drand`
+    pub beacon_source: Option<BeaconSource>,
+    /// Bitcoin block header or drand round a checked beacon is read from
+    pub beacon_input: Option<String>,
+    /// hex encoded public key of drand network
+    pub drand_public_key: Option<String>,
+    /// beacon is hashed 2^beacon_iterations times
+    pub beacon_iterations: Option<usize>,
+    /// file states of beacon hash chain are checkpointed in
+    pub beacon_checkpoints: Option<String>,
+}
+
+impl Curve {
+    /// Name of curve, as given by `--curve`.
+    pub fn name(self) -> &'static str {
+        match self {
+            Curve::Bls12_381 => "bls12-381",
+            Curve::Bn256 => "bn256",
+        }
+    }
+}
+
+impl Config {
+    pub fn parse(args: &[String], usage: &str) -> Config {
+        let mut table = toml::value::Table::new();
+        let mut flags = toml::value::Table::new();
+
+        let mut iter = args.iter();
+        while let Some(arg) = iter.next() {
+            let key = match arg.strip_prefix("--") {
+                Some(key) => key.replace('-', "_"),
+                None => panic!("unexpected argument `{}`, {}", arg, usage),
+            };
+            let value = iter
+                .next()
+                .unwrap_or_else(|| panic!("`{}` takes a value, {}", arg, usage));
+
+            if key == "config" {
+                let text = fs::read_to_string(value)
+                    .unwrap_or_else(|e| panic!("unable to read `{}`: {}", value, e));
+                match text.parse::<toml::Value>() {
+                    Ok(toml::Value::Table(t)) => table.extend(t),
+                    Ok(_) => panic!("`{}` must be a TOML table", value),
+                    Err(e) => panic!("`{}` isn't valid TOML: {}", value, e),
+                }
+            } else {
+                flags.insert(key.clone(), flag_value(&key, value));
+            }
+        }
+
+        // flags take precedence over config file, whatever their order
+        table.extend(flags);
+        toml::Value::Table(table)
+            .try_into()
+            .unwrap_or_else(|e| panic!("invalid settings: {}, {}", e, usage))
+    }
+
+    pub fn curve(&self) -> Curve {
+        self.curve.unwrap_or(Curve::Bls12_381)
+    }
+
+    /// Ceremony of `--power` if set, else of power read off length of `input`, given which
+    /// kind of file it is.
+    pub fn parameters<F>(&self, input: Option<&str>, size: F) -> CeremonyParameters
+    where
+        F: Fn(&CeremonyParameters) -> usize,
+    {
+        let default = match self.curve() {
+            Curve::Bls12_381 => CeremonyParameters::bls12_381(DEFAULT_POWER),
+            Curve::Bn256 => CeremonyParameters::bn256(DEFAULT_POWER),
+        };
+
+        match (self.power, input) {
+            (Some(power), _) => {
+                assert!(
+                    (1..=MAX_POWER).contains(&power),
+                    "power must be within 1..={}",
+                    MAX_POWER
+                );
+                default.with_power(power)
+            }
+            (None, Some(path)) => {
+                let len = fs::metadata(path)
+                    .unwrap_or_else(|e| {
+                        panic!("unable to get filesystem metadata for `{}`: {}", path, e)
+                    })
+                    .len();
+                default.detect(len, size).unwrap_or_else(|| {
+                    panic!(
+                        "`{}` of {}b isn't of any ceremony size over {:?}, set `--power`",
+                        path,
+                        len,
+                        self.curve()
+                    )
+                })
+            }
+            (None, None) => default,
+        }
+    }
+}
+
+fn flag_value(key: &str, value: &str) -> toml::Value {
+    if BOOL_KEYS.contains(&key) {
+        match value {
+            "yes" | "true" => toml::Value::Boolean(true),
+            "no" | "false" => toml::Value::Boolean(false),
+            _ => panic!("`--{}` takes `yes` or `no`", key.replace('_', "-")),
+        }
+    } else if INTEGER_KEYS.contains(&key) {
+        let n = value
+            .parse()
+            .unwrap_or_else(|_| panic!("`--{}` takes an integer", key.replace('_', "-")));
+        toml::Value::Integer(n)
+    } else if LIST_KEYS.contains(&key) {
+        let items = value
+            .split(',')
+            .map(|item| toml::Value::String(item.to_owned()))
+            .collect();
+        toml::Value::Array(items)
+    } else {
+        toml::Value::String(value.to_owned())
+    }
+}
+
+pub fn compression(flag: Option<bool>, default: UseCompression) -> UseCompression {
+    match flag {
+        Some(true) => UseCompression::Yes,
+        Some(false) => UseCompression::No,
+        None => default,
+    }
+}
+
+pub fn check(flag: Option<bool>, default: CheckForCorrectness) -> CheckForCorrectness {
+    match flag {
+        Some(true) => CheckForCorrectness::Yes,
+        Some(false) => CheckForCorrectness::No,
+        None => default,
+    }
+}
+
+pub fn path<'a>(value: &'a Option<String>, default: &'a str) -> &'a str {
+    value.as_deref().unwrap_or(default)
+}
@@ -0,0 +1,51 @@
+use crate::config::{self, Config};
+use bellman::pairing::Engine;
+use powersoftau::coordinator::{serve, Coordinator};
+use powersoftau::parameters::UseCompression;
+use std::net::TcpListener;
+use std::path::Path;
+use std::time::Duration;
+
+const DEFAULT_BIND: &str = "127.0.0.1:8080";
+/// Participants get an hour to contribute, once they lock the challenge.
+const DEFAULT_LOCK_TIMEOUT: u64 = 60 * 60;
+
+/// `powersoftau coordinate [--dir <path>] [--bind <address>] [--lock-timeout <seconds>]
+/// [--response-compressed yes|no]`
+///
+/// Resumes ceremony kept in `--dir`, or starts a new one there.
+pub fn run<E: Engine>(config: &Config) {
+    let dir = Path::new(config::path(&config.dir, "transcript"));
+    let bind = config::path(&config.bind, DEFAULT_BIND);
+    let lock_timeout = Duration::from_secs(config.lock_timeout.unwrap_or(DEFAULT_LOCK_TIMEOUT));
+    let response_compression = config::compression(config.response_compressed, UseCompression::Yes);
+
+    // power of a resumed ceremony is read off its first challenge
+    let first_challenge = dir.join("challenge_0");
+    let parameters = match first_challenge.to_str() {
+        Some(path) if first_challenge.exists() => {
+            config.parameters(Some(path), |p| p.challenge_size(UseCompression::No))
+        }
+        _ => config.parameters(None, |p| p.challenge_size(UseCompression::No)),
+    };
+
+    println!(
+        "Will coordinate a ceremony of 2^{} powers of tau in `{}`",
+        parameters.required_power,
+        dir.display()
+    );
+
+    let coordinator = Coordinator::open::<E>(dir, &parameters, response_compression, lock_timeout)
+        .unwrap_or_else(|e| panic!("unable to open ceremony in `{}`: {}", dir.display(), e));
+    println!(
+        "Round {}, {} participants in queue",
+        coordinator.state().round,
+        coordinator.state().queue.len()
+    );
+
+    let listener =
+        TcpListener::bind(bind).unwrap_or_else(|e| panic!("unable to bind `{}`: {}", bind, e));
+    println!("Listening on http://{}", bind);
+
+    serve::<E>(coordinator, listener).expect("must accept connections");
+}
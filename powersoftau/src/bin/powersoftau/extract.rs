@@ -0,0 +1,150 @@
+use crate::config::{self, Config};
+use crate::report::Report;
+use bellman::pairing::*;
+use memmap::*;
+use powersoftau::batched_accumulator::BachedAccumulator;
+use powersoftau::parameters::{CeremonyParameters, CheckForCorrectness, UseCompression};
+use std::fs::OpenOptions;
+use std::io::{Read, Write};
+
+const DEFAULT_POINTS: usize = 1 << 10;
+
+/// `powersoftau extract [--challenge <path>] [--output <path>] [--points <n>]
+/// [--challenge-compressed yes|no] [--check-challenge yes|no] [--report <path>]`
+pub fn run<E: Engine>(config: &Config) {
+    let challenge = config::path(&config.challenge, "challenge");
+    let output = config::path(&config.output, "extracted.data");
+    let n = config.points.unwrap_or(DEFAULT_POINTS);
+    let is_compressed = config::compression(config.challenge_compressed, UseCompression::No);
+    let check_input_for_correctness =
+        config::check(config.check_challenge, CheckForCorrectness::No);
+    let parameters = config.parameters(Some(challenge), |p| p.challenge_size(is_compressed));
+
+    assert!(
+        n > 0 && n <= parameters.tau_powers_length(),
+        "can extract at most {} points of 2^{} powers of tau",
+        parameters.tau_powers_length(),
+        parameters.required_power
+    );
+
+    let mut report = Report::start("extract", config, &parameters);
+    extract::<E>(
+        &parameters,
+        challenge,
+        output,
+        n,
+        is_compressed,
+        check_input_for_correctness,
+        &mut report,
+    )
+    .unwrap_or_else(|e| panic!("unable to extract from `{}`: {}", challenge, e));
+    report.finish(config, None);
+}
+
+fn extract<E: Engine>(
+    parameters: &CeremonyParameters,
+    challenge: &str,
+    output: &str,
+    n: usize,
+    is_compressed: UseCompression,
+    check_input_for_correctness: CheckForCorrectness,
+    report: &mut Report,
+) -> std::io::Result<()> {
+    let challenge_reader = OpenOptions::new().read(true).open(challenge)?;
+    let metadata = challenge_reader.metadata()?;
+    let expected_challenge_length = parameters.challenge_size(is_compressed);
+    assert_eq!(
+        metadata.len(),
+        expected_challenge_length as u64,
+        "expected to be {}b, found to be {}b",
+        expected_challenge_length,
+        metadata.len()
+    );
+
+    let challenge_readable_map = unsafe { MmapOptions::new().map(&challenge_reader).unwrap() };
+    report.input(
+        challenge,
+        BachedAccumulator::<E>::calculate_hash(&challenge_readable_map).as_slice(),
+    );
+
+    let mut hash = [0; 64];
+    let mut memory_slice = challenge_readable_map.get(0..64).unwrap();
+    memory_slice.read_exact(&mut hash)?;
+    println!("hash: {}", hex::encode(hash));
+
+    let mut out = OpenOptions::new()
+        .read(true)
+        .write(true)
+        .create(true)
+        .open(output)?;
+    out.set_len(expected_size(parameters, n) as u64)?;
+
+    // first write hash of contribution chain
+    out.write(&hash[..])?;
+    out.flush()?;
+
+    // then write N-many points
+    let mut accumulator = BachedAccumulator::<E>::empty(parameters);
+    accumulator
+        .read_chunk(
+            0,
+            n,
+            is_compressed,
+            check_input_for_correctness,
+            &challenge_readable_map,
+        )
+        .expect(&format!(
+            "must read a chunk from {} to {} from source of decompression",
+            0,
+            n - 1
+        ));
+
+    println!(
+        "\ntau_powers_g1: {}\ntau_powers_g2: {}\nalpha_tau_powers_g1: {}\nbeta_tau_powers_g1: {}\n",
+        accumulator.tau_powers_g1.len(),
+        accumulator.tau_powers_g2.len(),
+        accumulator.alpha_tau_powers_g1.len(),
+        accumulator.beta_tau_powers_g1.len()
+    );
+
+    for i in accumulator.tau_powers_g1.iter() {
+        out.write(i.into_compressed().as_ref())?;
+    }
+    out.flush()?;
+
+    for i in accumulator.tau_powers_g2.iter() {
+        out.write(i.into_compressed().as_ref())?;
+    }
+    out.flush()?;
+
+    for i in accumulator.alpha_tau_powers_g1.iter() {
+        out.write(i.into_compressed().as_ref())?;
+    }
+    out.flush()?;
+
+    for i in accumulator.beta_tau_powers_g1.iter() {
+        out.write(i.into_compressed().as_ref())?;
+    }
+    out.flush()?;
+
+    out.write(accumulator.beta_g2.into_compressed().as_ref())?;
+    out.flush()?;
+
+    println!("extracted {} params into `{}`\t✅", n, output);
+
+    let extracted = unsafe { MmapOptions::new().map(&out)? };
+    report.output(
+        output,
+        BachedAccumulator::<E>::calculate_hash(&extracted).as_slice(),
+    );
+    Ok(())
+}
+
+fn expected_size(parameters: &CeremonyParameters, n: usize) -> usize {
+    n * parameters.g1_compressed_byte_size + // g1 tau powers
+    n * parameters.g2_compressed_byte_size + // g2 tau powers
+    n * parameters.g1_compressed_byte_size + // alpha tau powers
+    n * parameters.g1_compressed_byte_size // beta tau powers
+    + parameters.g2_compressed_byte_size // beta in g2
+    + parameters.hash_size() // hash of contribution chain
+}
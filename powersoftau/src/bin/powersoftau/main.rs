@@ -0,0 +1,75 @@
+extern crate bellman;
+extern crate blake2;
+extern crate byteorder;
+extern crate hex;
+extern crate memmap;
+extern crate powersoftau;
+extern crate rand;
+extern crate serde;
+extern crate serde_json;
+extern crate sha2;
+extern crate toml;
+
+mod audit;
+mod beacon;
+mod compute;
+mod config;
+mod coordinate;
+mod extract;
+mod new;
+mod report;
+mod transcript;
+mod verify;
+mod verify_attestation;
+mod verify_beacon;
+
+use bellman::pairing::bls12_381::Bls12;
+use bellman::pairing::bn256::Bn256;
+use config::{Config, Curve};
+use std::env;
+
+const USAGE: &str = "invoke program using `cargo run \
+<new|compute|beacon|verify|transcript|extract|coordinate|audit|verify-attestation|verify-beacon> \
+[--config <path-to-config.toml>] [--curve bls12-381|bn256] [--power <n>] [--<setting> <value>]...`";
+
+macro_rules! on_curve {
+    ($command:ident, $config:expr) => {
+        match $config.curve() {
+            Curve::Bls12_381 => $command::run::<Bls12>($config),
+            Curve::Bn256 => $command::run::<Bn256>($config),
+        }
+    };
+}
+
+fn main() {
+    let cli_args: Vec<String> = env::args().collect();
+    let config = Config::parse(cli_args.get(2..).unwrap_or(&[]), USAGE);
+
+    match cli_args.get(1).map(String::as_str) {
+        Some("new") => on_curve!(new, &config),
+        Some("compute") => on_curve!(compute, &config),
+        Some("beacon") => on_curve!(beacon, &config),
+        Some("verify") => on_curve!(verify, &config),
+        Some("transcript") => on_curve!(transcript, &config),
+        Some("extract") => on_curve!(extract, &config),
+        Some("coordinate") => on_curve!(coordinate, &config),
+        Some("audit") => audit::run(&config),
+        Some("verify-attestation") => verify_attestation::run(&config),
+        Some("verify-beacon") => on_curve!(verify_beacon, &config),
+        _ => panic!("{}", USAGE),
+    }
+}
+
+/// Prints a BLAKE2b hash as 4 lines of 16 bytes.
+pub fn print_hash(hash: &[u8]) {
+    for line in hash.chunks(16) {
+        print!("\t");
+        for section in line.chunks(4) {
+            for b in section {
+                print!("{:02x}", b);
+            }
+            print!(" ");
+        }
+        println!("");
+    }
+}
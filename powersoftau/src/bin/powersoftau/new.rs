@@ -0,0 +1,83 @@
+use crate::config::{self, Config};
+use crate::print_hash;
+use crate::report::Report;
+use bellman::pairing::Engine;
+use memmap::*;
+use powersoftau::batched_accumulator::BachedAccumulator;
+use powersoftau::parameters::UseCompression;
+use powersoftau::utils::blank_hash;
+use std::fs::OpenOptions;
+use std::io::Write;
+
+/// `powersoftau new [--challenge <path>] [--challenge-compressed yes|no] [--report <path>]`
+pub fn run<E: Engine>(config: &Config) {
+    let challenge = config::path(&config.challenge, "challenge");
+    let compress_new_challenge =
+        config::compression(config.challenge_compressed, UseCompression::No);
+    let parameters = config.parameters(None, |p| p.challenge_size(compress_new_challenge));
+    let mut report = Report::start("new", config, &parameters);
+
+    println!(
+        "Will generate an empty accumulator for 2^{} powers of tau",
+        parameters.required_power
+    );
+    println!(
+        "In total will generate up to {} powers",
+        parameters.tau_powers_g1_length()
+    );
+
+    let file = OpenOptions::new()
+        .read(true)
+        .write(true)
+        .create_new(true)
+        .open(challenge)
+        .unwrap_or_else(|e| panic!("unable to create `{}`: {}", challenge, e));
+
+    let expected_challenge_length = parameters.challenge_size(compress_new_challenge);
+
+    file.set_len(expected_challenge_length as u64)
+        .expect("unable to allocate large enough file");
+
+    let mut writable_map = unsafe {
+        MmapOptions::new()
+            .map_mut(&file)
+            .expect("unable to create a memory map")
+    };
+
+    // Write a blank BLAKE2b hash:
+    let hash = blank_hash();
+    (&mut writable_map[0..])
+        .write(hash.as_slice())
+        .expect("unable to write a default hash to mmap");
+    writable_map
+        .flush()
+        .unwrap_or_else(|e| panic!("unable to write blank hash to `{}`: {}", challenge, e));
+
+    println!("Blank hash for an empty challenge:");
+    print_hash(hash.as_slice());
+
+    BachedAccumulator::<E>::generate_initial(
+        &mut writable_map,
+        compress_new_challenge,
+        &parameters,
+    )
+    .expect("generation of initial accumulator is successful");
+    writable_map
+        .flush()
+        .expect("unable to flush memmap to disk");
+
+    // Get the hash of the contribution, so the user can compare later
+    let output_readonly = writable_map
+        .make_read_only()
+        .expect("must make a map readonly");
+    let contribution_hash = BachedAccumulator::<E>::calculate_hash(&output_readonly);
+
+    println!("Empty contribution is formed with a hash:");
+    print_hash(contribution_hash.as_slice());
+
+    println!("Wrote a fresh accumulator to `{}`", challenge);
+
+    report.output(challenge, contribution_hash.as_slice());
+    report.batches(&parameters);
+    report.finish(config, None);
+}
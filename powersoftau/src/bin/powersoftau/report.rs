@@ -0,0 +1,207 @@
+//! JSON report of a ceremony operation, written to `--report <path>` when it's set, so results
+//! can be archived and compared without parsing console output.
+
+use crate::config::{Config, Curve};
+use bellman::pairing::Engine;
+use powersoftau::beacon::Beacon;
+use powersoftau::keypair::PublicKey;
+use powersoftau::parameters::{CeremonyParameters, VerificationReport};
+use serde::Serialize;
+use std::fs::File;
+use std::time::Instant;
+
+#[derive(Serialize)]
+pub struct Report {
+    operation: &'static str,
+    curve: Curve,
+    power: usize,
+    inputs: Vec<FileHash>,
+    outputs: Vec<FileHash>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    public_key: Option<String>,
+    checks: Vec<Check>,
+    /// number of chunks accumulator was processed in
+    #[serde(skip_serializing_if = "Option::is_none")]
+    chunks: Option<usize>,
+    #[serde(skip_serializing_if = "Vec::is_empty")]
+    contributions: Vec<Contribution>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    beacon: Option<BeaconRecord>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    error: Option<String>,
+    runtime_seconds: f64,
+    #[serde(skip)]
+    started: Instant,
+}
+
+/// BLAKE2b hash of a file, or of an accumulator that was never written out.
+#[derive(Serialize)]
+struct FileHash {
+    name: String,
+    blake2b: String,
+}
+
+#[derive(Serialize)]
+struct Check {
+    check: String,
+    element: String,
+    /// powers `start..end` checked, absent for checks of public key alone
+    #[serde(skip_serializing_if = "Option::is_none")]
+    chunk: Option<[usize; 2]>,
+    passed: bool,
+    seconds: f64,
+}
+
+/// One contribution of a transcript.
+#[derive(Serialize)]
+struct Contribution {
+    response: String,
+    challenge_hash: String,
+    response_hash: String,
+    public_key: String,
+    checks: Vec<Check>,
+    chunks: usize,
+}
+
+/// Beacon a contribution was derived from.
+#[derive(Serialize)]
+struct BeaconRecord {
+    #[serde(flatten)]
+    beacon: Beacon,
+    iterations: usize,
+    /// hex encoded value after `2^iterations` rounds of SHA-256
+    result: String,
+}
+
+impl Report {
+    /// Starts timing an `operation` over a ceremony of given `parameters`.
+    pub fn start(
+        operation: &'static str,
+        config: &Config,
+        parameters: &CeremonyParameters,
+    ) -> Report {
+        Report {
+            operation,
+            curve: config.curve(),
+            power: parameters.required_power,
+            inputs: vec![],
+            outputs: vec![],
+            public_key: None,
+            checks: vec![],
+            chunks: None,
+            contributions: vec![],
+            beacon: None,
+            error: None,
+            runtime_seconds: 0.0,
+            started: Instant::now(),
+        }
+    }
+
+    pub fn input(&mut self, name: &str, hash: &[u8]) {
+        self.inputs.push(FileHash {
+            name: name.to_owned(),
+            blake2b: hex::encode(hash),
+        });
+    }
+
+    pub fn output(&mut self, name: &str, hash: &[u8]) {
+        self.outputs.push(FileHash {
+            name: name.to_owned(),
+            blake2b: hex::encode(hash),
+        });
+    }
+
+    pub fn public_key<E: Engine>(&mut self, key: &PublicKey<E>) {
+        self.public_key = Some(public_key_hex(key));
+    }
+
+    /// Chunks an operation goes through, when it doesn't verify anything.
+    pub fn batches(&mut self, parameters: &CeremonyParameters) {
+        let batches = |len: usize| {
+            (len + parameters.empirical_batch_size - 1) / parameters.empirical_batch_size
+        };
+        self.chunks = Some(
+            batches(parameters.tau_powers_length())
+                + batches(parameters.tau_powers_g1_length() - parameters.tau_powers_length()),
+        );
+    }
+
+    pub fn verification(&mut self, report: &VerificationReport) {
+        self.checks = checks(report);
+        self.chunks = Some(report.chunks);
+    }
+
+    pub fn contribution<E: Engine>(
+        &mut self,
+        response: &str,
+        challenge_hash: &[u8],
+        response_hash: &[u8],
+        key: &PublicKey<E>,
+        report: &VerificationReport,
+    ) {
+        self.contributions.push(Contribution {
+            response: response.to_owned(),
+            challenge_hash: hex::encode(challenge_hash),
+            response_hash: hex::encode(response_hash),
+            public_key: public_key_hex(key),
+            checks: checks(report),
+            chunks: report.chunks,
+        });
+    }
+
+    /// `beacon` hashed `2^iterations` times into `result`.
+    pub fn beacon(&mut self, beacon: &Beacon, iterations: usize, result: &[u8]) {
+        self.beacon = Some(BeaconRecord {
+            beacon: beacon.clone(),
+            iterations,
+            result: hex::encode(result),
+        });
+    }
+
+    /// Writes report to `--report`, if set, noting `error` the operation failed with.
+    pub fn finish(mut self, config: &Config, error: Option<String>) {
+        let path = match config.report {
+            Some(ref path) => path,
+            None => return,
+        };
+        self.error = error;
+        self.runtime_seconds = self.started.elapsed().as_secs_f64();
+
+        let file =
+            File::create(path).unwrap_or_else(|e| panic!("unable to create `{}`: {}", path, e));
+        serde_json::to_writer_pretty(file, &self)
+            .unwrap_or_else(|e| panic!("unable to write report to `{}`: {}", path, e));
+        println!("Wrote a report to `{}`", path);
+    }
+
+    /// Writes report noting `error`, then fails with it.
+    pub fn fail(self, config: &Config, error: String) -> ! {
+        self.finish(config, Some(error.clone()));
+        panic!("{}", error);
+    }
+}
+
+fn checks(report: &VerificationReport) -> Vec<Check> {
+    report
+        .checks
+        .iter()
+        .map(|record| Check {
+            check: record.scope.check.to_string(),
+            element: format!("{:?}", record.scope.element),
+            chunk: record
+                .scope
+                .chunk
+                .as_ref()
+                .map(|chunk| [chunk.start, chunk.end]),
+            passed: record.passed,
+            seconds: record.elapsed.as_secs_f64(),
+        })
+        .collect()
+}
+
+pub fn public_key_hex<E: Engine>(key: &PublicKey<E>) -> String {
+    let mut bytes = vec![];
+    key.serialize(&mut bytes)
+        .expect("must serialize a public key");
+    hex::encode(bytes)
+}
@@ -0,0 +1,86 @@
+use crate::config::{self, Config};
+use crate::print_hash;
+use crate::report::{self, Report};
+use bellman::pairing::Engine;
+use powersoftau::parameters::UseCompression;
+use powersoftau::transcript::{verify_transcript, Scratch};
+use std::path::{Path, PathBuf};
+
+/// `powersoftau transcript --responses <path>,<path>... [--challenge <path>] [--scratch <dir>]
+/// [--response-compressed yes|no] [--report <path>]`
+///
+/// Without `--challenge`, first response must contribute on top of the initial accumulator
+/// `new` generates.
+pub fn run<E: Engine>(config: &Config) {
+    let responses = match config.responses {
+        Some(ref responses) if !responses.is_empty() => responses,
+        _ => panic!("`--responses` must list at least one response"),
+    };
+    let challenge = config.challenge.as_deref();
+    let response_compression = config::compression(config.response_compressed, UseCompression::Yes);
+    let scratch = match config.scratch {
+        Some(ref dir) => Scratch::Dir(PathBuf::from(dir)),
+        None => Scratch::Memory,
+    };
+    let parameters = match challenge {
+        Some(path) => config.parameters(Some(path), |p| p.challenge_size(UseCompression::No)),
+        None => config.parameters(Some(&responses[0]), |p| {
+            p.response_size(response_compression)
+        }),
+    };
+    let mut report = Report::start("transcript", config, &parameters);
+
+    println!(
+        "Will verify a transcript of {} contributions to accumulator for 2^{} powers of tau",
+        responses.len(),
+        parameters.required_power
+    );
+
+    let transcript = match verify_transcript::<E, _>(
+        &parameters,
+        challenge.map(Path::new),
+        responses,
+        response_compression,
+        &scratch,
+    ) {
+        Ok(transcript) => transcript,
+        Err(e) => report.fail(config, format!("INVALID TRANSCRIPT: {}", e)),
+    };
+
+    println!("Hash of the initial challenge:");
+    print_hash(transcript.initial_hash.as_slice());
+    report.input(
+        challenge.unwrap_or("initial accumulator"),
+        transcript.initial_hash.as_slice(),
+    );
+
+    for (i, contribution) in transcript.contributions.iter().enumerate() {
+        println!(
+            "\nContribution #{} in `{}`, response hash:",
+            i,
+            contribution.response.display()
+        );
+        print_hash(contribution.response_hash.as_slice());
+        println!(
+            "public key: {}",
+            report::public_key_hex(&contribution.public_key)
+        );
+
+        let response = contribution.response.to_string_lossy();
+        report.input(&response, contribution.response_hash.as_slice());
+        report.contribution(
+            &response,
+            contribution.challenge_hash.as_slice(),
+            contribution.response_hash.as_slice(),
+            &contribution.public_key,
+            &contribution.report,
+        );
+    }
+
+    println!("\nHash of the final accumulator:");
+    print_hash(transcript.final_hash.as_slice());
+    println!("Transcript is valid!");
+
+    report.output("final accumulator", transcript.final_hash.as_slice());
+    report.finish(config, None);
+}
@@ -0,0 +1,297 @@
+use crate::config::{self, Config};
+use crate::print_hash;
+use crate::report::{self, Report};
+use bellman::pairing::Engine;
+use memmap::*;
+use powersoftau::audit::{AuditLog, Contribution};
+use powersoftau::batched_accumulator::BachedAccumulator;
+use powersoftau::keypair::PublicKey;
+use powersoftau::parameters::{CheckForCorrectness, UseCompression, VerificationReport};
+use std::fs::{File, OpenOptions};
+use std::io::{Read, Write};
+use std::path::Path;
+
+/// `powersoftau verify [--challenge <path>] [--response <path>] [--new-challenge <path>]
+/// [--challenge-compressed yes|no] [--response-compressed yes|no]
+/// [--new-challenge-compressed yes|no] [--check-challenge yes|no] [--check-response yes|no]
+/// [--report <path>] [--audit-log <path>]`
+pub fn run<E: Engine>(config: &Config) {
+    let challenge = config::path(&config.challenge, "challenge");
+    let response = config::path(&config.response, "response");
+    let new_challenge = config::path(&config.new_challenge, "new_challenge");
+    let previous_challenge_is_compressed =
+        config::compression(config.challenge_compressed, UseCompression::No);
+    let contribution_is_compressed =
+        config::compression(config.response_compressed, UseCompression::Yes);
+    let compress_new_challenge =
+        config::compression(config.new_challenge_compressed, UseCompression::No);
+    let check_challenge = config::check(config.check_challenge, CheckForCorrectness::No);
+    let check_response = config::check(config.check_response, CheckForCorrectness::Yes);
+    let parameters = config.parameters(Some(challenge), |p| {
+        p.challenge_size(previous_challenge_is_compressed)
+    });
+    let mut report = Report::start("verify", config, &parameters);
+
+    println!(
+        "Will verify and decompress a contribution to accumulator for 2^{} powers of tau",
+        parameters.required_power
+    );
+
+    let challenge_reader = open_sized(
+        challenge,
+        parameters.challenge_size(previous_challenge_is_compressed),
+    );
+    let challenge_readable_map = unsafe {
+        MmapOptions::new()
+            .map(&challenge_reader)
+            .expect("unable to create a memory map for input")
+    };
+
+    let response_reader = open_sized(
+        response,
+        parameters.response_size(contribution_is_compressed),
+    );
+    let response_readable_map = unsafe {
+        MmapOptions::new()
+            .map(&response_reader)
+            .expect("unable to create a memory map for input")
+    };
+
+    println!("Calculating previous challenge hash...");
+
+    // Check that contribution is correct
+
+    let current_accumulator_hash = BachedAccumulator::<E>::calculate_hash(&challenge_readable_map);
+
+    println!("Hash of the `{}` file for verification:", challenge);
+    print_hash(current_accumulator_hash.as_slice());
+    report.input(challenge, current_accumulator_hash.as_slice());
+
+    let response_hash = BachedAccumulator::<E>::calculate_hash(&response_readable_map);
+
+    println!("Hash of the `{}` file for verification:", response);
+    print_hash(response_hash.as_slice());
+    report.input(response, response_hash.as_slice());
+
+    if let Err(e) = audit_names(
+        config,
+        (challenge, current_accumulator_hash.as_slice()),
+        (response, response_hash.as_slice()),
+    ) {
+        report.fail(config, e);
+    }
+
+    // Check the hash chain - a new response must be based on the previous challenge!
+    {
+        let mut response_challenge_hash = [0; 64];
+        let mut memory_slice = response_readable_map
+            .get(0..64)
+            .expect("must read point data from file");
+        memory_slice
+            .read_exact(&mut response_challenge_hash)
+            .expect("couldn't read hash of challenge file from response file");
+
+        println!("`{}` was based on the hash:", response);
+        print_hash(&response_challenge_hash);
+
+        if &response_challenge_hash[..] != current_accumulator_hash.as_slice() {
+            let error = "Hash chain failure. This is not the right response.".to_owned();
+            audit(
+                config,
+                (challenge, current_accumulator_hash.as_slice()),
+                (response, response_hash.as_slice()),
+                None,
+                Some(&error),
+            );
+            report.fail(config, error);
+        }
+    }
+
+    // get the contributor's public key
+    let public_key = PublicKey::<E>::read(
+        &response_readable_map,
+        contribution_is_compressed,
+        &parameters,
+    )
+    .expect("wasn't able to deserialize the response file's public key");
+    report.public_key(&public_key);
+
+    // check that it follows the protocol
+
+    println!(
+        "Verifying a contribution to contain proper powers and correspond to the public key..."
+    );
+
+    let mut verification = VerificationReport::default();
+    let verified = BachedAccumulator::<E>::verify_transformation_with_report(
+        &challenge_readable_map,
+        &response_readable_map,
+        &public_key,
+        current_accumulator_hash.as_slice(),
+        previous_challenge_is_compressed,
+        contribution_is_compressed,
+        check_challenge,
+        check_response,
+        &parameters,
+        &mut verification,
+    );
+    report.verification(&verification);
+    let error = verified.err().map(|e| e.to_string());
+    audit(
+        config,
+        (challenge, current_accumulator_hash.as_slice()),
+        (response, response_hash.as_slice()),
+        Some(report::public_key_hex(&public_key)),
+        error.as_deref(),
+    );
+    if let Some(e) = error {
+        report.fail(config, format!("INVALID CONTRIBUTION!!! {}", e));
+    }
+
+    println!(
+        "Verification succeeded! {} checks passed over {} chunks.",
+        verification.checks.len(),
+        verification.chunks
+    );
+
+    if compress_new_challenge == UseCompression::Yes {
+        println!(
+            "Don't need to recompress the contribution, please copy `{}` as `{}`",
+            response, new_challenge
+        );
+    } else {
+        println!("Verification succeeded! Writing to `{}`...", new_challenge);
+
+        let writer = OpenOptions::new()
+            .read(true)
+            .write(true)
+            .create_new(true)
+            .open(new_challenge)
+            .unwrap_or_else(|e| panic!("unable to create `{}`: {}", new_challenge, e));
+
+        // Recomputation stips the public key and uses hashing to link with the previous contibution after decompression
+        writer
+            .set_len(parameters.accumulator_byte_size() as u64)
+            .expect("must make output file large enough");
+
+        let mut writable_map = unsafe {
+            MmapOptions::new()
+                .map_mut(&writer)
+                .expect("unable to create a memory map for output")
+        };
+
+        {
+            (&mut writable_map[0..])
+                .write(response_hash.as_slice())
+                .expect("unable to write a default hash to mmap");
+
+            writable_map
+                .flush()
+                .unwrap_or_else(|e| panic!("unable to write hash to `{}`: {}", new_challenge, e));
+        }
+
+        BachedAccumulator::<E>::decompress(
+            &response_readable_map,
+            &mut writable_map,
+            CheckForCorrectness::No,
+            &parameters,
+        )
+        .expect("must decompress a response for a new challenge");
+
+        writable_map.flush().expect("must flush the memory map");
+
+        let new_challenge_readable_map = writable_map
+            .make_read_only()
+            .expect("must make a map readonly");
+
+        let recompressed_hash = BachedAccumulator::<E>::calculate_hash(&new_challenge_readable_map);
+
+        println!(
+            "Here's the BLAKE2b hash of the decompressed participant's response as `{}` file:",
+            new_challenge
+        );
+        print_hash(recompressed_hash.as_slice());
+        report.output(new_challenge, recompressed_hash.as_slice());
+
+        println!(
+            "Done! `{}` contains the new challenge file. The other files",
+            new_challenge
+        );
+        println!("were left alone.");
+    }
+
+    report.finish(config, None);
+}
+
+/// Appends outcome of verifying `(path, hash)` of response against those of challenge to
+/// `--audit-log`, if it's set.
+fn audit(
+    config: &Config,
+    challenge: (&str, &[u8]),
+    response: (&str, &[u8]),
+    public_key: Option<String>,
+    error: Option<&str>,
+) {
+    let path = match config.audit_log {
+        Some(ref path) => path,
+        None => return,
+    };
+    let mut log = AuditLog::open(Path::new(path))
+        .unwrap_or_else(|e| panic!("unable to open audit log `{}`: {}", path, e));
+    let entry = log
+        .append(Contribution {
+            challenge: challenge.0.to_owned(),
+            challenge_hash: hex::encode(challenge.1),
+            response: response.0.to_owned(),
+            response_hash: hex::encode(response.1),
+            public_key,
+            verified: error.is_none(),
+            error: error.map(str::to_owned),
+        })
+        .unwrap_or_else(|e| panic!("unable to append to audit log `{}`: {}", path, e));
+    println!(
+        "Appended entry #{} to audit log `{}`, with hash {}",
+        entry.index, path, entry.hash
+    );
+}
+
+/// Checks that `--audit-log`, if it's set, can name challenge & response by their paths, which
+/// must then be unique to the round, for `audit` to find them afterwards.
+fn audit_names(
+    config: &Config,
+    challenge: (&str, &[u8]),
+    response: (&str, &[u8]),
+) -> Result<(), String> {
+    let path = match config.audit_log {
+        Some(ref path) => path,
+        None => return Ok(()),
+    };
+    let log = AuditLog::open(Path::new(path))
+        .unwrap_or_else(|e| panic!("unable to open audit log `{}`: {}", path, e));
+    log.check_names(
+        (challenge.0, &hex::encode(challenge.1)),
+        (response.0, &hex::encode(response.1)),
+    )
+    .map_err(|e| format!("Can't log to `{}`: {}", path, e))
+}
+
+/// Opens a file, which must be of `expected_length` bytes.
+pub fn open_sized(path: &str, expected_length: usize) -> File {
+    let file = OpenOptions::new()
+        .read(true)
+        .open(path)
+        .unwrap_or_else(|e| panic!("unable open `{}`: {}", path, e));
+
+    let metadata = file
+        .metadata()
+        .unwrap_or_else(|e| panic!("unable to get filesystem metadata for `{}`: {}", path, e));
+    if metadata.len() != (expected_length as u64) {
+        panic!(
+            "The size of `{}` should be {}, but it's {}, so something isn't right.",
+            path,
+            expected_length,
+            metadata.len()
+        );
+    }
+    file
+}
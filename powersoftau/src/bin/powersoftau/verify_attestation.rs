@@ -0,0 +1,54 @@
+use crate::config::{self, Config};
+use powersoftau::attestation::Attestation;
+use powersoftau::coordinator::State;
+use std::path::Path;
+
+/// `powersoftau verify-attestation [--attestation <path>] [--response <path>] [--dir <path>]`
+///
+/// Checks signature of attestation bundle, that it's about `--response`, and, if `--dir` is set,
+/// that coordinator keeping its transcript there accepted that response.
+pub fn run(config: &Config) {
+    let path = config::path(&config.attestation, "attestation.json");
+    let response = config::path(&config.response, "response");
+
+    let attestation = Attestation::read(Path::new(path))
+        .unwrap_or_else(|e| panic!("unable to read attestation `{}`: {}", path, e));
+    let statement = attestation
+        .verify()
+        .unwrap_or_else(|e| panic!("ATTESTATION FAILED for `{}`: {}", path, e));
+
+    println!(
+        "`{}` is signed by `{}`{}",
+        path,
+        attestation.signer,
+        statement
+            .participant
+            .as_ref()
+            .map(|p| format!(" on behalf of `{}`", p))
+            .unwrap_or_default()
+    );
+    println!(
+        "Made by {} for 2^{} powers of tau over {}",
+        statement.tool, statement.power, statement.curve
+    );
+    println!("Hash of entropy: {}", statement.entropy_hash);
+
+    statement
+        .check_response(Path::new(response))
+        .unwrap_or_else(|e| panic!("ATTESTATION FAILED for `{}`: {}", response, e));
+    println!("`{}` is the response attested to", response);
+
+    if let Some(ref dir) = config.dir {
+        let state = State::load(Path::new(dir))
+            .unwrap_or_else(|e| panic!("unable to read ceremony in `{}`: {}", dir, e));
+        let round = statement
+            .find_in(&state)
+            .unwrap_or_else(|e| panic!("ATTESTATION FAILED: {}", e));
+        println!(
+            "Coordinator accepted it as contribution of round {} by `{}`",
+            round, state.contributions[round].participant
+        );
+    }
+
+    println!("Attestation is valid!");
+}
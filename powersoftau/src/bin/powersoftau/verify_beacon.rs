@@ -0,0 +1,115 @@
+use crate::beacon::{self, read_beacon, DEFAULT_BEACON_ITERATIONS, DEFAULT_CHECKPOINTS};
+use crate::config::{self, Config};
+use crate::print_hash;
+use crate::report::Report;
+use crate::verify::open_sized;
+use bellman::pairing::Engine;
+use memmap::*;
+use powersoftau::batched_accumulator::BachedAccumulator;
+use powersoftau::beacon::{HashChain, SEGMENTS};
+use powersoftau::keypair::{keypair, PublicKey};
+use powersoftau::parameters::UseCompression;
+use std::path::Path;
+use std::time::Instant;
+
+/// `powersoftau verify-beacon [--beacon-checkpoints <path>] [--challenge <path>]
+/// [--response <path>] [--response-compressed yes|no]`, along with flags of `powersoftau beacon`
+/// setting its source
+///
+/// Checks every segment of beacon hash chain in parallel, then that its final state is what
+/// contribution in `--response` was sampled from.
+pub fn run<E: Engine>(config: &Config) {
+    let challenge = config::path(&config.challenge, "challenge");
+    let response = config::path(&config.response, "response");
+    let checkpoints = config::path(&config.beacon_checkpoints, DEFAULT_CHECKPOINTS);
+    let response_is_compressed =
+        config::compression(config.response_compressed, UseCompression::Yes);
+    let n = config
+        .beacon_iterations
+        .unwrap_or(DEFAULT_BEACON_ITERATIONS);
+    let parameters = config.parameters(Some(challenge), |p| p.challenge_size(UseCompression::No));
+    let mut report = Report::start("verify-beacon", config, &parameters);
+
+    let beacon = match read_beacon(config) {
+        Ok(beacon) => beacon,
+        Err(e) => report.fail(config, e),
+    };
+    println!("Beacon value: {}", hex::encode(beacon.value()));
+
+    let chain = match HashChain::load(Path::new(checkpoints), beacon.value(), n) {
+        Ok(chain) => chain,
+        Err(e) => report.fail(
+            config,
+            format!("unable to read checkpoints `{}`: {}", checkpoints, e),
+        ),
+    };
+
+    println!(
+        "Verifying {} segments of 2^{} hashes on {} cores...",
+        SEGMENTS,
+        n - 10,
+        num_cpus::get()
+    );
+    let started = Instant::now();
+    let result = match chain.verify() {
+        Ok(result) => result,
+        Err(e) => report.fail(config, format!("BEACON HASH CHAIN FAILED: {}", e)),
+    };
+    println!(
+        "Hash chain verified in {:.1}s, final result of beacon: {}",
+        started.elapsed().as_secs_f64(),
+        hex::encode(result)
+    );
+    report.beacon(&beacon, n, &result);
+
+    let challenge_reader = open_sized(challenge, parameters.challenge_size(UseCompression::No));
+    let challenge_map = unsafe {
+        MmapOptions::new()
+            .map(&challenge_reader)
+            .expect("unable to create a memory map for input")
+    };
+    let response_reader = open_sized(response, parameters.response_size(response_is_compressed));
+    let response_map = unsafe {
+        MmapOptions::new()
+            .map(&response_reader)
+            .expect("unable to create a memory map for input")
+    };
+
+    let challenge_hash = BachedAccumulator::<E>::calculate_hash(&challenge_map);
+    println!("Hash of the `{}` file:", challenge);
+    print_hash(challenge_hash.as_slice());
+    report.input(challenge, challenge_hash.as_slice());
+    report.input(
+        response,
+        BachedAccumulator::<E>::calculate_hash(&response_map).as_slice(),
+    );
+
+    if response_map.get(0..64) != Some(challenge_hash.as_slice()) {
+        report.fail(
+            config,
+            format!("`{}` isn't based on `{}`", response, challenge),
+        );
+    }
+
+    let public_key = PublicKey::<E>::read(&response_map, response_is_compressed, &parameters)
+        .expect("wasn't able to deserialize the response file's public key");
+    report.public_key(&public_key);
+
+    // same keypair beacon sampled, if contribution came from it
+    let (expected, _) = keypair::<_, E>(&mut beacon::rng(&result), challenge_hash.as_ref());
+    if public_key != expected {
+        report.fail(
+            config,
+            format!(
+                "BEACON CONTRIBUTION FAILED, public key of `{}` wasn't sampled from beacon",
+                response
+            ),
+        );
+    }
+
+    report.finish(config, None);
+    println!(
+        "Beacon is valid, `{}` was contributed from it! Verify `{}` as any other contribution to check its powers.",
+        response, response
+    );
+}
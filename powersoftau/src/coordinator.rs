@@ -0,0 +1,807 @@
+//! Coordinator of a ceremony, handing the current challenge to a queue of participants one at a
+//! time over a small HTTP API, and verifying their responses as they come in.
+//!
+//! Everything is kept in a transcript directory:
+//!
+//! * `challenge_<n>`: challenge of round `n`, uncompressed
+//! * `response_<n>`: verified response to `challenge_<n>`
+//! * `state.json`: participant queue, lock on current challenge and accepted contributions
+//! * `audit.jsonl`: audit log, an entry for every accepted contribution, see `audit`
+//!
+//! Files are written under a temporary name and renamed into place, while state is saved last, so
+//! a coordinator killed at any point restarts from the last accepted contribution. One killed
+//! after logging a contribution, but before saving state, accepts it on restart.
+//!
+//! API, where `<id>` is a name made of letters, digits, `-` and `_` that a participant picks:
+//!
+//! * `GET /status`: state, as JSON
+//! * `POST /join?participant=<id>`: enqueues a participant
+//! * `POST /lock?participant=<id>`: locks current challenge for participant at head of queue, until
+//!   lock times out
+//! * `GET /challenge?participant=<id>`: current challenge, for participant holding the lock
+//! * `POST /response?participant=<id>`: response to current challenge, from participant holding
+//!   the lock; once it's verified, the next challenge is computed and next participant may lock it
+
+use bellman::pairing::Engine;
+use memmap::{Mmap, MmapMut, MmapOptions};
+use serde::{Deserialize, Serialize};
+use std::fmt;
+use std::fs::{self, File, OpenOptions};
+use std::io::{self, BufRead, BufReader, Read, Write};
+use std::net::{TcpListener, TcpStream};
+use std::path::{Path, PathBuf};
+use std::sync::{Arc, Mutex};
+use std::thread;
+use std::time::{Duration, SystemTime, UNIX_EPOCH};
+
+use super::audit::{AuditError, AuditLog, Contribution};
+use super::batched_accumulator::BachedAccumulator;
+use super::keypair::PublicKey;
+use super::parameters::{CeremonyParameters, CheckForCorrectness, UseCompression};
+use super::utils::blank_hash;
+
+const STATE: &str = "state.json";
+const UPLOAD: &str = "upload.tmp";
+const AUDIT_LOG: &str = "audit.jsonl";
+
+/// Progress of a ceremony, as saved in `state.json`.
+#[derive(Clone, Debug, Default, PartialEq, Serialize, Deserialize)]
+pub struct State {
+    /// number of contributions accepted so far
+    pub round: usize,
+    /// participants waiting for their turn, first one is next to contribute
+    pub queue: Vec<String>,
+    pub lock: Option<Lock>,
+    pub contributions: Vec<Accepted>,
+}
+
+impl State {
+    /// Reads state of ceremony kept in `dir`.
+    pub fn load(dir: &Path) -> Result<State, CoordinatorError> {
+        Ok(serde_json::from_slice(&fs::read(dir.join(STATE))?)?)
+    }
+}
+
+/// Current challenge is reserved for a participant until `expires_at`.
+#[derive(Clone, Debug, PartialEq, Serialize, Deserialize)]
+pub struct Lock {
+    pub participant: String,
+    /// seconds since Unix epoch
+    pub expires_at: u64,
+}
+
+/// A verified contribution.
+#[derive(Clone, Debug, PartialEq, Serialize, Deserialize)]
+pub struct Accepted {
+    pub participant: String,
+    /// hex encoded BLAKE2b hashes
+    pub challenge_hash: String,
+    pub response_hash: String,
+}
+
+#[derive(Debug)]
+pub enum CoordinatorError {
+    IoError(io::Error),
+    State(serde_json::Error),
+    Audit(AuditError),
+    BadRequest(String),
+    /// participant isn't allowed to do that now
+    Forbidden(String),
+    /// state and audit log disagree in a way a crash can't explain
+    Inconsistent(String),
+    NotFound,
+    /// response was uploaded, but isn't a valid contribution
+    InvalidResponse(String),
+}
+
+impl fmt::Display for CoordinatorError {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        match *self {
+            CoordinatorError::IoError(ref e) => write!(f, "Disk IO error: {}", e),
+            CoordinatorError::State(ref e) => write!(f, "Invalid state: {}", e),
+            CoordinatorError::Audit(ref e) => write!(f, "Audit log: {}", e),
+            CoordinatorError::BadRequest(ref e) => write!(f, "Bad request: {}", e),
+            CoordinatorError::Forbidden(ref e) => write!(f, "Forbidden: {}", e),
+            CoordinatorError::Inconsistent(ref e) => write!(f, "Inconsistent transcript: {}", e),
+            CoordinatorError::NotFound => write!(f, "Not found"),
+            CoordinatorError::InvalidResponse(ref e) => write!(f, "Invalid response: {}", e),
+        }
+    }
+}
+
+impl From<io::Error> for CoordinatorError {
+    fn from(err: io::Error) -> CoordinatorError {
+        CoordinatorError::IoError(err)
+    }
+}
+
+impl From<serde_json::Error> for CoordinatorError {
+    fn from(err: serde_json::Error) -> CoordinatorError {
+        CoordinatorError::State(err)
+    }
+}
+
+impl From<AuditError> for CoordinatorError {
+    fn from(err: AuditError) -> CoordinatorError {
+        CoordinatorError::Audit(err)
+    }
+}
+
+impl CoordinatorError {
+    fn status(&self) -> &'static str {
+        match *self {
+            CoordinatorError::IoError(_)
+            | CoordinatorError::State(_)
+            | CoordinatorError::Audit(_)
+            | CoordinatorError::Inconsistent(_) => "500 Internal Server Error",
+            CoordinatorError::BadRequest(_) => "400 Bad Request",
+            CoordinatorError::Forbidden(_) => "403 Forbidden",
+            CoordinatorError::NotFound => "404 Not Found",
+            CoordinatorError::InvalidResponse(_) => "422 Unprocessable Entity",
+        }
+    }
+}
+
+pub struct Coordinator {
+    dir: PathBuf,
+    parameters: CeremonyParameters,
+    response_compression: UseCompression,
+    lock_timeout: Duration,
+    state: State,
+    audit: AuditLog,
+}
+
+impl Coordinator {
+    /// Resumes ceremony kept in `dir`, or starts a new one there from the accumulator `new`
+    /// generates.
+    pub fn open<E: Engine>(
+        dir: &Path,
+        parameters: &CeremonyParameters,
+        response_compression: UseCompression,
+        lock_timeout: Duration,
+    ) -> Result<Coordinator, CoordinatorError> {
+        fs::create_dir_all(dir)?;
+
+        // leftovers of a crash, rounds only advance once state is saved
+        for entry in fs::read_dir(dir)? {
+            let path = entry?.path();
+            if path.extension().map_or(false, |e| e == "tmp") {
+                fs::remove_file(path)?;
+            }
+        }
+
+        let mut coordinator = Coordinator {
+            dir: dir.to_path_buf(),
+            parameters: *parameters,
+            response_compression,
+            lock_timeout,
+            state: State::default(),
+            audit: AuditLog::open(&dir.join(AUDIT_LOG))?,
+        };
+
+        if dir.join(STATE).exists() {
+            coordinator.state = State::load(dir)?;
+            coordinator.reconcile()?;
+        } else {
+            let (mut map, temporary) =
+                coordinator.create("challenge_0", parameters.accumulator_byte_size())?;
+            (&mut map[0..]).write(blank_hash().as_slice())?;
+            BachedAccumulator::<E>::generate_initial(&mut map, UseCompression::No, parameters)?;
+            map.flush()?;
+            drop(map);
+            coordinator.commit(&temporary, "challenge_0")?;
+            coordinator.save()?;
+        }
+
+        Ok(coordinator)
+    }
+
+    pub fn state(&self) -> &State {
+        &self.state
+    }
+
+    /// Audit log is appended to once files of a contribution are in place, before state is
+    /// saved, so a crash in between leaves log an entry ahead. That contribution was verified,
+    /// so it's accepted as it would've been, from participant still holding the lock.
+    fn reconcile(&mut self) -> Result<(), CoordinatorError> {
+        let round = self.state.round as u64;
+        if self.audit.len() == round {
+            return Ok(());
+        }
+        let inconsistent = |e: String| Err(CoordinatorError::Inconsistent(e));
+        if self.audit.len() != round + 1 {
+            return inconsistent(format!(
+                "audit log holds {} entries, while ceremony is at round {}",
+                self.audit.len(),
+                round
+            ));
+        }
+
+        let logged = match self.audit.last()? {
+            Some(entry) => entry.contribution,
+            None => return inconsistent("audit log is empty".to_owned()),
+        };
+        let next = self.dir.join(format!("challenge_{}", round + 1));
+        if logged.response != format!("response_{}", round) || !next.exists() {
+            return inconsistent(format!(
+                "last entry of audit log isn't a contribution to round {}",
+                round
+            ));
+        }
+        let participant = match self.state.lock {
+            Some(ref lock) => lock.participant.clone(),
+            None => {
+                return inconsistent(format!(
+                    "contribution to round {} is logged, but no one held the lock",
+                    round
+                ))
+            }
+        };
+
+        self.state.round += 1;
+        self.state.queue.retain(|p| *p != participant);
+        self.state.lock = None;
+        self.state.contributions.push(Accepted {
+            participant,
+            challenge_hash: logged.challenge_hash,
+            response_hash: logged.response_hash,
+        });
+        self.save()
+    }
+
+    /// Path of challenge of current round.
+    pub fn challenge_path(&self) -> PathBuf {
+        self.dir.join(format!("challenge_{}", self.state.round))
+    }
+
+    /// Enqueues `participant`, unless it's queued already, returning its position in queue.
+    pub fn join(&mut self, participant: &str) -> Result<usize, CoordinatorError> {
+        if let Some(position) = self.state.queue.iter().position(|p| p == participant) {
+            return Ok(position);
+        }
+        self.state.queue.push(participant.to_owned());
+        self.save()?;
+
+        Ok(self.state.queue.len() - 1)
+    }
+
+    /// Locks current challenge for `participant`, which must be first in queue. Locking again
+    /// while holding the lock doesn't extend it.
+    pub fn lock(&mut self, participant: &str, now: u64) -> Result<Lock, CoordinatorError> {
+        self.expire(now)?;
+        match self.state.lock {
+            Some(ref lock) if lock.participant == participant => return Ok(lock.clone()),
+            Some(_) => {
+                return Err(CoordinatorError::Forbidden(
+                    "challenge is locked by another participant".to_owned(),
+                ))
+            }
+            None => {}
+        }
+        if self.state.queue.first().map(String::as_str) != Some(participant) {
+            return Err(CoordinatorError::Forbidden(
+                "participant isn't first in queue".to_owned(),
+            ));
+        }
+
+        let lock = Lock {
+            participant: participant.to_owned(),
+            expires_at: now + self.lock_timeout.as_secs(),
+        };
+        self.state.lock = Some(lock.clone());
+        self.save()?;
+
+        Ok(lock)
+    }
+
+    /// Path of current challenge, if `participant` holds the lock on it.
+    pub fn challenge(&mut self, participant: &str, now: u64) -> Result<PathBuf, CoordinatorError> {
+        self.check_lock(participant, now)?;
+        Ok(self.challenge_path())
+    }
+
+    /// Takes `len` bytes of response of `participant` from `body`, then verifies it against
+    /// current challenge and computes next one. An invalid response leaves the lock in place,
+    /// so participant may upload again until it times out.
+    pub fn contribute<E: Engine, R: Read>(
+        &mut self,
+        participant: &str,
+        body: &mut R,
+        len: u64,
+        now: u64,
+    ) -> Result<Accepted, CoordinatorError> {
+        let upload = self.upload(participant, len, now)?;
+        upload.receive(body)?;
+        self.accept::<E>(upload)
+    }
+
+    /// Checks that `participant` may upload a response of `len` bytes, returning where it's to
+    /// be received. Receiving doesn't touch state, so it may be done with coordinator unlocked.
+    pub fn upload(
+        &mut self,
+        participant: &str,
+        len: u64,
+        now: u64,
+    ) -> Result<Upload, CoordinatorError> {
+        self.check_lock(participant, now)?;
+
+        let expected = self.parameters.response_size(self.response_compression) as u64;
+        if len != expected {
+            return Err(CoordinatorError::BadRequest(format!(
+                "response should be {}b, but it's {}b",
+                expected, len
+            )));
+        }
+
+        Ok(Upload {
+            participant: participant.to_owned(),
+            path: self.dir.join(UPLOAD),
+            len,
+            started: now,
+        })
+    }
+
+    /// Verifies a received response against current challenge and computes next one. Lock is
+    /// checked as of when upload started, so one taking longer than what's left of it still
+    /// counts, unless another participant has locked challenge since.
+    pub fn accept<E: Engine>(&mut self, upload: Upload) -> Result<Accepted, CoordinatorError> {
+        let accepted = self
+            .check_lock(&upload.participant, upload.started)
+            .and_then(|_| self.verify::<E>(&upload));
+        if accepted.is_err() {
+            // frees `upload.tmp` for the next attempt
+            let _ = fs::remove_file(&upload.path);
+        }
+        accepted
+    }
+
+    fn verify<E: Engine>(&mut self, upload: &Upload) -> Result<Accepted, CoordinatorError> {
+        let participant = upload.participant.as_str();
+        let upload = &upload.path;
+
+        let challenge = map(&self.challenge_path())?;
+        let response = map(&upload)?;
+        let challenge_hash = BachedAccumulator::<E>::calculate_hash(&challenge);
+        if &response[0..self.parameters.hash_size()] != challenge_hash.as_slice() {
+            return Err(CoordinatorError::InvalidResponse(
+                "response isn't based on current challenge".to_owned(),
+            ));
+        }
+        let public_key =
+            PublicKey::<E>::read(&response, self.response_compression, &self.parameters)
+                .map_err(|e| CoordinatorError::InvalidResponse(e.to_string()))?;
+        // challenges are generated or recomputed here, so only response needs its points checked
+        BachedAccumulator::<E>::verify_transformation(
+            &challenge,
+            &response,
+            &public_key,
+            challenge_hash.as_slice(),
+            UseCompression::No,
+            self.response_compression,
+            CheckForCorrectness::No,
+            CheckForCorrectness::Yes,
+            &self.parameters,
+        )
+        .map_err(|e| CoordinatorError::InvalidResponse(e.to_string()))?;
+        let response_hash = BachedAccumulator::<E>::calculate_hash(&response);
+
+        // next challenge, linked to this response by its hash
+        let next = format!("challenge_{}", self.state.round + 1);
+        let (mut map, temporary) = self.create(&next, self.parameters.accumulator_byte_size())?;
+        match self.response_compression {
+            UseCompression::Yes => BachedAccumulator::<E>::decompress(
+                &response,
+                &mut map,
+                CheckForCorrectness::No,
+                &self.parameters,
+            )?,
+            UseCompression::No => {
+                map.copy_from_slice(&response[..self.parameters.accumulator_byte_size()])
+            }
+        }
+        (&mut map[0..]).write(response_hash.as_slice())?;
+        map.flush()?;
+        drop((map, challenge, response));
+
+        self.commit(&temporary, &next)?;
+        let response_name = format!("response_{}", self.state.round);
+        fs::rename(&upload, self.dir.join(&response_name))?;
+
+        // logged before state is saved, `reconcile` accepts it on restart if that's interrupted
+        let mut key = vec![];
+        public_key.serialize(&mut key)?;
+        self.audit.append(Contribution {
+            challenge: format!("challenge_{}", self.state.round),
+            challenge_hash: hex::encode(challenge_hash.as_slice()),
+            response: response_name,
+            response_hash: hex::encode(response_hash.as_slice()),
+            public_key: Some(hex::encode(key)),
+            verified: true,
+            error: None,
+        })?;
+
+        let accepted = Accepted {
+            participant: participant.to_owned(),
+            challenge_hash: hex::encode(challenge_hash.as_slice()),
+            response_hash: hex::encode(response_hash.as_slice()),
+        };
+        self.state.round += 1;
+        self.state.queue.retain(|p| p != participant);
+        self.state.lock = None;
+        self.state.contributions.push(accepted.clone());
+        self.save()?;
+
+        Ok(accepted)
+    }
+
+    /// Releases a timed out lock, dropping its holder from queue.
+    fn expire(&mut self, now: u64) -> Result<(), CoordinatorError> {
+        let expired = match self.state.lock {
+            Some(ref lock) if lock.expires_at <= now => lock.participant.clone(),
+            _ => return Ok(()),
+        };
+        self.state.lock = None;
+        self.state.queue.retain(|p| *p != expired);
+        self.save()
+    }
+
+    fn check_lock(&mut self, participant: &str, now: u64) -> Result<(), CoordinatorError> {
+        self.expire(now)?;
+        match self.state.lock {
+            Some(ref lock) if lock.participant == participant => Ok(()),
+            _ => Err(CoordinatorError::Forbidden(
+                "participant doesn't hold the lock on challenge".to_owned(),
+            )),
+        }
+    }
+
+    /// Temporary file of `len` bytes, to be committed as `name`.
+    fn create(&self, name: &str, len: usize) -> io::Result<(MmapMut, PathBuf)> {
+        let path = self.dir.join(format!("{}.tmp", name));
+        let file = OpenOptions::new()
+            .read(true)
+            .write(true)
+            .create(true)
+            .truncate(true)
+            .open(&path)?;
+        file.set_len(len as u64)?;
+        let map = unsafe { MmapOptions::new().map_mut(&file)? };
+        Ok((map, path))
+    }
+
+    fn commit(&self, temporary: &Path, name: &str) -> io::Result<()> {
+        File::open(temporary)?.sync_all()?;
+        fs::rename(temporary, self.dir.join(name))
+    }
+
+    fn save(&self) -> Result<(), CoordinatorError> {
+        let temporary = self.dir.join(format!("{}.tmp", STATE));
+        {
+            let mut file = File::create(&temporary)?;
+            serde_json::to_writer_pretty(&mut file, &self.state)?;
+            file.sync_all()?;
+        }
+        fs::rename(temporary, self.dir.join(STATE))?;
+        Ok(())
+    }
+}
+
+/// Response of lock holder, received into `upload.tmp` before it's verified.
+pub struct Upload {
+    participant: String,
+    path: PathBuf,
+    len: u64,
+    /// seconds since Unix epoch
+    started: u64,
+}
+
+impl Upload {
+    /// Takes response from `body`. Only one upload is received at a time, another one is
+    /// refused until this one is accepted or rejected.
+    pub fn receive<R: Read>(&self, body: &mut R) -> Result<(), CoordinatorError> {
+        let mut file = match OpenOptions::new()
+            .write(true)
+            .create_new(true)
+            .open(&self.path)
+        {
+            Ok(file) => file,
+            Err(ref e) if e.kind() == io::ErrorKind::AlreadyExists => {
+                return Err(CoordinatorError::Forbidden(
+                    "another response is being uploaded".to_owned(),
+                ))
+            }
+            Err(e) => return Err(e.into()),
+        };
+
+        let received = match io::copy(&mut body.take(self.len), &mut file) {
+            Ok(copied) if copied == self.len => file.sync_all().map_err(CoordinatorError::from),
+            Ok(copied) => Err(CoordinatorError::BadRequest(format!(
+                "response ended after {}b",
+                copied
+            ))),
+            Err(e) => Err(e.into()),
+        };
+        if received.is_err() {
+            drop(file);
+            let _ = fs::remove_file(&self.path);
+        }
+        received
+    }
+}
+
+fn map(path: &Path) -> io::Result<Mmap> {
+    let file = File::open(path)?;
+    unsafe { MmapOptions::new().map(&file) }
+}
+
+/// Seconds since Unix epoch, as locks count time.
+pub fn unix_now() -> u64 {
+    SystemTime::now()
+        .duration_since(UNIX_EPOCH)
+        .expect("system clock is set after 1970")
+        .as_secs()
+}
+
+/// Connections idle for longer are dropped, so a stalled upload can't hold on to `upload.tmp`.
+const READ_TIMEOUT: Duration = Duration::from_secs(60);
+
+/// Serves `coordinator` on `listener`, a thread per connection. Responses are received
+/// concurrently, while other requests are handled one at a time, so verifying a response holds
+/// up the others.
+pub fn serve<E: Engine>(coordinator: Coordinator, listener: TcpListener) -> io::Result<()> {
+    let coordinator = Arc::new(Mutex::new(coordinator));
+    for stream in listener.incoming() {
+        let stream = stream?;
+        let coordinator = coordinator.clone();
+        thread::spawn(move || {
+            if let Err(e) = handle::<E>(&coordinator, stream) {
+                println!("Unable to handle a request: {}", e);
+            }
+        });
+    }
+    Ok(())
+}
+
+enum Body {
+    Json(Vec<u8>),
+    File(PathBuf),
+}
+
+fn handle<E: Engine>(coordinator: &Mutex<Coordinator>, stream: TcpStream) -> io::Result<()> {
+    stream.set_read_timeout(Some(READ_TIMEOUT))?;
+    let mut reader = BufReader::new(stream.try_clone()?);
+
+    let mut request_line = String::new();
+    reader.read_line(&mut request_line)?;
+    let mut content_length = 0;
+    loop {
+        let mut header = String::new();
+        if reader.read_line(&mut header)? == 0 || header.trim().is_empty() {
+            break;
+        }
+        if let Some((name, value)) = header.split_once(':') {
+            if name.trim().eq_ignore_ascii_case("content-length") {
+                content_length = value.trim().parse().unwrap_or(0);
+            }
+        }
+    }
+
+    let mut parts = request_line.split_whitespace();
+    let method = parts.next().unwrap_or("");
+    let target = parts.next().unwrap_or("");
+    let (path, query) = target.split_once('?').unwrap_or((target, ""));
+    let participant = query
+        .split('&')
+        .filter_map(|pair| pair.split_once('='))
+        .find(|(key, _)| *key == "participant")
+        .map(|(_, value)| value);
+
+    let now = unix_now();
+    let locked = || {
+        coordinator
+            .lock()
+            .expect("coordinator must not be poisoned")
+    };
+    let result = match (method, path) {
+        ("GET", "/status") => json(locked().state()),
+        ("POST", "/join") => participant_id(participant)
+            .and_then(|p| locked().join(p))
+            .and_then(|position| json(&position)),
+        ("POST", "/lock") => participant_id(participant)
+            .and_then(|p| locked().lock(p, now))
+            .and_then(|lock| json(&lock)),
+        ("GET", "/challenge") => participant_id(participant)
+            .and_then(|p| locked().challenge(p, now))
+            .map(Body::File),
+        // body is received with coordinator unlocked, it's locked again to verify it
+        ("POST", "/response") => participant_id(participant)
+            .and_then(|p| locked().upload(p, content_length, now))
+            .and_then(|upload| {
+                upload.receive(&mut reader)?;
+                locked().accept::<E>(upload)
+            })
+            .and_then(|accepted| json(&accepted)),
+        _ => Err(CoordinatorError::NotFound),
+    };
+
+    let mut stream = stream;
+    match result {
+        Ok(Body::Json(body)) => respond(&mut stream, "200 OK", "application/json", &body),
+        Ok(Body::File(path)) => {
+            let mut file = File::open(path)?;
+            let len = file.metadata()?.len();
+            write!(
+                stream,
+                "HTTP/1.1 200 OK\r\nContent-Type: application/octet-stream\r\n\
+                 Content-Length: {}\r\nConnection: close\r\n\r\n",
+                len
+            )?;
+            io::copy(&mut file, &mut stream)?;
+            stream.flush()
+        }
+        Err(e) => {
+            let body = serde_json::to_vec(&serde_json::json!({ "error": e.to_string() }))?;
+            respond(&mut stream, e.status(), "application/json", &body)
+        }
+    }
+}
+
+fn participant_id(participant: Option<&str>) -> Result<&str, CoordinatorError> {
+    match participant {
+        Some(p)
+            if !p.is_empty()
+                && p.chars()
+                    .all(|c| c.is_ascii_alphanumeric() || c == '-' || c == '_') =>
+        {
+            Ok(p)
+        }
+        _ => Err(CoordinatorError::BadRequest(
+            "`participant` must be made of letters, digits, `-` and `_`".to_owned(),
+        )),
+    }
+}
+
+fn json<T: Serialize>(value: &T) -> Result<Body, CoordinatorError> {
+    Ok(Body::Json(serde_json::to_vec_pretty(value)?))
+}
+
+fn respond(
+    stream: &mut TcpStream,
+    status: &str,
+    content_type: &str,
+    body: &[u8],
+) -> io::Result<()> {
+    write!(
+        stream,
+        "HTTP/1.1 {}\r\nContent-Type: {}\r\nContent-Length: {}\r\nConnection: close\r\n\r\n",
+        status,
+        content_type,
+        body.len()
+    )?;
+    stream.write_all(body)?;
+    stream.flush()
+}
+
+#[test]
+fn test_coordinator() {
+    use super::batched_accumulator::{test_contribution, test_parameters};
+    use bellman::pairing::bn256::Bn256;
+    use std::net::SocketAddr;
+
+    fn request(addr: SocketAddr, method: &str, target: &str, body: &[u8]) -> (String, Vec<u8>) {
+        let mut stream = TcpStream::connect(addr).unwrap();
+        write!(
+            stream,
+            "{} {} HTTP/1.1\r\nHost: localhost\r\nContent-Length: {}\r\n\r\n",
+            method,
+            target,
+            body.len()
+        )
+        .unwrap();
+        stream.write_all(body).unwrap();
+
+        let mut response = vec![];
+        stream.read_to_end(&mut response).unwrap();
+        let split = response.windows(4).position(|w| w == b"\r\n\r\n").unwrap();
+        let head = String::from_utf8(response[..split].to_vec()).unwrap();
+        let status = head.split_whitespace().nth(1).unwrap().to_owned();
+        (status, response[split + 4..].to_vec())
+    }
+
+    let parameters = test_parameters();
+    let dir = std::env::temp_dir().join(format!("coordinator-{}", std::process::id()));
+    let timeout = Duration::from_secs(60);
+    let coordinator =
+        Coordinator::open::<Bn256>(&dir, &parameters, UseCompression::Yes, timeout).unwrap();
+
+    let listener = TcpListener::bind("127.0.0.1:0").unwrap();
+    let addr = listener.local_addr().unwrap();
+    thread::spawn(move || serve::<Bn256>(coordinator, listener));
+
+    assert_eq!(
+        request(addr, "POST", "/join?participant=alice", b"").0,
+        "200"
+    );
+    assert_eq!(request(addr, "POST", "/join?participant=bob", b"").0, "200");
+    assert_eq!(request(addr, "POST", "/lock?participant=bob", b"").0, "403");
+    assert_eq!(
+        request(addr, "POST", "/lock?participant=alice", b"").0,
+        "200"
+    );
+    assert_eq!(
+        request(addr, "GET", "/challenge?participant=bob", b"").0,
+        "403"
+    );
+
+    let (status, challenge) = request(addr, "GET", "/challenge?participant=alice", b"");
+    assert_eq!(status, "200");
+    assert_eq!(challenge.len(), parameters.accumulator_byte_size());
+
+    let mut map = MmapOptions::new().len(challenge.len()).map_anon().unwrap();
+    map.copy_from_slice(&challenge);
+    let challenge = map.make_read_only().unwrap();
+    let mut response = test_contribution(Some(challenge), UseCompression::Yes)
+        .response
+        .to_vec();
+
+    // a response being received doesn't hold up other requests
+    let mut stalled = TcpStream::connect(addr).unwrap();
+    write!(
+        stalled,
+        "POST /response?participant=alice HTTP/1.1\r\nContent-Length: {}\r\n\r\n",
+        response.len()
+    )
+    .unwrap();
+    stalled.write_all(&response[..64]).unwrap();
+    while !dir.join(UPLOAD).exists() {
+        thread::sleep(Duration::from_millis(10));
+    }
+    assert_eq!(request(addr, "GET", "/status", b"").0, "200");
+    // an upload cut short is dropped
+    drop(stalled);
+    while dir.join(UPLOAD).exists() {
+        thread::sleep(Duration::from_millis(10));
+    }
+
+    // an invalid response is rejected, while lock stays in place
+    response[0] ^= 1;
+    let (status, _) = request(addr, "POST", "/response?participant=alice", &response[..]);
+    assert_eq!(status, "422");
+    response[0] ^= 1;
+    let before = fs::read(dir.join(STATE)).unwrap();
+    let (status, _) = request(addr, "POST", "/response?participant=alice", &response[..]);
+    assert_eq!(status, "200");
+    let after = fs::read(dir.join(STATE)).unwrap();
+
+    // killed after logging contribution but before saving state, it's accepted on restart
+    fs::write(dir.join(STATE), &before).unwrap();
+    Coordinator::open::<Bn256>(&dir, &parameters, UseCompression::Yes, timeout).unwrap();
+    assert_eq!(
+        State::load(&dir).unwrap(),
+        serde_json::from_slice::<State>(&after).unwrap()
+    );
+
+    // state survives a restart
+    let mut coordinator =
+        Coordinator::open::<Bn256>(&dir, &parameters, UseCompression::Yes, timeout).unwrap();
+    assert_eq!(coordinator.state().round, 1);
+    assert_eq!(coordinator.state().queue, vec!["bob".to_owned()]);
+    assert_eq!(coordinator.state().contributions[0].participant, "alice");
+    assert!(dir.join("challenge_1").exists() && dir.join("response_0").exists());
+
+    // accepted contribution is in audit log, matching transcript files
+    let audit = super::audit::audit(&dir.join(AUDIT_LOG), &dir).unwrap();
+    assert_eq!(audit.entries.len(), 1);
+    assert_eq!(audit.entries[0].contribution.response, "response_0");
+    assert_eq!(audit.findings, vec![]);
+
+    // a lock times out, handing challenge to the next in queue
+    let now = unix_now();
+    coordinator.join("carol").unwrap();
+    coordinator.lock("bob", now).unwrap();
+    assert!(coordinator.lock("carol", now).is_err());
+    coordinator.lock("carol", now + timeout.as_secs()).unwrap();
+    assert_eq!(coordinator.state().queue, vec!["carol".to_owned()]);
+
+    fs::remove_dir_all(dir).unwrap();
+}
@@ -185,42 +185,37 @@ impl<E: Engine> PublicKey<E> {
     /// This function is intended to write the key to the memory map and calculates
     /// a position for writing into the file itself based on information whether
     /// contribution was output in compressed on uncompressed form
-    pub fn write<P>(
+    pub fn write(
         &self,
         output_map: &mut MmapMut,
         accumulator_was_compressed: UseCompression,
-    ) -> io::Result<()>
-    where
-        P: PowersOfTauParameters,
-    {
-        let mut position = match accumulator_was_compressed {
-            UseCompression::Yes => P::CONTRIBUTION_BYTE_SIZE - P::PUBLIC_KEY_SIZE,
-            UseCompression::No => P::ACCUMULATOR_BYTE_SIZE,
-        };
+        parameters: &CeremonyParameters,
+    ) -> io::Result<()> {
+        let mut position = parameters.challenge_size(accumulator_was_compressed);
 
         (&mut output_map[position..]).write(&self.tau_g1.0.into_uncompressed().as_ref())?;
-        position += P::G1_UNCOMPRESSED_BYTE_SIZE;
+        position += parameters.g1_uncompressed_byte_size;
 
         (&mut output_map[position..]).write(&self.tau_g1.1.into_uncompressed().as_ref())?;
-        position += P::G1_UNCOMPRESSED_BYTE_SIZE;
+        position += parameters.g1_uncompressed_byte_size;
 
         (&mut output_map[position..]).write(&self.alpha_g1.0.into_uncompressed().as_ref())?;
-        position += P::G1_UNCOMPRESSED_BYTE_SIZE;
+        position += parameters.g1_uncompressed_byte_size;
 
         (&mut output_map[position..]).write(&self.alpha_g1.1.into_uncompressed().as_ref())?;
-        position += P::G1_UNCOMPRESSED_BYTE_SIZE;
+        position += parameters.g1_uncompressed_byte_size;
 
         (&mut output_map[position..]).write(&self.beta_g1.0.into_uncompressed().as_ref())?;
-        position += P::G1_UNCOMPRESSED_BYTE_SIZE;
+        position += parameters.g1_uncompressed_byte_size;
 
         (&mut output_map[position..]).write(&self.beta_g1.1.into_uncompressed().as_ref())?;
-        position += P::G1_UNCOMPRESSED_BYTE_SIZE;
+        position += parameters.g1_uncompressed_byte_size;
 
         (&mut output_map[position..]).write(&self.tau_g2.into_uncompressed().as_ref())?;
-        position += P::G2_UNCOMPRESSED_BYTE_SIZE;
+        position += parameters.g2_uncompressed_byte_size;
 
         (&mut output_map[position..]).write(&self.alpha_g2.into_uncompressed().as_ref())?;
-        position += P::G2_UNCOMPRESSED_BYTE_SIZE;
+        position += parameters.g2_uncompressed_byte_size;
 
         (&mut output_map[position..]).write(&self.beta_g2.into_uncompressed().as_ref())?;
 
@@ -232,13 +227,11 @@ impl<E: Engine> PublicKey<E> {
     /// Deserialize the public key. Points are always in uncompressed form, and
     /// always checked, since there aren't very many of them. Does not allow any
     /// points at infinity.
-    pub fn read<P>(
+    pub fn read(
         input_map: &Mmap,
         accumulator_was_compressed: UseCompression,
-    ) -> Result<Self, DeserializationError>
-    where
-        P: PowersOfTauParameters,
-    {
+        parameters: &CeremonyParameters,
+    ) -> Result<Self, DeserializationError> {
         fn read_uncompressed<EE: Engine, C: CurveAffine<Engine = EE, Scalar = EE::Fr>>(
             input_map: &Mmap,
             position: usize,
@@ -258,34 +251,31 @@ impl<E: Engine> PublicKey<E> {
             }
         }
 
-        let mut position = match accumulator_was_compressed {
-            UseCompression::Yes => P::CONTRIBUTION_BYTE_SIZE - P::PUBLIC_KEY_SIZE,
-            UseCompression::No => P::ACCUMULATOR_BYTE_SIZE,
-        };
+        let mut position = parameters.challenge_size(accumulator_was_compressed);
 
         let tau_g1_s = read_uncompressed::<E, _>(input_map, position)?;
-        position += P::G1_UNCOMPRESSED_BYTE_SIZE;
+        position += parameters.g1_uncompressed_byte_size;
 
         let tau_g1_s_tau = read_uncompressed::<E, _>(input_map, position)?;
-        position += P::G1_UNCOMPRESSED_BYTE_SIZE;
+        position += parameters.g1_uncompressed_byte_size;
 
         let alpha_g1_s = read_uncompressed::<E, _>(input_map, position)?;
-        position += P::G1_UNCOMPRESSED_BYTE_SIZE;
+        position += parameters.g1_uncompressed_byte_size;
 
         let alpha_g1_s_alpha = read_uncompressed::<E, _>(input_map, position)?;
-        position += P::G1_UNCOMPRESSED_BYTE_SIZE;
+        position += parameters.g1_uncompressed_byte_size;
 
         let beta_g1_s = read_uncompressed::<E, _>(input_map, position)?;
-        position += P::G1_UNCOMPRESSED_BYTE_SIZE;
+        position += parameters.g1_uncompressed_byte_size;
 
         let beta_g1_s_beta = read_uncompressed::<E, _>(input_map, position)?;
-        position += P::G1_UNCOMPRESSED_BYTE_SIZE;
+        position += parameters.g1_uncompressed_byte_size;
 
         let tau_g2 = read_uncompressed::<E, _>(input_map, position)?;
-        position += P::G2_UNCOMPRESSED_BYTE_SIZE;
+        position += parameters.g2_uncompressed_byte_size;
 
         let alpha_g2 = read_uncompressed::<E, _>(input_map, position)?;
-        position += P::G2_UNCOMPRESSED_BYTE_SIZE;
+        position += parameters.g2_uncompressed_byte_size;
 
         let beta_g2 = read_uncompressed::<E, _>(input_map, position)?;
 
@@ -298,4 +288,21 @@ impl<E: Engine> PublicKey<E> {
             beta_g2: beta_g2,
         })
     }
+
+    /// Same as `write`, sizes taken from a `PowersOfTauParameters` type.
+    pub fn write_with<P: PowersOfTauParameters>(
+        &self,
+        output_map: &mut MmapMut,
+        accumulator_was_compressed: UseCompression,
+    ) -> io::Result<()> {
+        self.write(output_map, accumulator_was_compressed, &P::parameters())
+    }
+
+    /// Same as `read`, sizes taken from a `PowersOfTauParameters` type.
+    pub fn read_with<P: PowersOfTauParameters>(
+        input_map: &Mmap,
+        accumulator_was_compressed: UseCompression,
+    ) -> Result<Self, DeserializationError> {
+        Self::read(input_map, accumulator_was_compressed, &P::parameters())
+    }
 }
@@ -2,10 +2,15 @@
 
 // pub mod bls12_381;
 pub mod accumulator;
+pub mod attestation;
+pub mod audit;
 pub mod batched_accumulator;
+pub mod beacon;
 pub mod bn256;
+pub mod coordinator;
 pub mod keypair;
 pub mod parameters;
 pub mod small_bls12_381;
 pub mod small_bn256;
+pub mod transcript;
 pub mod utils;
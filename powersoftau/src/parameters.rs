@@ -17,7 +17,9 @@ use rand::chacha::ChaChaRng;
 use rand::{Rand, Rng, SeedableRng};
 use std::fmt;
 use std::io::{self, Read, Write};
+use std::ops::Range;
 use std::sync::{Arc, Mutex};
+use std::time::{Duration, Instant};
 use typenum::consts::U64;
 
 use super::keypair::*;
@@ -56,8 +58,137 @@ pub trait PowersOfTauParameters: Clone {
     const HASH_SIZE: usize = 64;
 
     const EMPIRICAL_BATCH_SIZE: usize = 1 << 21;
+
+    /// Same sizes as a runtime value, for APIs taking `CeremonyParameters`.
+    fn parameters() -> CeremonyParameters {
+        CeremonyParameters {
+            required_power: Self::REQUIRED_POWER,
+            g1_uncompressed_byte_size: Self::G1_UNCOMPRESSED_BYTE_SIZE,
+            g2_uncompressed_byte_size: Self::G2_UNCOMPRESSED_BYTE_SIZE,
+            g1_compressed_byte_size: Self::G1_COMPRESSED_BYTE_SIZE,
+            g2_compressed_byte_size: Self::G2_COMPRESSED_BYTE_SIZE,
+            empirical_batch_size: Self::EMPIRICAL_BATCH_SIZE,
+        }
+    }
+}
+
+/// Sizes of a ceremony, as `PowersOfTauParameters` defines them, but known only at runtime, so
+/// that one type covers a ceremony of any power of two, given by a flag or read off a file.
+#[derive(Copy, Clone, Debug, PartialEq, Eq)]
+pub struct CeremonyParameters {
+    pub required_power: usize,
+
+    pub g1_uncompressed_byte_size: usize,
+    pub g2_uncompressed_byte_size: usize,
+    pub g1_compressed_byte_size: usize,
+    pub g2_compressed_byte_size: usize,
+
+    pub empirical_batch_size: usize,
+}
+
+impl CeremonyParameters {
+    // Blake2b hash size
+    pub const HASH_SIZE: usize = 64;
+
+    /// Ceremony of 2^power powers of tau over BLS12-381.
+    pub fn bls12_381(power: usize) -> Self {
+        CeremonyParameters {
+            required_power: power,
+            g1_uncompressed_byte_size: 96,
+            g2_uncompressed_byte_size: 192,
+            g1_compressed_byte_size: 48,
+            g2_compressed_byte_size: 96,
+            empirical_batch_size: 1 << 21,
+        }
+    }
+
+    /// Ceremony of 2^power powers of tau over BN256.
+    pub fn bn256(power: usize) -> Self {
+        CeremonyParameters {
+            required_power: power,
+            g1_uncompressed_byte_size: 64,
+            g2_uncompressed_byte_size: 128,
+            g1_compressed_byte_size: 32,
+            g2_compressed_byte_size: 64,
+            empirical_batch_size: 1 << 21,
+        }
+    }
+
+    /// Same point sizes, but for a ceremony of 2^power powers of tau.
+    pub fn with_power(&self, power: usize) -> Self {
+        CeremonyParameters {
+            required_power: power,
+            ..*self
+        }
+    }
+
+    pub fn tau_powers_length(&self) -> usize {
+        1 << self.required_power
+    }
+
+    pub fn tau_powers_g1_length(&self) -> usize {
+        (self.tau_powers_length() << 1) - 1
+    }
+
+    pub fn hash_size(&self) -> usize {
+        Self::HASH_SIZE
+    }
+
+    pub fn accumulator_byte_size(&self) -> usize {
+        (self.tau_powers_g1_length() * self.g1_uncompressed_byte_size) + // g1 tau powers
+            (self.tau_powers_length() * self.g2_uncompressed_byte_size) + // g2 tau powers
+            (self.tau_powers_length() * self.g1_uncompressed_byte_size) + // alpha tau powers
+            (self.tau_powers_length() * self.g1_uncompressed_byte_size) // beta tau powers
+            + self.g2_uncompressed_byte_size // beta in g2
+            + self.hash_size() // blake2b hash of previous contribution
+    }
+
+    pub fn public_key_size(&self) -> usize {
+        3 * self.g2_uncompressed_byte_size + // tau, alpha, and beta in g2
+            6 * self.g1_uncompressed_byte_size // (s1, s1*tau), (s2, s2*alpha), (s3, s3*beta) in g1
+    }
+
+    pub fn contribution_byte_size(&self) -> usize {
+        (self.tau_powers_g1_length() * self.g1_compressed_byte_size) + // g1 tau powers
+            (self.tau_powers_length() * self.g2_compressed_byte_size) + // g2 tau powers
+            (self.tau_powers_length() * self.g1_compressed_byte_size) + // alpha tau powers
+            (self.tau_powers_length() * self.g1_compressed_byte_size) // beta tau powers
+            + self.g2_compressed_byte_size // beta in g2
+            + self.hash_size() // blake2b hash of input accumulator
+            + self.public_key_size() // public key
+    }
+
+    /// Size of a challenge, i.e. an accumulator without public key.
+    pub fn challenge_size(&self, compression: UseCompression) -> usize {
+        match compression {
+            UseCompression::Yes => self.contribution_byte_size() - self.public_key_size(),
+            UseCompression::No => self.accumulator_byte_size(),
+        }
+    }
+
+    /// Size of a response, i.e. an accumulator followed by contributor's public key.
+    pub fn response_size(&self, compression: UseCompression) -> usize {
+        match compression {
+            UseCompression::Yes => self.contribution_byte_size(),
+            UseCompression::No => self.accumulator_byte_size() + self.public_key_size(),
+        }
+    }
+
+    /// Finds power of a ceremony with same point sizes, whose file of given kind ( i.e.
+    /// `challenge_size` or `response_size` ) is `len` bytes.
+    pub fn detect<F>(&self, len: u64, size: F) -> Option<Self>
+    where
+        F: Fn(&CeremonyParameters) -> usize,
+    {
+        (1..=MAX_POWER)
+            .map(|power| self.with_power(power))
+            .find(|parameters| size(parameters) as u64 == len)
+    }
 }
 
+/// Largest ceremony whose file sizes are sure to fit in a `usize`, on 64-bit targets.
+pub const MAX_POWER: usize = 32;
+
 /// Determines if point compression should be used.
 #[derive(Copy, Clone, PartialEq)]
 pub enum UseCompression {
@@ -112,3 +243,162 @@ pub enum ElementType {
     BetaG1,
     BetaG2,
 }
+
+/// Checks a contribution must pass, in order `verify_transformation` performs them.
+#[derive(Copy, Clone, Debug, PartialEq)]
+pub enum VerificationCheck {
+    /// public key proves knowledge of contributed tau
+    TauProofOfKnowledge,
+    /// public key proves knowledge of contributed alpha
+    AlphaProofOfKnowledge,
+    /// public key proves knowledge of contributed beta
+    BetaProofOfKnowledge,
+    /// zeroth power of tau is the group generator
+    Generator,
+    /// new accumulator is previous one multiplied by contributed secret
+    ContributionRatio,
+    /// successive powers share the same ratio, i.e. tau
+    PowersRatio,
+}
+
+impl fmt::Display for VerificationCheck {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        let name = match *self {
+            VerificationCheck::TauProofOfKnowledge => "proof of knowledge of tau",
+            VerificationCheck::AlphaProofOfKnowledge => "proof of knowledge of alpha",
+            VerificationCheck::BetaProofOfKnowledge => "proof of knowledge of beta",
+            VerificationCheck::Generator => "generator check",
+            VerificationCheck::ContributionRatio => "contribution ratio check",
+            VerificationCheck::PowersRatio => "powers ratio check",
+        };
+        write!(f, "{}", name)
+    }
+}
+
+/// A check, together with elements it was performed on.
+#[derive(Clone, Debug, PartialEq)]
+pub struct CheckScope {
+    pub check: VerificationCheck,
+    pub element: ElementType,
+    /// range of powers of `element` read from both files, `None` for checks of public key alone
+    pub chunk: Option<Range<usize>>,
+}
+
+impl fmt::Display for CheckScope {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        match self.chunk {
+            Some(ref chunk) => write!(
+                f,
+                "{} of {:?} powers {}..{}",
+                self.check, self.element, chunk.start, chunk.end
+            ),
+            None => write!(f, "{} of {:?} in public key", self.check, self.element),
+        }
+    }
+}
+
+/// A check performed, with its outcome.
+#[derive(Clone, Debug)]
+pub struct CheckRecord {
+    pub scope: CheckScope,
+    pub passed: bool,
+    pub elapsed: Duration,
+}
+
+/// Checks performed on a contribution, in order. Only the last one may have failed.
+#[derive(Clone, Debug, Default)]
+pub struct VerificationReport {
+    pub checks: Vec<CheckRecord>,
+    /// number of chunks read from each of challenge and response
+    pub chunks: usize,
+}
+
+impl VerificationReport {
+    /// Performs and records a check, failing with its `scope` if it doesn't pass.
+    pub fn require<F>(&mut self, check: F, scope: CheckScope) -> Result<(), VerificationError>
+    where
+        F: FnOnce() -> bool,
+    {
+        let start = Instant::now();
+        let passed = check();
+        self.checks.push(CheckRecord {
+            scope: scope.clone(),
+            passed,
+            elapsed: start.elapsed(),
+        });
+
+        if passed {
+            Ok(())
+        } else {
+            Err(VerificationError::Failed(scope))
+        }
+    }
+}
+
+/// Reasons a contribution is rejected.
+#[derive(Debug)]
+pub enum VerificationError {
+    /// challenge couldn't be read in given range of powers
+    Challenge {
+        chunk: Range<usize>,
+        error: DeserializationError,
+    },
+    /// response couldn't be read in given range of powers
+    Response {
+        chunk: Range<usize>,
+        error: DeserializationError,
+    },
+    Failed(CheckScope),
+}
+
+impl fmt::Display for VerificationError {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        match *self {
+            VerificationError::Challenge {
+                ref chunk,
+                ref error,
+            } => write!(
+                f,
+                "unable to read powers {}..{} of challenge: {}",
+                chunk.start, chunk.end, error
+            ),
+            VerificationError::Response {
+                ref chunk,
+                ref error,
+            } => write!(
+                f,
+                "unable to read powers {}..{} of response: {}",
+                chunk.start, chunk.end, error
+            ),
+            VerificationError::Failed(ref scope) => write!(f, "failed {}", scope),
+        }
+    }
+}
+
+#[test]
+fn test_ceremony_parameters_match_consts() {
+    use crate::small_bn256::Bn256CeremonyParameters;
+
+    type P = Bn256CeremonyParameters;
+    let parameters = P::parameters();
+    assert_eq!(parameters, CeremonyParameters::bn256(P::REQUIRED_POWER));
+    assert_eq!(parameters.tau_powers_g1_length(), P::TAU_POWERS_G1_LENGTH);
+    assert_eq!(parameters.accumulator_byte_size(), P::ACCUMULATOR_BYTE_SIZE);
+    assert_eq!(parameters.public_key_size(), P::PUBLIC_KEY_SIZE);
+    assert_eq!(
+        parameters.contribution_byte_size(),
+        P::CONTRIBUTION_BYTE_SIZE
+    );
+
+    // power is found back from size of a file of known kind
+    let other = CeremonyParameters::bn256(7);
+    let len = other.response_size(UseCompression::Yes) as u64;
+    assert_eq!(
+        parameters.detect(len, |p| p.response_size(UseCompression::Yes)),
+        Some(other)
+    );
+    assert_eq!(
+        parameters.detect(len + 1, |p| p.response_size(UseCompression::Yes)),
+        None
+    );
+}
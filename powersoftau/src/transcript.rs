@@ -0,0 +1,327 @@
+//! Verification of a whole ceremony transcript, i.e. an ordered chain of responses, each one
+//! contributing on top of the challenge recomputed from previous one.
+//!
+//! Starting from either a given challenge or the one `generate_initial` produces, every response
+//! must be linked to its challenge by hash, and must be a valid transformation of it. The next
+//! challenge is then recomputed from the response, in memory or in a scratch directory, so that
+//! nothing but responses needs to be kept around.
+
+use bellman::pairing::Engine;
+use blake2::digest::generic_array::GenericArray;
+use memmap::{Mmap, MmapMut, MmapOptions};
+use std::fmt;
+use std::fs::{self, File, OpenOptions};
+use std::io::{self, Write};
+use std::path::{Path, PathBuf};
+use typenum::consts::U64;
+
+use super::batched_accumulator::BachedAccumulator;
+use super::keypair::PublicKey;
+use super::parameters::{
+    CeremonyParameters, CheckForCorrectness, DeserializationError, UseCompression,
+    VerificationError, VerificationReport,
+};
+use super::utils::blank_hash;
+
+/// Where recomputed challenges are kept while verifying.
+#[derive(Clone, Debug)]
+pub enum Scratch {
+    /// anonymous memory maps, so whole challenge must fit in memory
+    Memory,
+    /// files in given directory, removed as soon as they aren't needed
+    Dir(PathBuf),
+}
+
+/// One verified contribution.
+pub struct Contribution<E: Engine> {
+    pub response: PathBuf,
+    /// hash of challenge it contributed on top of
+    pub challenge_hash: GenericArray<u8, U64>,
+    /// hash of response file, as published by contributor
+    pub response_hash: GenericArray<u8, U64>,
+    pub public_key: PublicKey<E>,
+    /// checks its response passed
+    pub report: VerificationReport,
+}
+
+/// Outcome of verifying a whole transcript.
+pub struct Transcript<E: Engine> {
+    pub initial_hash: GenericArray<u8, U64>,
+    pub contributions: Vec<Contribution<E>>,
+    /// hash of challenge recomputed from last response, i.e. of final accumulator
+    pub final_hash: GenericArray<u8, U64>,
+}
+
+#[derive(Debug)]
+pub enum TranscriptError {
+    IoError(io::Error),
+    /// file isn't of size expected for ceremony
+    Size {
+        path: PathBuf,
+        expected: u64,
+        found: u64,
+    },
+    /// response isn't based on challenge it must contribute to
+    HashChain {
+        index: usize,
+    },
+    PublicKey {
+        index: usize,
+        error: DeserializationError,
+    },
+    InvalidContribution {
+        index: usize,
+        error: VerificationError,
+    },
+}
+
+impl fmt::Display for TranscriptError {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        match *self {
+            TranscriptError::IoError(ref e) => write!(f, "Disk IO error: {}", e),
+            TranscriptError::Size {
+                ref path,
+                expected,
+                found,
+            } => write!(
+                f,
+                "`{}` should be {}b, but it's {}b",
+                path.display(),
+                expected,
+                found
+            ),
+            TranscriptError::HashChain { index } => write!(
+                f,
+                "Hash chain failure, response #{} isn't based on its challenge",
+                index
+            ),
+            TranscriptError::PublicKey { index, ref error } => {
+                write!(f, "Public key of response #{} is invalid: {}", index, error)
+            }
+            TranscriptError::InvalidContribution { index, ref error } => write!(
+                f,
+                "Response #{} isn't a valid contribution: {}",
+                index, error
+            ),
+        }
+    }
+}
+
+impl From<io::Error> for TranscriptError {
+    fn from(err: io::Error) -> TranscriptError {
+        TranscriptError::IoError(err)
+    }
+}
+
+impl Scratch {
+    fn allocate(&self, name: &str, len: usize) -> io::Result<(MmapMut, Option<PathBuf>)> {
+        match self {
+            Scratch::Memory => Ok((MmapOptions::new().len(len).map_anon()?, None)),
+            Scratch::Dir(dir) => {
+                let path = dir.join(name);
+                let file = OpenOptions::new()
+                    .read(true)
+                    .write(true)
+                    .create(true)
+                    .truncate(true)
+                    .open(&path)?;
+                file.set_len(len as u64)?;
+                let map = unsafe { MmapOptions::new().map_mut(&file)? };
+                Ok((map, Some(path)))
+            }
+        }
+    }
+}
+
+/// Verifies `responses` in order, first one contributing on top of `initial_challenge`, or on top
+/// of a freshly generated accumulator if it's not given. Challenges, given or recomputed, are
+/// uncompressed, while every response is compressed as `response_compression` says.
+pub fn verify_transcript<E: Engine, P: AsRef<Path>>(
+    parameters: &CeremonyParameters,
+    initial_challenge: Option<&Path>,
+    responses: &[P],
+    response_compression: UseCompression,
+    scratch: &Scratch,
+) -> Result<Transcript<E>, TranscriptError> {
+    let mut scratch_file = None;
+    let mut challenge = match initial_challenge {
+        Some(path) => open_sized(path, parameters.challenge_size(UseCompression::No))?,
+        None => {
+            let (mut map, path) =
+                scratch.allocate("challenge_0", parameters.accumulator_byte_size())?;
+            (&mut map[0..]).write(blank_hash().as_slice())?;
+            BachedAccumulator::<E>::generate_initial(&mut map, UseCompression::No, parameters)?;
+            scratch_file = path;
+            map.make_read_only()?
+        }
+    };
+    let initial_hash = BachedAccumulator::<E>::calculate_hash(&challenge);
+
+    let mut contributions = Vec::with_capacity(responses.len());
+    for (index, path) in responses.iter().enumerate() {
+        let path = path.as_ref();
+        let challenge_hash = BachedAccumulator::<E>::calculate_hash(&challenge);
+
+        let response = open_sized(path, parameters.response_size(response_compression))?;
+        if &response[0..parameters.hash_size()] != challenge_hash.as_slice() {
+            return Err(TranscriptError::HashChain { index });
+        }
+        let response_hash = BachedAccumulator::<E>::calculate_hash(&response);
+
+        let public_key = PublicKey::<E>::read(&response, response_compression, parameters)
+            .map_err(|error| TranscriptError::PublicKey { index, error })?;
+
+        // only a given challenge can hold invalid points, recomputed ones come from checked
+        // responses
+        let check_challenge = match (index, initial_challenge) {
+            (0, Some(_)) => CheckForCorrectness::Yes,
+            _ => CheckForCorrectness::No,
+        };
+        let report = BachedAccumulator::<E>::verify_transformation(
+            &challenge,
+            &response,
+            &public_key,
+            challenge_hash.as_slice(),
+            UseCompression::No,
+            response_compression,
+            check_challenge,
+            CheckForCorrectness::Yes,
+            parameters,
+        )
+        .map_err(|error| TranscriptError::InvalidContribution { index, error })?;
+
+        // next challenge, linked to this response by its hash
+        let (mut next, next_file) = scratch.allocate(
+            &format!("challenge_{}", index + 1),
+            parameters.accumulator_byte_size(),
+        )?;
+        match response_compression {
+            UseCompression::Yes => {
+                BachedAccumulator::<E>::decompress(
+                    &response,
+                    &mut next,
+                    CheckForCorrectness::No,
+                    parameters,
+                )?;
+            }
+            UseCompression::No => {
+                // same points, without public key
+                next.copy_from_slice(&response[..parameters.accumulator_byte_size()]);
+            }
+        }
+        (&mut next[0..]).write(response_hash.as_slice())?;
+        next.flush()?;
+
+        challenge = next.make_read_only()?;
+        if let Some(previous) = std::mem::replace(&mut scratch_file, next_file) {
+            fs::remove_file(previous)?;
+        }
+
+        contributions.push(Contribution {
+            response: path.to_path_buf(),
+            challenge_hash,
+            response_hash,
+            public_key,
+            report,
+        });
+    }
+
+    let final_hash = BachedAccumulator::<E>::calculate_hash(&challenge);
+    if let Some(last) = scratch_file {
+        drop(challenge);
+        fs::remove_file(last)?;
+    }
+
+    Ok(Transcript {
+        initial_hash,
+        contributions,
+        final_hash,
+    })
+}
+
+fn open_sized(path: &Path, expected: usize) -> Result<Mmap, TranscriptError> {
+    let file = File::open(path)?;
+    let found = file.metadata()?.len();
+    if found != expected as u64 {
+        return Err(TranscriptError::Size {
+            path: path.to_path_buf(),
+            expected: expected as u64,
+            found,
+        });
+    }
+    Ok(unsafe { MmapOptions::new().map(&file)? })
+}
+
+#[test]
+fn test_verify_transcript() {
+    use super::batched_accumulator::test_contribution;
+    use bellman::pairing::bn256::Bn256;
+
+    let dir = std::env::temp_dir().join(format!("transcript-{}", std::process::id()));
+    fs::create_dir_all(&dir).unwrap();
+    let scratch = Scratch::Dir(dir.clone());
+    let keep = |response: &Mmap, name: &str| -> PathBuf {
+        let (mut file, path) = scratch.allocate(name, response.len()).unwrap();
+        file.copy_from_slice(response);
+        file.flush().unwrap();
+        path.unwrap()
+    };
+
+    let contribution = test_contribution(None, UseCompression::Yes);
+    let parameters = contribution.parameters;
+    let initial = contribution.challenge;
+    let first = keep(&contribution.response, "response_0");
+
+    // challenge of second contributor, as coordinator recomputes it
+    let (mut challenge, _) = Scratch::Memory
+        .allocate("", parameters.accumulator_byte_size())
+        .unwrap();
+    let response = open_sized(&first, parameters.response_size(UseCompression::Yes)).unwrap();
+    BachedAccumulator::<Bn256>::decompress(
+        &response,
+        &mut challenge,
+        CheckForCorrectness::No,
+        &parameters,
+    )
+    .unwrap();
+    (&mut challenge[0..])
+        .write(BachedAccumulator::<Bn256>::calculate_hash(&response).as_slice())
+        .unwrap();
+    let contribution = test_contribution(
+        Some(challenge.make_read_only().unwrap()),
+        UseCompression::Yes,
+    );
+    let challenge = contribution.challenge;
+    let second = keep(&contribution.response, "response_1");
+
+    let responses = vec![first, second];
+    let transcript =
+        verify_transcript::<Bn256, _>(&parameters, None, &responses, UseCompression::Yes, &scratch)
+            .unwrap();
+    assert_eq!(transcript.contributions.len(), 2);
+    assert_eq!(
+        transcript.initial_hash,
+        BachedAccumulator::<Bn256>::calculate_hash(&initial)
+    );
+    assert_eq!(
+        transcript.contributions[1].challenge_hash,
+        BachedAccumulator::<Bn256>::calculate_hash(&challenge)
+    );
+    // only responses are left in scratch directory
+    assert_eq!(fs::read_dir(&dir).unwrap().count(), 2);
+
+    // out of order responses break hash chain
+    let swapped = vec![responses[1].clone(), responses[0].clone()];
+    match verify_transcript::<Bn256, _>(
+        &parameters,
+        None,
+        &swapped,
+        UseCompression::Yes,
+        &Scratch::Memory,
+    ) {
+        Err(TranscriptError::HashChain { index: 0 }) => {}
+        _ => panic!("swapped responses must break hash chain"),
+    }
+
+    fs::remove_dir_all(dir).unwrap();
+}
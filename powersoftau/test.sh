@@ -6,13 +6,16 @@ rm new_challenge
 rm challenge_old
 rm response_old
 
-cargo run --release --bin new_constrained
-cargo run --release --bin compute_constrained
-cargo run --release --bin verify_transform_constrained
+# small ceremony, so that it runs in a few minutes
+ARGS="--curve bls12-381 --power 10"
+
+cargo run --release --bin powersoftau new $ARGS
+echo "test" | cargo run --release --bin powersoftau compute $ARGS
+cargo run --release --bin powersoftau verify $ARGS
 
 mv challenge challenge_old
 mv response response_old
 
 mv new_challenge challenge
-cargo run --release --bin beacon_constrained
-cargo run --release --bin verify_transform_constrained
+cargo run --release --bin powersoftau beacon $ARGS --beacon 0000000000000000000a558a61ddc8ee4e488d647a747fe4dcc362fe2026c620 --beacon-iterations 10
+cargo run --release --bin powersoftau verify $ARGS
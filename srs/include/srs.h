@@ -0,0 +1,156 @@
+#ifndef SRS_H
+#define SRS_H
+
+/* Generated by cbindgen from src/ffi.rs, don't edit by hand. */
+
+#include <stdarg.h>
+#include <stdbool.h>
+#include <stddef.h>
+#include <stdint.h>
+#include <stdlib.h>
+
+// Call succeeded.
+#define SRS_OK 0
+
+// A required pointer was null.
+#define SRS_ERR_NULL -1
+
+// Input bytes don't decode, e.g. a non-canonical scalar or an invalid point.
+#define SRS_ERR_INVALID -2
+
+// Requested degree is out of range of public parameters or commit key.
+#define SRS_ERR_DEGREE -3
+
+// Proof, or batch of proofs, doesn't verify.
+#define SRS_ERR_PROOF -4
+
+// Rust code panicked, which is a bug.
+#define SRS_ERR_PANIC -5
+
+// Size of a scalar, in bytes.
+#define SRS_SCALAR_SIZE 32
+
+// Size of a compressed g1 point, in bytes.
+#define SRS_G1_SIZE 48
+
+// Commit key, as returned by `srs_trim`.
+typedef struct SrsCommitKey SrsCommitKey;
+
+// Opening key, as returned by `srs_trim`.
+typedef struct SrsOpeningKey SrsOpeningKey;
+
+// Public parameters, as returned by `srs_load`.
+typedef struct SrsPublicParameters SrsPublicParameters;
+
+#ifdef __cplusplus
+extern "C" {
+#endif // __cplusplus
+
+// Message describing last error on calling thread, valid until next failing call on it.
+const char *srs_last_error(void);
+
+// Decodes public parameters from content of `pp_<N>.data` or `pp_raw_<N>.data`, checking every
+// point. `g1_g2_<N>.txt` is refused with `SRS_ERR_INVALID`, holding tau·G2 in place of beta_h.
+//
+// # Safety
+//
+// `bytes` must point to `len` readable bytes, `out` must be writable.
+int32_t srs_load(const uint8_t *bytes, size_t len, struct SrsPublicParameters **out);
+
+// Maximum degree of polynomials public parameters can commit to, zero if `pp` is null.
+//
+// # Safety
+//
+// `pp` must be null or returned by `srs_load`.
+size_t srs_max_degree(const struct SrsPublicParameters *pp);
+
+// Trims public parameters into a commit key for polynomials of degree ≤ `degree` & an
+// opening key.
+//
+// # Safety
+//
+// `pp` must be returned by `srs_load`, `commit_key` & `opening_key` must be writable.
+int32_t srs_trim(const struct SrsPublicParameters *pp,
+                 size_t degree,
+                 struct SrsCommitKey **commit_key,
+                 struct SrsOpeningKey **opening_key);
+
+// Commits to polynomial given by `len` coefficients, lowest degree first, writing 48 bytes
+// compressed commitment into `out`.
+//
+// # Safety
+//
+// `commit_key` must be returned by `srs_trim`, `coeffs` must point to `len * 32` readable bytes
+// & `out` to 48 writable bytes.
+int32_t kzg_commit(const struct SrsCommitKey *commit_key,
+                   const uint8_t *coeffs,
+                   size_t len,
+                   uint8_t *out);
+
+// Opens polynomial given by `len` coefficients at `point`, writing its 32 bytes evaluation into
+// `value` & 48 bytes compressed witness into `witness`.
+//
+// # Safety
+//
+// `commit_key` must be returned by `srs_trim`, `coeffs` must point to `len * 32` readable bytes,
+// `point` to 32 readable bytes, `value` to 32 writable bytes & `witness` to 48 writable bytes.
+int32_t kzg_open(const struct SrsCommitKey *commit_key,
+                 const uint8_t *coeffs,
+                 size_t len,
+                 const uint8_t *point,
+                 uint8_t *value,
+                 uint8_t *witness);
+
+// Verifies that polynomial committed to by `commitment` evaluates to `value` at `point`,
+// returning `SRS_OK` if `witness` proves so & `SRS_ERR_PROOF` if it doesn't.
+//
+// # Safety
+//
+// `opening_key` must be returned by `srs_trim`, `commitment` & `witness` must point to 48
+// readable bytes, `point` & `value` to 32 readable bytes.
+int32_t kzg_verify(const struct SrsOpeningKey *opening_key,
+                   const uint8_t *commitment,
+                   const uint8_t *point,
+                   const uint8_t *value,
+                   const uint8_t *witness);
+
+// Verifies `len` proofs at once, i-th proof being made of i-th commitment, point, value &
+// witness of given arrays. Returns `SRS_OK` only if every proof verifies.
+//
+// # Safety
+//
+// `opening_key` must be returned by `srs_trim`, `commitments` & `witnesses` must point to
+// `len * 48` readable bytes, `points` & `values` to `len * 32` readable bytes.
+int32_t kzg_batch_verify(const struct SrsOpeningKey *opening_key,
+                         const uint8_t *commitments,
+                         const uint8_t *points,
+                         const uint8_t *values,
+                         const uint8_t *witnesses,
+                         size_t len);
+
+// Releases public parameters, null is ignored.
+//
+// # Safety
+//
+// `pp` must be null or returned by `srs_load`, and not released before.
+void srs_free(struct SrsPublicParameters *pp);
+
+// Releases a commit key, null is ignored.
+//
+// # Safety
+//
+// `commit_key` must be null or returned by `srs_trim`, and not released before.
+void srs_commit_key_free(struct SrsCommitKey *commit_key);
+
+// Releases an opening key, null is ignored.
+//
+// # Safety
+//
+// `opening_key` must be null or returned by `srs_trim`, and not released before.
+void srs_opening_key_free(struct SrsOpeningKey *opening_key);
+
+#ifdef __cplusplus
+} // extern "C"
+#endif // __cplusplus
+
+#endif /* SRS_H */
@@ -0,0 +1,408 @@
+//! Detection and decoding of every SRS artifact this repository produces, so
+//! that tools can reason about points regardless of the file they came from.
+//!
+//! file | layout
+//! --- | ---
+//! `extracted.data` | hash, then N g1, N g2, N alpha, N beta tau powers & beta in g2, all compressed
+//! `pp_<N>.data` | opening key, then N g1 tau powers, all compressed ( `to_var_bytes` )
+//! `pp_raw_<N>.data` | opening key, u64 length & N raw g1 tau powers ( `to_raw_var_bytes` )
+//! `g1_g2_<N>.txt` | g1 & g2 counts, then hex encoded compressed g1s & g2s, one per line
+
+use dusk_bytes::Serializable;
+use dusk_plonk::bls12_381::{G1Affine, G2Affine};
+use std::convert::TryInto;
+use std::fmt;
+use std::fs;
+use std::io;
+use std::path::Path;
+use std::str::FromStr;
+
+/// Blake2b hash of contribution chain, found at the start of `extracted.data`
+pub const HASH_SIZE: usize = 64;
+/// compressed G1 point
+pub const G1_SIZE: usize = 48;
+/// compressed G2 point
+pub const G2_SIZE: usize = 96;
+/// G1 point as written by `G1Affine::to_raw_bytes`
+pub const G1_RAW_SIZE: usize = 97;
+/// opening key ( g, h, beta_h ), always compressed
+pub const OPENING_KEY_SIZE: usize = G1_SIZE + G2_SIZE * 2;
+/// length prefix `CommitKey::to_raw_var_bytes` places before raw points
+pub const RAW_LENGTH_SIZE: usize = 8;
+
+// one tau power in each of g1, g2, alpha & beta sections of `extracted.data`
+const EXTRACTED_POWER_SIZE: usize = G1_SIZE + G2_SIZE + G1_SIZE + G1_SIZE;
+
+#[derive(Copy, Clone, Debug, PartialEq, Eq)]
+pub enum Format {
+    /// `extracted.data`
+    Extracted,
+    /// `pp_<N>.data`, written using `PublicParameters::to_var_bytes`
+    Compressed,
+    /// `pp_raw_<N>.data`, written using `PublicParameters::to_raw_var_bytes`
+    Raw,
+    /// `g1_g2_<N>.txt`
+    Text,
+}
+
+impl Format {
+    /// Guesses format of artifact by looking at its content, file names are never consulted.
+    pub fn detect(bytes: &[u8]) -> Option<Format> {
+        if is_text(bytes) {
+            return Some(Format::Text);
+        }
+
+        if bytes.len() > OPENING_KEY_SIZE + RAW_LENGTH_SIZE {
+            let len = raw_length(bytes);
+            let expected = len
+                .checked_mul(G1_RAW_SIZE)
+                .and_then(|l| l.checked_add(OPENING_KEY_SIZE + RAW_LENGTH_SIZE));
+            if expected == Some(bytes.len()) {
+                return Some(Format::Raw);
+            }
+        }
+
+        if bytes.len() > OPENING_KEY_SIZE && (bytes.len() - OPENING_KEY_SIZE) % G1_SIZE == 0 {
+            return Some(Format::Compressed);
+        }
+
+        let fixed = HASH_SIZE + G2_SIZE;
+        if bytes.len() > fixed && (bytes.len() - fixed) % EXTRACTED_POWER_SIZE == 0 {
+            return Some(Format::Extracted);
+        }
+
+        None
+    }
+
+    pub fn name(&self) -> &'static str {
+        match self {
+            Format::Extracted => "extracted",
+            Format::Compressed => "compressed",
+            Format::Raw => "raw",
+            Format::Text => "text",
+        }
+    }
+}
+
+impl fmt::Display for Format {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        write!(f, "{}", self.name())
+    }
+}
+
+impl FromStr for Format {
+    type Err = io::Error;
+
+    fn from_str(s: &str) -> Result<Format, io::Error> {
+        match s {
+            "extracted" => Ok(Format::Extracted),
+            "compressed" => Ok(Format::Compressed),
+            "raw" => Ok(Format::Raw),
+            "text" => Ok(Format::Text),
+            _ => Err(invalid(format!(
+                "unknown format `{}`, expected one of extracted, compressed, raw or text",
+                s
+            ))),
+        }
+    }
+}
+
+/// A point along with exact bytes it was decoded from. `point` is `None` when
+/// those bytes don't decode to a valid, torsion free curve point.
+#[derive(Clone, Debug)]
+pub struct Encoded<P> {
+    pub bytes: Vec<u8>,
+    pub point: Option<P>,
+}
+
+/// Decoded content of an SRS artifact.
+///
+/// For `pp_*` formats `g1s` holds commit key & `g2s` holds ( h, beta_h ) of opening key, while
+/// for `extracted.data` & `g1_g2_*.txt` they hold tau powers in g1 and g2 respectively.
+#[derive(Clone, Debug)]
+pub struct Artifact {
+    pub format: Format,
+    /// size of artifact in bytes
+    pub len: usize,
+    /// hash of contribution chain, only `extracted.data` carries it
+    pub hash: Option<[u8; HASH_SIZE]>,
+    /// g1 & g2 point counts, as claimed by artifact itself
+    pub declared: Option<(usize, usize)>,
+    /// g of opening key, only `pp_*` formats carry it
+    pub opening_g: Option<Encoded<G1Affine>>,
+    /// beta in g2, only `extracted.data` carries it
+    pub beta_g2: Option<Encoded<G2Affine>>,
+    pub g1s: Vec<Encoded<G1Affine>>,
+    pub g2s: Vec<Encoded<G2Affine>>,
+}
+
+impl Artifact {
+    pub fn open<P: AsRef<Path>>(path: P) -> io::Result<Artifact> {
+        let bytes = fs::read(path)?;
+        Artifact::from_bytes(&bytes)
+    }
+
+    pub fn from_bytes(bytes: &[u8]) -> io::Result<Artifact> {
+        match Format::detect(bytes) {
+            Some(Format::Extracted) => Ok(parse_extracted(bytes)),
+            Some(Format::Compressed) => Ok(parse_compressed(bytes)),
+            Some(Format::Raw) => Ok(parse_raw(bytes)),
+            Some(Format::Text) => parse_text(bytes),
+            None => Err(invalid(format!(
+                "{}b doesn't match layout of any known SRS artifact",
+                bytes.len()
+            ))),
+        }
+    }
+
+    /// Maximum degree of polynomial g1 powers of this artifact can commit to.
+    pub fn max_degree(&self) -> usize {
+        self.g1s.len().saturating_sub(1)
+    }
+
+    /// Number of points which failed to decode.
+    pub fn invalid_points(&self) -> usize {
+        let g1s = self
+            .g1s
+            .iter()
+            .chain(self.opening_g.iter())
+            .filter(|p| p.point.is_none())
+            .count();
+        let g2s = self
+            .g2s
+            .iter()
+            .chain(self.beta_g2.iter())
+            .filter(|p| p.point.is_none())
+            .count();
+        g1s + g2s
+    }
+}
+
+pub(crate) fn invalid<E>(err: E) -> io::Error
+where
+    E: Into<Box<dyn std::error::Error + Send + Sync>>,
+{
+    io::Error::new(io::ErrorKind::InvalidData, err)
+}
+
+fn is_text(bytes: &[u8]) -> bool {
+    match std::str::from_utf8(bytes) {
+        Ok(text) => text
+            .lines()
+            .next()
+            .map_or(false, |l| l.trim().parse::<usize>().is_ok()),
+        Err(_) => false,
+    }
+}
+
+fn raw_length(bytes: &[u8]) -> usize {
+    // `CommitKey::to_raw_var_bytes` transmutes length, so it's in native endianness
+    let len = &bytes[OPENING_KEY_SIZE..(OPENING_KEY_SIZE + RAW_LENGTH_SIZE)];
+    u64::from_ne_bytes(len.try_into().unwrap()) as usize
+}
+
+pub(crate) fn g1_compressed(bytes: &[u8]) -> Encoded<G1Affine> {
+    let point = bytes
+        .try_into()
+        .ok()
+        .and_then(|b: &[u8; G1_SIZE]| G1Affine::from_bytes(b).ok());
+    Encoded {
+        bytes: bytes.to_vec(),
+        point,
+    }
+}
+
+pub(crate) fn g2_compressed(bytes: &[u8]) -> Encoded<G2Affine> {
+    let point = bytes
+        .try_into()
+        .ok()
+        .and_then(|b: &[u8; G2_SIZE]| G2Affine::from_bytes(b).ok());
+    Encoded {
+        bytes: bytes.to_vec(),
+        point,
+    }
+}
+
+pub(crate) fn g1_raw(bytes: &[u8]) -> Encoded<G1Affine> {
+    // raw points are trusted by `from_slice_unchecked`, so checks are performed here
+    let point = unsafe { G1Affine::from_slice_unchecked(bytes) };
+    let valid = point.is_on_curve() & point.is_torsion_free();
+    Encoded {
+        bytes: bytes.to_vec(),
+        point: if bool::from(valid) { Some(point) } else { None },
+    }
+}
+
+fn parse_opening_key(bytes: &[u8]) -> (Encoded<G1Affine>, Vec<Encoded<G2Affine>>) {
+    let g = g1_compressed(&bytes[..G1_SIZE]);
+    let hs = bytes[G1_SIZE..OPENING_KEY_SIZE]
+        .chunks(G2_SIZE)
+        .map(g2_compressed)
+        .collect();
+    (g, hs)
+}
+
+fn parse_extracted(bytes: &[u8]) -> Artifact {
+    let n = (bytes.len() - HASH_SIZE - G2_SIZE) / EXTRACTED_POWER_SIZE;
+
+    let mut hash = [0u8; HASH_SIZE];
+    hash.copy_from_slice(&bytes[..HASH_SIZE]);
+
+    let g1_start = HASH_SIZE;
+    let g2_start = g1_start + n * G1_SIZE;
+    let g2_end = g2_start + n * G2_SIZE;
+    let beta_g2_start = bytes.len() - G2_SIZE;
+
+    Artifact {
+        format: Format::Extracted,
+        len: bytes.len(),
+        hash: Some(hash),
+        declared: None,
+        opening_g: None,
+        beta_g2: Some(g2_compressed(&bytes[beta_g2_start..])),
+        g1s: bytes[g1_start..g2_start]
+            .chunks(G1_SIZE)
+            .map(g1_compressed)
+            .collect(),
+        g2s: bytes[g2_start..g2_end]
+            .chunks(G2_SIZE)
+            .map(g2_compressed)
+            .collect(),
+    }
+}
+
+fn parse_compressed(bytes: &[u8]) -> Artifact {
+    let (g, hs) = parse_opening_key(bytes);
+
+    Artifact {
+        format: Format::Compressed,
+        len: bytes.len(),
+        hash: None,
+        declared: None,
+        opening_g: Some(g),
+        beta_g2: None,
+        g1s: bytes[OPENING_KEY_SIZE..]
+            .chunks(G1_SIZE)
+            .map(g1_compressed)
+            .collect(),
+        g2s: hs,
+    }
+}
+
+fn parse_raw(bytes: &[u8]) -> Artifact {
+    let (g, hs) = parse_opening_key(bytes);
+    let len = raw_length(bytes);
+
+    Artifact {
+        format: Format::Raw,
+        len: bytes.len(),
+        hash: None,
+        declared: Some((len, hs.len())),
+        opening_g: Some(g),
+        beta_g2: None,
+        g1s: bytes[(OPENING_KEY_SIZE + RAW_LENGTH_SIZE)..]
+            .chunks(G1_RAW_SIZE)
+            .map(g1_raw)
+            .collect(),
+        g2s: hs,
+    }
+}
+
+fn parse_text(bytes: &[u8]) -> io::Result<Artifact> {
+    let text = std::str::from_utf8(bytes).map_err(invalid)?;
+    let mut lines = text.lines().map(str::trim).filter(|l| !l.is_empty());
+
+    let mut count = |what: &str| -> io::Result<usize> {
+        lines
+            .next()
+            .ok_or_else(|| invalid(format!("missing {} count", what)))?
+            .parse::<usize>()
+            .map_err(invalid)
+    };
+    let g1_count = count("g1")?;
+    let g2_count = count("g2")?;
+
+    let mut point = |what: &str, i: usize| -> io::Result<Vec<u8>> {
+        let line = lines
+            .next()
+            .ok_or_else(|| invalid(format!("missing {} point #{}", what, i)))?;
+        hex::decode(line).map_err(invalid)
+    };
+    let g1s = (0..g1_count)
+        .map(|i| point("g1", i).map(|b| g1_compressed(&b)))
+        .collect::<io::Result<Vec<_>>>()?;
+    let g2s = (0..g2_count)
+        .map(|i| point("g2", i).map(|b| g2_compressed(&b)))
+        .collect::<io::Result<Vec<_>>>()?;
+
+    Ok(Artifact {
+        format: Format::Text,
+        len: bytes.len(),
+        hash: None,
+        declared: Some((g1_count, g2_count)),
+        opening_g: None,
+        beta_g2: None,
+        g1s,
+        g2s,
+    })
+}
+
+/// Artifacts of a made up ceremony of `n` powers, as `extracted.data`, `pp_<n>.data` &
+/// `g1_g2_<n>.txt`, each derived from `extracted.data` the way `main.rs` derives them.
+#[cfg(test)]
+pub(crate) fn test_artifacts(n: usize) -> (Vec<u8>, Vec<u8>, Vec<u8>) {
+    use dusk_plonk::bls12_381::BlsScalar;
+
+    let tau = BlsScalar::from(0x7a0);
+    let beta = BlsScalar::from(0xbe7a);
+    let mut power = BlsScalar::one();
+    let mut g1s = Vec::with_capacity(n);
+    let mut g2s = Vec::with_capacity(n);
+    for _ in 0..n {
+        g1s.push(G1Affine::from(G1Affine::generator() * power).to_bytes());
+        g2s.push(G2Affine::from(G2Affine::generator() * power).to_bytes());
+        power *= tau;
+    }
+    let beta_g2 = G2Affine::from(G2Affine::generator() * beta).to_bytes();
+
+    // alpha & beta tau powers are never read back, any valid g1 points do
+    let mut extracted = vec![0x5a; HASH_SIZE];
+    g1s.iter().for_each(|p| extracted.extend_from_slice(p));
+    g2s.iter().for_each(|p| extracted.extend_from_slice(p));
+    g1s.iter().for_each(|p| extracted.extend_from_slice(p));
+    g1s.iter().for_each(|p| extracted.extend_from_slice(p));
+    extracted.extend_from_slice(&beta_g2);
+
+    let mut compressed = g1s[0].to_vec();
+    compressed.extend_from_slice(&g2s[0]);
+    compressed.extend_from_slice(&beta_g2);
+    g1s.iter().for_each(|p| compressed.extend_from_slice(p));
+
+    let mut text = format!("{}\n{}\n", n, 2);
+    for p in g1s.iter() {
+        text.push_str(&hex::encode(p));
+        text.push('\n');
+    }
+    for p in g2s.iter().take(2) {
+        text.push_str(&hex::encode(p));
+        text.push('\n');
+    }
+
+    (extracted, compressed, text.into_bytes())
+}
+
+#[test]
+fn test_detect_format() {
+    let (extracted, compressed, text) = test_artifacts(8);
+
+    assert_eq!(Format::detect(&extracted), Some(Format::Extracted));
+    assert_eq!(Format::detect(&compressed), Some(Format::Compressed));
+    assert_eq!(Format::detect(&text), Some(Format::Text));
+    assert_eq!(Format::detect(&extracted[..100]), None);
+
+    let artifact = Artifact::from_bytes(&extracted).unwrap();
+    assert_eq!((artifact.g1s.len(), artifact.g2s.len()), (8, 8));
+    assert_eq!(artifact.invalid_points(), 0);
+    let artifact = Artifact::from_bytes(&text).unwrap();
+    assert_eq!(artifact.declared, Some((8, 2)));
+}
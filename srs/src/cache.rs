@@ -0,0 +1,490 @@
+//! Local cache of artifacts & their validated, decoded forms, shared by every process on a host.
+//!
+//! cache entry | path | content
+//! --- | --- | ---
+//! artifact | `<dir>/<sha256>` | artifact exactly as published, named after its hash
+//! decoded form | `<dir>/decoded/<sha256>.data` | same points, as in `pp_raw_<N>.data`
+//! stamp | `<dir>/decoded/<sha256>.json` | hash of decoded form, recorded once its points were validated
+//!
+//! Decoding `pp_<N>.data` means decompressing & subgroup checking every point. Decoded form is
+//! read back using `PublicParameters::from_slice_unchecked`, skipping those checks, as long as it
+//! matches its stamp: a decoded form whose hash doesn't match its stamp, or which was stamped by a
+//! different version of this crate, is decoded & validated again from artifact.
+//!
+//! Stamp only catches corruption & stale entries. It's stored next to decoded form, so whoever
+//! may write into cache directory may rewrite both, making unchecked points trusted. Cache
+//! directory must therefore be writable by trusted users only; `cache verify` re-checks every
+//! point of an entry whose origin is in doubt.
+//!
+//! Hashes are the only names taken from callers, anything but 64 hex digits is refused, so that
+//! no entry point reaches outside cache directory.
+
+use crate::artifact::{invalid, Artifact, Format};
+use crate::convert::to_public_parameters;
+use crate::manifest::sha256;
+use dusk_plonk::commitment_scheme::kzg10::PublicParameters;
+use serde::{Deserialize, Serialize};
+use std::env;
+use std::fs;
+use std::io;
+use std::path::{Path, PathBuf};
+use std::time::{SystemTime, UNIX_EPOCH};
+
+/// Overrides default cache directory.
+pub const CACHE_DIR_ENV: &str = "SRS_CACHE_DIR";
+
+const DECODED_DIR: &str = "decoded";
+
+/// Proof of validation of a decoded form.
+#[derive(Clone, Debug, PartialEq, Eq, Serialize, Deserialize)]
+pub struct Stamp {
+    /// hex encoded sha256 of artifact points were decoded from
+    pub artifact: String,
+    /// hex encoded sha256 of decoded form
+    pub decoded: String,
+    /// number of g1 powers
+    pub points: usize,
+    /// version of this crate, which validated points
+    pub version: String,
+    /// seconds since unix epoch
+    pub validated_at: u64,
+}
+
+/// What's cached under a hash.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct Entry {
+    pub hash: String,
+    /// size of artifact, if it's cached
+    pub artifact: Option<u64>,
+    /// size of decoded form along with its stamp, if it's cached
+    pub decoded: Option<u64>,
+    /// last time any file of this entry was written, or read through cache
+    pub last_used: SystemTime,
+}
+
+impl Entry {
+    pub fn size(&self) -> u64 {
+        self.artifact.unwrap_or(0) + self.decoded.unwrap_or(0)
+    }
+}
+
+/// Content-addressed store of artifacts & their decoded forms, keyed by hex encoded sha256 of
+/// artifact.
+#[derive(Clone, Debug)]
+pub struct Cache {
+    dir: PathBuf,
+}
+
+impl Cache {
+    pub fn new<P: AsRef<Path>>(dir: P) -> Cache {
+        Cache {
+            dir: dir.as_ref().to_path_buf(),
+        }
+    }
+
+    /// `$SRS_CACHE_DIR`, else `$HOME/.cache/avail-srs`, else a directory under temp dir.
+    pub fn default_dir() -> PathBuf {
+        if let Some(dir) = env::var_os(CACHE_DIR_ENV) {
+            return PathBuf::from(dir);
+        }
+        match env::var_os("HOME") {
+            Some(home) => Path::new(&home).join(".cache").join("avail-srs"),
+            None => env::temp_dir().join("avail-srs"),
+        }
+    }
+
+    pub fn dir(&self) -> &Path {
+        &self.dir
+    }
+
+    /// Path of artifact with given hash, which must be 64 hex digits.
+    pub fn path(&self, hash: &str) -> io::Result<PathBuf> {
+        Ok(self.dir.join(checked(hash)?))
+    }
+
+    fn decoded_path(&self, hash: &str) -> io::Result<PathBuf> {
+        Ok(self
+            .dir
+            .join(DECODED_DIR)
+            .join(format!("{}.data", checked(hash)?)))
+    }
+
+    fn stamp_path(&self, hash: &str) -> io::Result<PathBuf> {
+        Ok(self
+            .dir
+            .join(DECODED_DIR)
+            .join(format!("{}.json", checked(hash)?)))
+    }
+
+    /// Cached content with given hash, `None` if it's missing or doesn't match its hash anymore.
+    pub fn get(&self, hash: &str) -> io::Result<Option<Vec<u8>>> {
+        let path = self.path(hash)?;
+        match fs::read(&path) {
+            Ok(bytes) if sha256(&bytes) == hash => {
+                touch(&path);
+                Ok(Some(bytes))
+            }
+            Ok(_) => Ok(None),
+            Err(e) if e.kind() == io::ErrorKind::NotFound => Ok(None),
+            Err(e) => Err(e),
+        }
+    }
+
+    /// Stores content, returning its hash.
+    pub fn put(&self, bytes: &[u8]) -> io::Result<String> {
+        let hash = sha256(bytes);
+        write_atomic(&self.path(&hash)?, bytes)?;
+        Ok(hash)
+    }
+
+    /// Stamp of decoded form of artifact with given hash.
+    pub fn stamp(&self, hash: &str) -> io::Result<Option<Stamp>> {
+        match fs::read(self.stamp_path(hash)?) {
+            Ok(bytes) => Ok(serde_json::from_slice(&bytes).ok()),
+            Err(e) if e.kind() == io::ErrorKind::NotFound => Ok(None),
+            Err(e) => Err(e),
+        }
+    }
+
+    /// Decoded form of artifact with given hash, `None` unless it's vouched for by its stamp.
+    pub fn decoded(&self, hash: &str) -> io::Result<Option<PublicParameters>> {
+        let stamp = match self.stamp(hash)? {
+            Some(stamp) if stamp.artifact == hash && stamp.version == env!("CARGO_PKG_VERSION") => {
+                stamp
+            }
+            _ => return Ok(None),
+        };
+
+        let decoded_path = self.decoded_path(hash)?;
+        let bytes = match fs::read(&decoded_path) {
+            Ok(bytes) => bytes,
+            Err(e) if e.kind() == io::ErrorKind::NotFound => return Ok(None),
+            Err(e) => return Err(e),
+        };
+        if sha256(&bytes) != stamp.decoded || Format::detect(&bytes) != Some(Format::Raw) {
+            return Ok(None);
+        }
+
+        // safe, stamp vouches points were validated before being written
+        let pp = unsafe { PublicParameters::from_slice_unchecked(&bytes) };
+        if pp.max_degree() + 1 != stamp.points {
+            return Ok(None);
+        }
+        touch(&decoded_path);
+        Ok(Some(pp))
+    }
+
+    /// Writes decoded form of artifact with given hash, stamping it. Points must've been validated.
+    pub fn put_decoded(&self, hash: &str, pp: &PublicParameters) -> io::Result<Stamp> {
+        let bytes = pp.to_raw_var_bytes();
+        let stamp = Stamp {
+            artifact: hash.to_owned(),
+            decoded: sha256(&bytes),
+            points: pp.max_degree() + 1,
+            version: env!("CARGO_PKG_VERSION").to_owned(),
+            validated_at: SystemTime::now()
+                .duration_since(UNIX_EPOCH)
+                .map(|d| d.as_secs())
+                .unwrap_or(0),
+        };
+
+        // decoded form first, so that a stamp never points to a missing one
+        write_atomic(&self.decoded_path(hash)?, &bytes)?;
+        let mut json = serde_json::to_vec_pretty(&stamp)?;
+        json.push(b'\n');
+        write_atomic(&self.stamp_path(hash)?, &json)?;
+        Ok(stamp)
+    }
+
+    /// Hashes of everything cached, with sizes.
+    pub fn entries(&self) -> io::Result<Vec<Entry>> {
+        let mut entries: Vec<Entry> = Vec::new();
+        let mut record = |hash: &str, artifact: bool, meta: fs::Metadata| -> io::Result<()> {
+            let used = meta.modified()?;
+            let i = match entries.iter().position(|e| e.hash == hash) {
+                Some(i) => i,
+                None => {
+                    entries.push(Entry {
+                        hash: hash.to_owned(),
+                        artifact: None,
+                        decoded: None,
+                        last_used: used,
+                    });
+                    entries.len() - 1
+                }
+            };
+
+            let entry = &mut entries[i];
+            let size = if artifact {
+                &mut entry.artifact
+            } else {
+                &mut entry.decoded
+            };
+            *size = Some(size.unwrap_or(0) + meta.len());
+            entry.last_used = entry.last_used.max(used);
+            Ok(())
+        };
+
+        for (dir, artifact) in [
+            (self.dir.clone(), true),
+            (self.dir.join(DECODED_DIR), false),
+        ] {
+            let listing = match fs::read_dir(&dir) {
+                Ok(listing) => listing,
+                Err(e) if e.kind() == io::ErrorKind::NotFound => continue,
+                Err(e) => return Err(e),
+            };
+            for file in listing {
+                let file = file?;
+                let meta = file.metadata()?;
+                let name = file.file_name().to_string_lossy().into_owned();
+                let hash = name.split('.').next().unwrap_or_default();
+                if meta.is_file() && is_hash(hash) && (artifact == (name == hash)) {
+                    record(hash, artifact, meta)?;
+                }
+            }
+        }
+
+        entries.sort_by(|a, b| a.hash.cmp(&b.hash));
+        Ok(entries)
+    }
+
+    /// Removes artifact with given hash along with its decoded form, returning bytes freed.
+    pub fn evict(&self, hash: &str) -> io::Result<u64> {
+        let mut freed = 0;
+        for path in [
+            self.path(hash)?,
+            self.decoded_path(hash)?,
+            self.stamp_path(hash)?,
+        ] {
+            match fs::metadata(&path) {
+                Ok(meta) => {
+                    fs::remove_file(&path)?;
+                    freed += meta.len();
+                }
+                Err(e) if e.kind() == io::ErrorKind::NotFound => {}
+                Err(e) => return Err(e),
+            }
+        }
+        Ok(freed)
+    }
+
+    /// Evicts least recently used entries until cache takes at most `max_bytes`, returning
+    /// evicted hashes.
+    pub fn prune(&self, max_bytes: u64) -> io::Result<Vec<String>> {
+        let mut entries = self.entries()?;
+        entries.sort_by_key(|e| e.last_used);
+
+        let mut total: u64 = entries.iter().map(Entry::size).sum();
+        let mut evicted = Vec::new();
+        for entry in entries {
+            if total <= max_bytes {
+                break;
+            }
+            total -= self.evict(&entry.hash)?.min(total);
+            evicted.push(entry.hash);
+        }
+        Ok(evicted)
+    }
+
+    /// Re-checks integrity of everything cached under given hash: artifact must match its hash,
+    /// decoded form must match its stamp, every decoded point must be valid, and decoded form must
+    /// hold same points as artifact, when both are cached.
+    pub fn check(&self, hash: &str) -> io::Result<()> {
+        let artifact = match fs::read(self.path(hash)?) {
+            Ok(bytes) if sha256(&bytes) == hash => Some(bytes),
+            Ok(_) => return Err(invalid(format!("artifact doesn't match its hash {}", hash))),
+            Err(e) if e.kind() == io::ErrorKind::NotFound => None,
+            Err(e) => return Err(e),
+        };
+
+        let stamp = match self.stamp(hash)? {
+            Some(stamp) => stamp,
+            None if self.decoded_path(hash)?.exists() => {
+                return Err(invalid(format!("decoded form of {} isn't stamped", hash)))
+            }
+            None => return Ok(()),
+        };
+        if stamp.artifact != hash {
+            return Err(invalid(format!(
+                "stamp of {} is for artifact {}",
+                hash, stamp.artifact
+            )));
+        }
+
+        let decoded = fs::read(self.decoded_path(hash)?)?;
+        if sha256(&decoded) != stamp.decoded {
+            return Err(invalid(format!(
+                "decoded form of {} doesn't match its stamp",
+                hash
+            )));
+        }
+
+        let points = Artifact::from_bytes(&decoded)?;
+        if points.format != Format::Raw || points.g1s.len() != stamp.points {
+            return Err(invalid(format!(
+                "decoded form of {} must hold {} raw g1 powers",
+                hash, stamp.points
+            )));
+        }
+        if points.invalid_points() != 0 {
+            return Err(invalid(format!(
+                "decoded form of {} holds {} invalid points",
+                hash,
+                points.invalid_points()
+            )));
+        }
+
+        if let Some(artifact) = artifact {
+            let pp = to_public_parameters(&Artifact::from_bytes(&artifact)?)?;
+            if pp.to_raw_var_bytes() != decoded {
+                return Err(invalid(format!(
+                    "decoded form of {} doesn't hold same points as artifact",
+                    hash
+                )));
+            }
+        }
+        Ok(())
+    }
+}
+
+impl Default for Cache {
+    fn default() -> Cache {
+        Cache::new(Cache::default_dir())
+    }
+}
+
+/// Public parameters opened through cache, along with hash of artifact they came from.
+pub struct Srs {
+    pub hash: String,
+    pub pp: PublicParameters,
+}
+
+impl Srs {
+    /// Opens artifact with given hash through default cache, see `open_cached_in`.
+    pub fn open_cached(hash: &str) -> io::Result<Srs> {
+        Srs::open_cached_in(&Cache::default(), hash)
+    }
+
+    /// Opens artifact with given hash, which must've been cached, e.g. by `Fetcher`. Stamped
+    /// decoded form is used as is, otherwise artifact is decoded, validated & stamped.
+    pub fn open_cached_in(cache: &Cache, hash: &str) -> io::Result<Srs> {
+        checked(hash)?;
+        if let Some(pp) = cache.decoded(hash)? {
+            return Ok(Srs {
+                hash: hash.to_owned(),
+                pp,
+            });
+        }
+
+        let bytes = cache
+            .get(hash)?
+            .ok_or_else(|| invalid(format!("no artifact with hash {} in cache", hash)))?;
+        let pp = to_public_parameters(&Artifact::from_bytes(&bytes)?)?;
+        cache.put_decoded(hash, &pp)?;
+        Ok(Srs {
+            hash: hash.to_owned(),
+            pp,
+        })
+    }
+}
+
+fn is_hash(s: &str) -> bool {
+    s.len() == 64 && s.bytes().all(|b| b.is_ascii_hexdigit())
+}
+
+fn checked(hash: &str) -> io::Result<&str> {
+    if is_hash(hash) {
+        Ok(hash)
+    } else {
+        Err(io::Error::new(
+            io::ErrorKind::InvalidInput,
+            format!("`{}` isn't a hex encoded sha256", hash),
+        ))
+    }
+}
+
+/// Marks a cache file as just used, for `prune`. Best effort, a cache shared read-only may not
+/// allow it.
+fn touch(path: &Path) {
+    if let Ok(file) = fs::OpenOptions::new().write(true).open(path) {
+        let _ = file.set_modified(SystemTime::now());
+    }
+}
+
+/// Writes into a temporary file first, which is then renamed, so that readers never see a
+/// partially written file.
+fn write_atomic(path: &Path, bytes: &[u8]) -> io::Result<()> {
+    let dir = path.parent().unwrap_or_else(|| Path::new("."));
+    fs::create_dir_all(dir)?;
+
+    let name = path.file_name().unwrap_or_default().to_string_lossy();
+    let tmp = dir.join(format!(".{}.{}.tmp", name, std::process::id()));
+    fs::write(&tmp, bytes)?;
+    fs::rename(&tmp, path)
+}
+
+#[test]
+fn test_open_cached() {
+    use rand::rngs::StdRng;
+    use rand::SeedableRng;
+
+    let dir = env::temp_dir().join(format!("srs_cache_{}", std::process::id()));
+    let _ = fs::remove_dir_all(&dir);
+    let cache = Cache::new(&dir);
+
+    let mut rng = StdRng::seed_from_u64(36);
+    let pp = PublicParameters::setup(1 << 4, &mut rng).unwrap();
+    let hash = cache.put(&pp.to_var_bytes()).unwrap();
+    assert!(cache.decoded(&hash).unwrap().is_none());
+
+    // first open validates & stamps
+    let srs = Srs::open_cached_in(&cache, &hash).unwrap();
+    assert_eq!(srs.pp.to_var_bytes(), pp.to_var_bytes());
+    let stamp = cache.stamp(&hash).unwrap().unwrap();
+    assert_eq!(stamp.points, 17);
+    assert!(cache.decoded(&hash).unwrap().is_some());
+    assert!(cache.check(&hash).is_ok());
+
+    // a decoded form not matching its stamp isn't used, it's rebuilt from artifact instead
+    let decoded_path = cache.decoded_path(&hash).unwrap();
+    let mut decoded = fs::read(&decoded_path).unwrap();
+    let last = decoded.len() - 1;
+    decoded[last] ^= 1;
+    fs::write(&decoded_path, &decoded).unwrap();
+    assert!(cache.decoded(&hash).unwrap().is_none());
+    assert!(cache.check(&hash).is_err());
+    let srs = Srs::open_cached_in(&cache, &hash).unwrap();
+    assert_eq!(srs.pp.to_var_bytes(), pp.to_var_bytes());
+    assert!(cache.check(&hash).is_ok());
+
+    let entries = cache.entries().unwrap();
+    assert_eq!(entries.len(), 1);
+    assert!(entries[0].artifact.is_some() && entries[0].decoded.is_some());
+
+    assert_eq!(cache.prune(u64::MAX).unwrap(), Vec::<String>::new());
+    assert_eq!(cache.prune(0).unwrap(), vec![hash.clone()]);
+    assert!(cache.entries().unwrap().is_empty());
+    assert!(Srs::open_cached_in(&cache, &hash).is_err());
+
+    // names other than hashes never reach outside cache directory
+    let outside = dir.with_extension("outside");
+    fs::write(&outside, b"not cached").unwrap();
+    let traversal = format!("../{}", outside.file_name().unwrap().to_string_lossy());
+    assert_eq!(
+        cache.evict(&traversal).unwrap_err().kind(),
+        io::ErrorKind::InvalidInput
+    );
+    assert!(outside.exists());
+    for hash in [traversal.as_str(), "", &"a".repeat(63), &"g".repeat(64)] {
+        assert_eq!(
+            cache.path(hash).unwrap_err().kind(),
+            io::ErrorKind::InvalidInput
+        );
+        assert!(cache.get(hash).is_err() && cache.check(hash).is_err());
+        assert!(Srs::open_cached_in(&cache, hash).is_err());
+    }
+    fs::remove_file(&outside).unwrap();
+
+    fs::remove_dir_all(&dir).unwrap();
+}
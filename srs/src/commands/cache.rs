@@ -0,0 +1,106 @@
+use srs::cache::{Cache, Srs};
+
+const USAGE: &str = "invoke program using `cargo run cache [--cache <cache-dir>] list`, \
+`cargo run cache [--cache <cache-dir>] open <sha256>`, \
+`cargo run cache [--cache <cache-dir>] verify [<sha256>...]`, \
+`cargo run cache [--cache <cache-dir>] evict <sha256>...` or \
+`cargo run cache [--cache <cache-dir>] prune <max-bytes>`";
+
+/// `srs cache [--cache <dir>] list|open|verify|evict|prune ...`
+pub fn run(args: &[String]) -> std::io::Result<()> {
+    let (cache, args) = match args {
+        [flag, dir, rest @ ..] if flag == "--cache" => (Cache::new(dir), rest),
+        _ => (Cache::default(), args),
+    };
+    let args: Vec<&str> = args.iter().map(String::as_str).collect();
+
+    match args.as_slice() {
+        ["list"] => list(&cache),
+        ["open", hash] => open(&cache, hash),
+        ["verify", hashes @ ..] => verify(&cache, hashes),
+        ["evict", hashes @ ..] if !hashes.is_empty() => evict(&cache, hashes),
+        ["prune", max_bytes] => prune(&cache, max_bytes),
+        _ => panic!("{}", USAGE),
+    }
+}
+
+fn list(cache: &Cache) -> std::io::Result<()> {
+    println!("cache `{}`", cache.dir().display());
+    for entry in cache.entries()? {
+        let stamp = cache.stamp(&entry.hash)?;
+        println!(
+            "{}\tartifact: {}\tdecoded: {}",
+            entry.hash,
+            entry
+                .artifact
+                .map(|s| format!("{}b", s))
+                .unwrap_or_else(|| "-".to_owned()),
+            match (entry.decoded, stamp) {
+                (Some(s), Some(stamp)) => format!(
+                    "{}b, {} points, stamped by v{} at {}",
+                    s, stamp.points, stamp.version, stamp.validated_at
+                ),
+                (Some(s), None) => format!("{}b, unstamped", s),
+                (None, _) => "-".to_owned(),
+            }
+        );
+    }
+    Ok(())
+}
+
+fn open(cache: &Cache, hash: &str) -> std::io::Result<()> {
+    let stamped = cache.decoded(hash)?.is_some();
+    let srs = Srs::open_cached_in(cache, hash)?;
+    println!(
+        "public params of max degree: {}, {}\t[OBTAINED]",
+        srs.pp.max_degree(),
+        if stamped {
+            "from stamped decoded form"
+        } else {
+            "validated & stamped now"
+        }
+    );
+    Ok(())
+}
+
+fn verify(cache: &Cache, hashes: &[&str]) -> std::io::Result<()> {
+    let hashes: Vec<String> = if hashes.is_empty() {
+        cache.entries()?.into_iter().map(|e| e.hash).collect()
+    } else {
+        hashes.iter().map(|h| h.to_string()).collect()
+    };
+
+    let mut intact = true;
+    for hash in hashes.iter() {
+        match cache.check(hash) {
+            Ok(()) => println!("{}\t✅", hash),
+            Err(e) => {
+                intact = false;
+                println!("{}\t❌ {}", hash, e);
+            }
+        }
+    }
+
+    if !intact {
+        std::process::exit(1);
+    }
+    println!("{} cache entries\t[VERIFIED]", hashes.len());
+    Ok(())
+}
+
+fn evict(cache: &Cache, hashes: &[&str]) -> std::io::Result<()> {
+    for hash in hashes {
+        println!("{}\tfreed {}b", hash, cache.evict(hash)?);
+    }
+    Ok(())
+}
+
+fn prune(cache: &Cache, max_bytes: &str) -> std::io::Result<()> {
+    let max_bytes: u64 = max_bytes
+        .parse()
+        .expect("max bytes must be an unsigned integer");
+    for hash in cache.prune(max_bytes)? {
+        println!("{}\tevicted", hash);
+    }
+    Ok(())
+}
@@ -0,0 +1,23 @@
+use srs::artifact::Format;
+use srs::convert::convert;
+use std::fs::{self, File};
+use std::io::Write;
+
+/// `srs convert <compressed|raw|text> <input> <output>`
+pub fn run(args: &[String]) -> std::io::Result<()> {
+    assert_eq!(
+        args.len(),
+        3,
+        "invoke program using `cargo run convert <compressed|raw|text> <path-to-input> <path-to-output>`"
+    );
+
+    let to: Format = args[0].parse()?;
+    let input = fs::read(&args[1])?;
+    let output = convert(&input, to)?;
+
+    let mut fd = File::create(&args[2])?;
+    fd.write_all(&output)?;
+    println!("converted `{}` into {} form `{}`\t✅", args[1], to, args[2]);
+
+    Ok(())
+}
@@ -0,0 +1,47 @@
+use srs::artifact::Artifact;
+use srs::crosscheck::{self, Arkworks, Backend, Dusk, PolyMultiproof, Powers};
+
+const USAGE: &str = "invoke program using `cargo run --features crosscheck crosscheck <path-to-artifact> <degree> <polynomials> [seed]`";
+
+/// `srs crosscheck <artifact> <degree> <polynomials> [seed]`
+pub fn run(args: &[String]) -> std::io::Result<()> {
+    assert!(args.len() == 3 || args.len() == 4, "{}", USAGE);
+    let degree: usize = args[1].parse().expect("degree must be a positive integer");
+    let count: usize = args[2]
+        .parse()
+        .expect("polynomial count must be a positive integer");
+    let seed: u64 = args
+        .get(3)
+        .map(|s| s.parse().expect("seed must be an unsigned integer"))
+        .unwrap_or(0);
+
+    let powers = Powers::from_artifact(&Artifact::open(&args[0])?, degree)?;
+    let dusk = Dusk::new(&powers)?;
+    let ark = Arkworks::new(&powers)?;
+    let pmp = PolyMultiproof::new(&powers)?;
+    let backends: [&dyn Backend; 3] = [&dusk, &ark, &pmp];
+    println!(
+        "loaded {} g1 & 2 g2 powers into {}",
+        powers.g1s.len(),
+        backends
+            .iter()
+            .map(|b| b.name())
+            .collect::<Vec<_>>()
+            .join(", ")
+    );
+
+    let polys = crosscheck::polynomials(seed, count, degree);
+    let mismatches = crosscheck::compare(&backends, &polys)?;
+    for m in mismatches.iter() {
+        println!("{}\t❌", m);
+    }
+    if !mismatches.is_empty() {
+        std::process::exit(1);
+    }
+
+    println!(
+        "{} polynomials of degree {}: commitments & proofs byte-identical across libraries\t✅",
+        count, degree
+    );
+    Ok(())
+}
@@ -0,0 +1,45 @@
+use srs::artifact::Artifact;
+use srs::diff::Diff;
+use std::process;
+
+/// `srs diff <artifact-a> <artifact-b>`
+///
+/// Exits with status 1 when artifacts hold different points, same as `diff(1)`.
+pub fn run(args: &[String]) -> std::io::Result<()> {
+    assert_eq!(
+        args.len(),
+        2,
+        "invoke program using `cargo run diff <path-to-artifact> <path-to-artifact>`"
+    );
+
+    let a = Artifact::open(&args[0])?;
+    let b = Artifact::open(&args[1])?;
+    println!(
+        "a: {}\t{} format, {} g1s, {} g2s",
+        args[0],
+        a.format,
+        a.g1s.len(),
+        a.g2s.len()
+    );
+    println!(
+        "b: {}\t{} format, {} g1s, {} g2s",
+        args[1],
+        b.format,
+        b.g1s.len(),
+        b.g2s.len()
+    );
+
+    let diff = Diff::new(&a, &b);
+    print!("{}", diff);
+
+    if diff.is_identical() {
+        println!("artifacts are identical\t✅");
+    } else if diff.same_points() {
+        println!("artifacts hold same points where they overlap\t✅");
+    } else {
+        println!("artifacts hold different points\t❌");
+        process::exit(1);
+    }
+
+    Ok(())
+}
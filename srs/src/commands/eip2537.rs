@@ -0,0 +1,86 @@
+use srs::convert::open_public_parameters;
+use srs::eip2537::{self, VerifierKey};
+use srs::kzg::OpeningProof;
+use std::fs;
+
+const USAGE: &str = "invoke program using `cargo run eip2537 encode <path-to-pp>`, \
+`cargo run eip2537 solidity <path-to-pp> <path-to-output.sol>` or \
+`cargo run eip2537 proof <path-to-pp> <path-to-proof.json>`";
+
+/// `srs eip2537 encode|solidity|proof ...`
+pub fn run(args: &[String]) -> std::io::Result<()> {
+    match args.first().map(String::as_str) {
+        Some("encode") if args.len() == 2 => encode(&args[1]),
+        Some("solidity") if args.len() == 3 => solidity(&args[1], &args[2]),
+        Some("proof") if args.len() == 3 => proof(&args[1], &args[2]),
+        _ => panic!("{}", USAGE),
+    }
+}
+
+fn encode(pp_path: &str) -> std::io::Result<()> {
+    let key = VerifierKey::from_public_parameters(&open_public_parameters(pp_path)?)?;
+
+    println!("g: 0x{}", hex::encode(eip2537::g1_to_eip2537(&key.g)));
+    println!("h: 0x{}", hex::encode(eip2537::g2_to_eip2537(&key.h)));
+    println!(
+        "beta_h: 0x{}",
+        hex::encode(eip2537::g2_to_eip2537(&key.beta_h))
+    );
+    println!(
+        "neg_beta_h: 0x{}",
+        hex::encode(eip2537::g2_to_eip2537(&-key.beta_h))
+    );
+    Ok(())
+}
+
+fn solidity(pp_path: &str, out: &str) -> std::io::Result<()> {
+    let key = VerifierKey::from_public_parameters(&open_public_parameters(pp_path)?)?;
+
+    fs::write(out, key.solidity())?;
+    println!("wrote KZG verifier contract into `{}`\t✅", out);
+    Ok(())
+}
+
+fn proof(pp_path: &str, proof_path: &str) -> std::io::Result<()> {
+    let pp = open_public_parameters(pp_path)?;
+    let key = VerifierKey::from_public_parameters(&pp)?;
+
+    for (i, encoded) in OpeningProof::open_all(proof_path)?.iter().enumerate() {
+        let (point, proof) = encoded.decode()?;
+        let commitment = proof.commitment_to_polynomial.0;
+        let witness = proof.commitment_to_witness.0;
+
+        let msm_input = key.msm_input(&commitment, &point, &proof.evaluated_point, &witness);
+        let pairing_input = key.pairing_input(&eip2537::g1_msm(&msm_input)?, &witness);
+        assert!(
+            eip2537::pairing_check(&pairing_input)?,
+            "proof {} of `{}` doesn't pass pairing check",
+            i,
+            proof_path
+        );
+
+        println!("proof {}", i);
+        println!(
+            "commitment: 0x{}",
+            hex::encode(eip2537::g1_to_eip2537(&commitment))
+        );
+        println!(
+            "point: 0x{}",
+            hex::encode(eip2537::scalar_to_eip2537(&point))
+        );
+        println!(
+            "value: 0x{}",
+            hex::encode(eip2537::scalar_to_eip2537(&proof.evaluated_point))
+        );
+        println!(
+            "witness: 0x{}",
+            hex::encode(eip2537::g1_to_eip2537(&witness))
+        );
+        println!("g1 msm input: 0x{}", hex::encode(&msm_input));
+        println!(
+            "pairing check input: 0x{}\t[VERIFIED]",
+            hex::encode(&pairing_input)
+        );
+    }
+    Ok(())
+}
@@ -0,0 +1,43 @@
+use srs::fetch::{Cache, Fetcher, Source, PINNED};
+use std::fs;
+use std::path::Path;
+
+const USAGE: &str = "invoke program using `cargo run fetch [--from <base-url-or-dir>] [--cache <cache-dir>] [--out <output-dir>] [<artifact>...]`";
+
+/// `srs fetch [--from <source>] [--cache <dir>] [--out <dir>] [<artifact>...]`
+pub fn run(args: &[String]) -> std::io::Result<()> {
+    let mut source = Source::default();
+    let mut cache = Cache::default();
+    let mut out = None;
+    let mut names = Vec::new();
+    let mut iter = args.iter();
+    while let Some(arg) = iter.next() {
+        match arg.as_str() {
+            "--from" => source = Source::parse(iter.next().expect(USAGE)),
+            "--cache" => cache = Cache::new(iter.next().expect(USAGE)),
+            "--out" => out = Some(Path::new(iter.next().expect(USAGE))),
+            _ => names.push(arg.as_str()),
+        }
+    }
+    if names.is_empty() {
+        names = PINNED.iter().map(|(n, _)| *n).collect();
+    }
+
+    let fetcher = Fetcher::new(source, cache);
+    for name in names {
+        let bytes = fetcher.fetch(name)?;
+        let hash = &fetcher.pins()[name];
+        println!(
+            "{}\t{}\t`{}`\t[VERIFIED]",
+            name,
+            hash,
+            fetcher.cache.path(hash)?.display()
+        );
+
+        if let Some(dir) = out {
+            fs::create_dir_all(dir)?;
+            fs::write(dir.join(name), &bytes)?;
+        }
+    }
+    Ok(())
+}
@@ -0,0 +1,22 @@
+use srs::artifact::Artifact;
+use srs::inspect::Summary;
+
+/// `srs inspect [--json] <artifact>`
+pub fn run(args: &[String]) -> std::io::Result<()> {
+    let json = args.iter().any(|a| a == "--json");
+    let paths: Vec<&String> = args.iter().filter(|a| *a != "--json").collect();
+    assert_eq!(
+        paths.len(),
+        1,
+        "invoke program using `cargo run inspect [--json] <path-to-artifact>`"
+    );
+
+    let summary = Summary::new(&Artifact::open(paths[0])?);
+    if json {
+        println!("{}", serde_json::to_string_pretty(&summary.to_json())?);
+    } else {
+        print!("{}", summary);
+    }
+
+    Ok(())
+}
@@ -0,0 +1,130 @@
+use dusk_plonk::bls12_381::BlsScalar;
+use merlin::Transcript;
+use srs::convert::open_public_parameters;
+use srs::kzg::{self, OpeningProof, PolynomialFile};
+use std::fs;
+use std::io;
+use std::process;
+
+const USAGE: &str = "invoke program using `cargo run kzg commit <path-to-pp> <path-to-polynomial> [--evaluations]`, \
+`cargo run kzg open <path-to-pp> <path-to-polynomial> <point-hex> [--evaluations] [--out <path-to-proof.json>]`, \
+`cargo run kzg verify <path-to-pp> <path-to-proof.json>` or \
+`cargo run kzg batch-verify <path-to-pp> <path-to-proof.json>...`";
+
+/// `srs kzg commit|open|verify|batch-verify ...`
+///
+/// `verify` & `batch-verify` exit with status 1 when a proof is invalid.
+pub fn run(args: &[String]) -> std::io::Result<()> {
+    let evaluations = args.iter().any(|a| a == "--evaluations");
+    let mut out = None;
+    let mut positional = Vec::new();
+    let mut iter = args.iter();
+    while let Some(arg) = iter.next() {
+        match arg.as_str() {
+            "--evaluations" => {}
+            "--out" => out = Some(iter.next().expect(USAGE).as_str()),
+            _ => positional.push(arg.as_str()),
+        }
+    }
+
+    match positional.as_slice() {
+        ["commit", pp, poly] => commit(pp, poly, evaluations),
+        ["open", pp, poly, point] => open(pp, poly, point, evaluations, out),
+        ["verify", pp, proof] => verify(pp, proof),
+        ["batch-verify", pp, proofs @ ..] if !proofs.is_empty() => batch_verify(pp, proofs),
+        _ => panic!("{}", USAGE),
+    }
+}
+
+fn commit(pp_path: &str, poly_path: &str, evaluations: bool) -> std::io::Result<()> {
+    let pp = open_public_parameters(pp_path)?;
+    let poly = PolynomialFile::open(poly_path, evaluations)?.to_polynomial()?;
+
+    let commitment = pp
+        .commit_key()
+        .commit(&poly)
+        .map_err(|e| invalid(format!("unable to commit to polynomial: {:?}", e)))?;
+    println!("degree: {}", poly.degree());
+    println!("commitment: {}", kzg::g1_to_hex(&commitment.0));
+    Ok(())
+}
+
+fn open(
+    pp_path: &str,
+    poly_path: &str,
+    point: &str,
+    evaluations: bool,
+    out: Option<&str>,
+) -> std::io::Result<()> {
+    let pp = open_public_parameters(pp_path)?;
+    let poly = PolynomialFile::open(poly_path, evaluations)?.to_polynomial()?;
+    let point = kzg::scalar_from_hex(point)?;
+    let value = poly.evaluate(&point);
+
+    let proof = pp
+        .commit_key()
+        .open_single(&poly, &value, &point)
+        .map_err(|e| invalid(format!("unable to open polynomial: {:?}", e)))?;
+    let json = serde_json::to_string_pretty(&OpeningProof::new(&point, &proof))?;
+
+    match out {
+        Some(path) => {
+            fs::write(path, &json)?;
+            println!("wrote opening proof into `{}`\t✅", path);
+        }
+        None => println!("{}", json),
+    }
+    Ok(())
+}
+
+fn verify(pp_path: &str, proof_path: &str) -> std::io::Result<()> {
+    let pp = open_public_parameters(pp_path)?;
+
+    let mut valid = true;
+    for (i, proof) in OpeningProof::open_all(proof_path)?.iter().enumerate() {
+        let (point, proof) = proof.decode()?;
+        if pp.opening_key().check(point, proof) {
+            println!("proof #{}\t✅", i);
+        } else {
+            println!("proof #{}\t❌", i);
+            valid = false;
+        }
+    }
+
+    if !valid {
+        println!("invalid proof found\t❌");
+        process::exit(1);
+    }
+    Ok(())
+}
+
+fn batch_verify(pp_path: &str, proof_paths: &[&str]) -> std::io::Result<()> {
+    let pp = open_public_parameters(pp_path)?;
+
+    let mut points: Vec<BlsScalar> = Vec::new();
+    let mut proofs = Vec::new();
+    for path in proof_paths {
+        for proof in OpeningProof::open_all(path)? {
+            let (point, proof) = proof.decode()?;
+            points.push(point);
+            proofs.push(proof);
+        }
+    }
+
+    match pp
+        .opening_key()
+        .batch_check(&points, &proofs, &mut Transcript::new(b"srs-kzg-batch"))
+    {
+        Ok(()) => println!("batch of {} proofs\t✅", proofs.len()),
+        Err(e) => {
+            println!("batch of {} proofs is invalid: {:?}\t❌", proofs.len(), e);
+            process::exit(1);
+        }
+    }
+    Ok(())
+}
+
+/// Polynomial of higher degree than reference string supports.
+fn invalid(message: String) -> io::Error {
+    io::Error::new(io::ErrorKind::InvalidInput, message)
+}
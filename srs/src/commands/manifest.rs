@@ -0,0 +1,84 @@
+use ed25519_dalek::SigningKey;
+use srs::signed::{self, SignedManifest, TrustedKeys};
+use std::fs::{self, OpenOptions};
+use std::io::Write;
+
+const USAGE: &str = "invoke program using `cargo run manifest keygen <path-to-secret-key>`, \
+`cargo run manifest sign <path-to-manifest.json> <path-to-secret-key> <path-to-signed-manifest.json>` or \
+`cargo run manifest verify <path-to-signed-manifest.json> <path-to-trusted-keys.json> [<path-to-artifact>...]`";
+
+/// `srs manifest keygen|sign|verify ...`
+pub fn run(args: &[String]) -> std::io::Result<()> {
+    match args.first().map(String::as_str) {
+        Some("keygen") if args.len() == 2 => keygen(&args[1]),
+        Some("sign") if args.len() == 4 => sign(&args[1], &args[2], &args[3]),
+        Some("verify") if args.len() >= 3 => verify(&args[1], &args[2], &args[3..]),
+        _ => panic!("{}", USAGE),
+    }
+}
+
+fn keygen(secret_path: &str) -> std::io::Result<()> {
+    let key = SigningKey::generate(&mut rand::rngs::OsRng);
+    // never overwrite an existing key, & keep new one readable by owner only
+    let mut options = OpenOptions::new();
+    options.write(true).create_new(true);
+    #[cfg(unix)]
+    {
+        use std::os::unix::fs::OpenOptionsExt;
+        options.mode(0o600);
+    }
+    let mut file = options.open(secret_path)?;
+    writeln!(file, "{}", hex::encode(key.to_bytes()))?;
+    println!("wrote secret key into `{}`\t✅", secret_path);
+    println!(
+        "public key: {}",
+        hex::encode(key.verifying_key().to_bytes())
+    );
+    Ok(())
+}
+
+fn sign(manifest_path: &str, secret_path: &str, out: &str) -> std::io::Result<()> {
+    let mut signed = SignedManifest::open(manifest_path)?;
+    let key = signed::open_signing_key(secret_path)?;
+    signed.sign(&key)?;
+    signed.write(out)?;
+    println!(
+        "signed as {}, manifest now carries {} signatures, written into `{}`\t✅",
+        hex::encode(key.verifying_key().to_bytes()),
+        signed.signatures.len(),
+        out
+    );
+    Ok(())
+}
+
+fn verify(signed_path: &str, trusted_path: &str, artifacts: &[String]) -> std::io::Result<()> {
+    let signed = SignedManifest::open(signed_path)?;
+    let trusted = TrustedKeys::open(trusted_path)?;
+
+    let verification = signed.check(&trusted)?;
+    for name in verification.valid.iter() {
+        println!("{}\t✅", name);
+    }
+    for name in verification.invalid.iter() {
+        println!("{}\t❌ invalid signature", name);
+    }
+    for key in verification.untrusted.iter() {
+        println!("{}\tuntrusted, ignored", key);
+    }
+    signed.verify(&trusted)?;
+    println!(
+        "signed by {} of required {} trusted keys\t[VERIFIED]",
+        verification.valid.len(),
+        trusted.threshold
+    );
+
+    for path in artifacts {
+        let name = std::path::Path::new(path)
+            .file_name()
+            .and_then(|n| n.to_str())
+            .unwrap_or(path);
+        signed.accept(&trusted, name, &fs::read(path)?)?;
+        println!("`{}` matches signed manifest\t[VERIFIED]", path);
+    }
+    Ok(())
+}
@@ -0,0 +1,92 @@
+use srs::manifest::Manifest;
+use srs::merkle::{self, RangeProof};
+use std::fs;
+
+const USAGE: &str = "invoke program using `cargo run merkle root <path-to-pp.data>`, \
+`cargo run merkle prove <path-to-pp.data> <degree> <path-to-proof.json>`, \
+`cargo run merkle prove-range <path-to-pp.data> <start> <end> <path-to-proof.json>` or \
+`cargo run merkle verify <path-to-manifest.json> <path-to-proof.json> <path-to-points>`";
+
+/// `srs merkle root|prove|prove-range|verify ...`
+pub fn run(args: &[String]) -> std::io::Result<()> {
+    match args.first().map(String::as_str) {
+        Some("root") if args.len() == 2 => root(&args[1]),
+        Some("prove") if args.len() == 4 => {
+            let degree = parse(&args[2]);
+            prove(&args[1], merkle::degree_range(degree), &args[3])
+        }
+        Some("prove-range") if args.len() == 5 => {
+            let (start, end) = (parse(&args[2]), parse(&args[3]));
+            prove(&args[1], start..end, &args[4])
+        }
+        Some("verify") if args.len() == 4 => verify(&args[1], &args[2], &args[3]),
+        _ => panic!("{}", USAGE),
+    }
+}
+
+fn parse(arg: &str) -> usize {
+    arg.parse()
+        .unwrap_or_else(|_| panic!("expected a non-negative integer, found `{}`", arg))
+}
+
+fn root(pp_path: &str) -> std::io::Result<()> {
+    let tree = merkle::tree(&fs::read(pp_path)?)?;
+    println!("root: {}", hex::encode(tree.root()));
+    println!("leaves: {}", tree.leaves());
+    Ok(())
+}
+
+fn prove(pp_path: &str, range: std::ops::Range<usize>, proof_path: &str) -> std::io::Result<()> {
+    let tree = merkle::tree(&fs::read(pp_path)?)?;
+    let proof = tree.prove(range.clone()).map_err(|e| {
+        std::io::Error::new(
+            e.kind(),
+            format!(
+                "{}, `{}` covers degree ≤ {}",
+                e,
+                pp_path,
+                tree.leaves().saturating_sub(merkle::OPENING_KEY_LEAVES + 1)
+            ),
+        )
+    })?;
+    fs::write(proof_path, serde_json::to_vec_pretty(&proof)?)?;
+
+    println!(
+        "proved leaves {}..{}, i.e. bytes {}..{} of `{}`, using {} hashes into `{}`\t✅",
+        range.start,
+        range.end,
+        merkle::prefix_len(range.start),
+        merkle::prefix_len(range.end),
+        pp_path,
+        proof.hashes.len(),
+        proof_path
+    );
+    Ok(())
+}
+
+fn verify(manifest_path: &str, proof_path: &str, points_path: &str) -> std::io::Result<()> {
+    let manifest = Manifest::open(manifest_path)?;
+    let commitment = manifest.merkle.unwrap_or_else(|| {
+        panic!("`{}` doesn't carry a merkle root", manifest_path);
+    });
+    let proof: RangeProof = serde_json::from_slice(&fs::read(proof_path)?)?;
+    let points = fs::read(points_path)?;
+
+    if proof.start == 0 {
+        let pp = merkle::load_prefix(&commitment, &proof, &points)?;
+        println!(
+            "public params of max degree: {}\t[VERIFIED]",
+            pp.max_degree()
+        );
+    } else {
+        commitment.verify(&proof, &merkle::split_leaves(proof.start, &points)?)?;
+        println!("leaves {}..{}\t[VERIFIED]", proof.start, proof.end);
+    }
+
+    println!(
+        "matches root {} of {} leaves\t✅",
+        hex::encode(commitment.root),
+        commitment.leaves
+    );
+    Ok(())
+}
@@ -0,0 +1,16 @@
+//! Subcommands of `srs`, each invoked as `srs <subcommand> [args]`.
+
+pub mod cache;
+pub mod convert;
+#[cfg(feature = "crosscheck")]
+pub mod crosscheck;
+pub mod diff;
+pub mod eip2537;
+pub mod fetch;
+pub mod inspect;
+pub mod kzg;
+pub mod manifest;
+pub mod merkle;
+pub mod scale;
+pub mod shard;
+pub mod vectors;
@@ -0,0 +1,60 @@
+use codec::Encode;
+use srs::artifact::Format;
+use srs::convert::{encode, open_public_parameters};
+use srs::scale::VersionedSrs;
+use std::fs;
+
+const USAGE: &str =
+    "invoke program using `cargo run scale export <path-to-pp> <path-to-output.scale>` \
+or `cargo run scale check <path-to-pp> <path-to-input.scale>`";
+
+/// `srs scale export|check ...`
+pub fn run(args: &[String]) -> std::io::Result<()> {
+    match args.first().map(String::as_str) {
+        Some("export") if args.len() == 3 => export(&args[1], &args[2]),
+        Some("check") if args.len() == 3 => check(&args[1], &args[2]),
+        _ => panic!("{}", USAGE),
+    }
+}
+
+fn export(pp_path: &str, out: &str) -> std::io::Result<()> {
+    let pp = open_public_parameters(pp_path)?;
+    let encoded = VersionedSrs::from_public_parameters(&pp).encode();
+
+    // decode once again, so that a faulty export never goes unnoticed
+    let decoded = VersionedSrs::decode_all(&encoded)?;
+    assert_eq!(
+        decoded.to_pp_bytes(),
+        encode(&pp, Format::Compressed)?,
+        "SCALE encoded SRS doesn't decode back into same points"
+    );
+
+    fs::write(out, &encoded)?;
+    println!(
+        "wrote SCALE encoded SRS of max degree {} into `{}`, {}b\t✅",
+        pp.max_degree(),
+        out,
+        encoded.len()
+    );
+    Ok(())
+}
+
+fn check(pp_path: &str, scale_path: &str) -> std::io::Result<()> {
+    let pp = open_public_parameters(pp_path)?;
+    let decoded = VersionedSrs::decode_all(&fs::read(scale_path)?)?;
+    let points = decoded.to_public_parameters()?;
+
+    assert_eq!(
+        points.to_var_bytes(),
+        pp.to_var_bytes(),
+        "`{}` doesn't hold same points as `{}`",
+        scale_path,
+        pp_path
+    );
+    println!(
+        "SCALE encoded SRS of max degree {} matches `{}`\t[VERIFIED]",
+        points.max_degree(),
+        pp_path
+    );
+    Ok(())
+}
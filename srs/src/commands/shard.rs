@@ -0,0 +1,81 @@
+use srs::manifest::sha256;
+use srs::shard::ShardIndex;
+use srs::signed::{self, SignedManifest, TrustedKeys};
+use std::fs;
+use std::path::Path;
+
+const USAGE: &str =
+    "invoke program using `cargo run shard split <path-to-pp.data> <shard-size> <output-dir>`, \
+`cargo run shard load <path-to-index.json> <degree> --sha256 <index-hash>` or \
+`cargo run shard load <path-to-index.json> <degree> --manifest <path-to-signed-manifest.json> <path-to-trusted-keys.json>`";
+
+/// `srs shard split|load ...`
+///
+/// `load` trusts index only once it matches a pinned hash, or a signed manifest lists it.
+pub fn run(args: &[String]) -> std::io::Result<()> {
+    let args: Vec<&str> = args.iter().map(String::as_str).collect();
+    match args.as_slice() {
+        ["split", pp, shard_size, out_dir] => split(pp, shard_size, out_dir),
+        ["load", index, degree, "--sha256", hash] => {
+            load(index, degree, ShardIndex::open_pinned(index, hash)?)
+        }
+        ["load", index, degree, "--manifest", signed, trusted] => {
+            let signed = SignedManifest::open(signed)?;
+            let trusted = TrustedKeys::open(trusted)?;
+            load(
+                index,
+                degree,
+                signed::open_shard_index(index, &signed, &trusted)?,
+            )
+        }
+        _ => panic!("{}", USAGE),
+    }
+}
+
+fn split(pp_path: &str, shard_size: &str, out_dir: &str) -> std::io::Result<()> {
+    let shard_size: usize = shard_size
+        .parse()
+        .expect("shard size must be a positive integer");
+    let (index, files) = ShardIndex::split(&fs::read(pp_path)?, shard_size)?;
+
+    let out_dir = Path::new(out_dir);
+    fs::create_dir_all(out_dir)?;
+    for (shard, content) in index.shards.iter().zip(files.iter()) {
+        fs::write(out_dir.join(&shard.file), content)?;
+        println!(
+            "powers {}..{}\t`{}`\t{}",
+            shard.start, shard.end, shard.file, shard.sha256
+        );
+    }
+
+    let index_name = format!("index_{}.json", index.points);
+    index.write(out_dir.join(&index_name))?;
+    println!(
+        "split {} powers into {} shards, indexed by `{}`\t✅",
+        index.points,
+        index.shards.len(),
+        out_dir.join(&index_name).display()
+    );
+    println!(
+        "index hash, to pin or list in manifest: {}",
+        sha256(&fs::read(out_dir.join(&index_name))?)
+    );
+    Ok(())
+}
+
+fn load(index_path: &str, degree: &str, index: ShardIndex) -> std::io::Result<()> {
+    let degree: usize = degree.parse().expect("degree must be a positive integer");
+    let dir = Path::new(index_path)
+        .parent()
+        .unwrap_or_else(|| Path::new("."));
+
+    for shard in index.shards_for(degree) {
+        println!("needs `{}`", shard.file);
+    }
+    let pp = index.load(dir, degree)?;
+    println!(
+        "public params of max degree: {}\t[VERIFIED]",
+        pp.max_degree()
+    );
+    Ok(())
+}
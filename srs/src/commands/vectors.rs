@@ -0,0 +1,49 @@
+use srs::convert::open_public_parameters;
+use srs::manifest::sha256;
+use srs::vectors::TestVector;
+use std::fs;
+
+const USAGE: &str = "invoke program using `cargo run vectors generate <path-to-pp> <seed> <rows> <cols> <path-to-vectors.json>` \
+or `cargo run vectors verify <path-to-pp> <path-to-vectors.json>`";
+
+/// `srs vectors generate|verify ...`
+pub fn run(args: &[String]) -> std::io::Result<()> {
+    match args.first().map(String::as_str) {
+        Some("generate") if args.len() == 6 => {
+            generate(&args[1], &args[2], &args[3], &args[4], &args[5])
+        }
+        Some("verify") if args.len() == 3 => verify(&args[1], &args[2]),
+        _ => panic!("{}", USAGE),
+    }
+}
+
+fn generate(pp_path: &str, seed: &str, rows: &str, cols: &str, out: &str) -> std::io::Result<()> {
+    let seed: u64 = seed.parse().expect("seed must be an unsigned integer");
+    let rows: usize = rows.parse().expect("rows must be a positive integer");
+    let cols: usize = cols.parse().expect("cols must be a power of two");
+
+    let pp = open_public_parameters(pp_path)?;
+    let vector = TestVector::generate(&pp, &sha256(&fs::read(pp_path)?), seed, rows, cols)?;
+    vector.write(out)?;
+    println!(
+        "wrote {} commitments & {} cell proofs into `{}`\t✅",
+        vector.commitments.len(),
+        vector.cells.len(),
+        out
+    );
+    Ok(())
+}
+
+fn verify(pp_path: &str, vectors_path: &str) -> std::io::Result<()> {
+    let pp = open_public_parameters(pp_path)?;
+    let vector = TestVector::open(vectors_path)?;
+    vector.verify(&pp, &sha256(&fs::read(pp_path)?))?;
+    println!(
+        "{} x {} matrix of seed {}, {} cell proofs\t[VERIFIED]",
+        vector.rows,
+        vector.cols,
+        vector.seed,
+        vector.cells.len()
+    );
+    Ok(())
+}
@@ -0,0 +1,161 @@
+//! Conversion between `pp_<N>.data`, `pp_raw_<N>.data` & `g1_g2_<N>.txt`, validating every point on the way.
+//!
+//! Text form holds tau powers ( G2, tau·G2 ) in g2, where `pp_*` formats hold ( h, beta_h ) of
+//! opening key, beta_h being beta in g2 of `extracted.data`. Neither can be derived from the
+//! other, so text form is only ever re-encoded as text, never converted to or from `pp_*`.
+
+use crate::artifact::{
+    invalid, Artifact, Encoded, Format, G1_RAW_SIZE, OPENING_KEY_SIZE, RAW_LENGTH_SIZE,
+};
+use crate::diff::Diff;
+use dusk_bytes::Serializable;
+use dusk_plonk::bls12_381::{G1Affine, G2Affine};
+use dusk_plonk::commitment_scheme::kzg10::PublicParameters;
+use std::io;
+use std::path::Path;
+
+/// Converts an artifact into another format. Output is decoded once again and compared
+/// against input, so that a faulty conversion never goes unnoticed.
+pub fn convert(input: &[u8], to: Format) -> io::Result<Vec<u8>> {
+    let source = Artifact::from_bytes(input)?;
+    let output = match (source.format, to) {
+        (Format::Text, Format::Text) => {
+            let g1s = points(&source.g1s, "g1")?;
+            let g2s = points(&source.g2s, "g2")?;
+            text(&g1s, &g2s)
+        }
+        (Format::Text, _) | (_, Format::Text) => return Err(unfaithful()),
+        _ => encode(&to_public_parameters(&source)?, to)?,
+    };
+
+    let converted = Artifact::from_bytes(&output)?;
+    if converted.format != to || !Diff::new(&source, &converted).same_points() {
+        return Err(invalid(
+            "converted artifact doesn't hold same points as source",
+        ));
+    }
+
+    Ok(output)
+}
+
+/// Reads public parameters from a compressed or raw artifact, checking every point. Text artifacts
+/// are refused, holding tau·G2 in place of beta_h.
+pub fn open_public_parameters<P: AsRef<Path>>(path: P) -> io::Result<PublicParameters> {
+    to_public_parameters(&Artifact::open(path)?)
+}
+
+/// Builds public parameters from an artifact, refusing it if any of its points is invalid.
+pub fn to_public_parameters(a: &Artifact) -> io::Result<PublicParameters> {
+    let opening_g =
+        match a.format {
+            Format::Compressed | Format::Raw => a.opening_g.as_ref(),
+            Format::Text => return Err(unfaithful()),
+            Format::Extracted => return Err(invalid(
+                "`extracted.data` must be serialised using `cargo run <path-to-extracted.data>`",
+            )),
+        };
+
+    if a.g1s.is_empty() || a.g2s.len() != 2 {
+        return Err(invalid(format!(
+            "expected non-empty g1s & exactly 2 g2s, found {} g1s, {} g2s",
+            a.g1s.len(),
+            a.g2s.len()
+        )));
+    }
+    if let Some((g1s, _)) = a.declared {
+        if g1s != a.g1s.len() {
+            return Err(invalid(format!(
+                "artifact declares {} g1s, but holds {}",
+                g1s,
+                a.g1s.len()
+            )));
+        }
+    }
+
+    let g = valid(opening_g.unwrap(), "opening key g", 0)?;
+    let h = valid(&a.g2s[0], "g2", 0)?;
+    let beta_h = valid(&a.g2s[1], "g2", 1)?;
+
+    let mut bytes =
+        Vec::with_capacity(OPENING_KEY_SIZE + RAW_LENGTH_SIZE + a.g1s.len() * G1_RAW_SIZE);
+    bytes.extend_from_slice(&g.to_bytes());
+    bytes.extend_from_slice(&h.to_bytes());
+    bytes.extend_from_slice(&beta_h.to_bytes());
+    bytes.extend_from_slice(&(a.g1s.len() as u64).to_ne_bytes());
+    for (i, p) in a.g1s.iter().enumerate() {
+        bytes.extend_from_slice(&valid(p, "g1", i)?.to_raw_bytes());
+    }
+
+    // safe, every point has been checked above
+    Ok(unsafe { PublicParameters::from_slice_unchecked(&bytes) })
+}
+
+/// Serialises public parameters into requested format.
+pub fn encode(pp: &PublicParameters, format: Format) -> io::Result<Vec<u8>> {
+    match format {
+        Format::Compressed => Ok(pp.to_var_bytes()),
+        Format::Raw => Ok(pp.to_raw_var_bytes()),
+        Format::Text => Err(unfaithful()),
+        Format::Extracted => Err(invalid(
+            "public parameters don't carry enough points for `extracted.data`",
+        )),
+    }
+}
+
+fn unfaithful() -> io::Error {
+    invalid(
+        "text form holds tau·G2 where `pp_*` formats hold beta_h, \
+         so it can't be converted faithfully to or from them",
+    )
+}
+
+/// Text form of points, as `main.rs` writes `g1_g2_<N>.txt`.
+fn text(g1s: &[G1Affine], g2s: &[G2Affine]) -> Vec<u8> {
+    let mut text = format!("{}\n{}\n", g1s.len(), g2s.len());
+    for p in g1s.iter() {
+        text.push_str(&hex::encode(p.to_bytes()));
+        text.push('\n');
+    }
+    for p in g2s.iter() {
+        text.push_str(&hex::encode(p.to_bytes()));
+        text.push('\n');
+    }
+    text.into_bytes()
+}
+
+fn points<P: Copy>(ps: &[Encoded<P>], what: &str) -> io::Result<Vec<P>> {
+    ps.iter()
+        .enumerate()
+        .map(|(i, p)| valid(p, what, i))
+        .collect()
+}
+
+fn valid<P: Copy>(p: &Encoded<P>, what: &str, index: usize) -> io::Result<P> {
+    p.point
+        .ok_or_else(|| invalid(format!("{} point #{} is invalid", what, index)))
+}
+
+#[test]
+fn test_convert_round_trips() {
+    use crate::artifact::test_artifacts;
+
+    let (_, compressed, text) = test_artifacts(8);
+
+    // compressed -> raw -> compressed
+    let raw = convert(&compressed, Format::Raw).unwrap();
+    assert_eq!(Format::detect(&raw), Some(Format::Raw));
+    assert_eq!(convert(&raw, Format::Compressed).unwrap(), compressed);
+    assert_eq!(convert(&raw, Format::Raw).unwrap(), raw);
+
+    // text -> text
+    assert_eq!(convert(&text, Format::Text).unwrap(), text);
+
+    // text holds tau·G2 where pp holds beta_h, so neither way is faithful
+    for source in [&compressed, &raw].iter() {
+        assert!(convert(source, Format::Text).is_err());
+    }
+    for to in [Format::Compressed, Format::Raw].iter() {
+        assert!(convert(&text, *to).is_err());
+    }
+    assert!(to_public_parameters(&Artifact::from_bytes(&text).unwrap()).is_err());
+}
@@ -0,0 +1,404 @@
+//! Cross-library consistency harness: same tau powers are loaded into dusk-plonk, plain arkworks
+//! & poly-multiproof, then same polynomials are committed to & opened at same points in each of
+//! them. Commitments & witnesses must be byte-identical ( compressed ) across libraries, which
+//! catches encoding flag & endianness mismatches before they reach consumers.
+//!
+//! Polynomials & points are derived from a seed, every scalar being `BlsScalar::from_bytes_wide`
+//! over next 64 bytes of `ChaCha20Rng::seed_from_u64(seed)` keystream. Scalars are handed to each
+//! library as 32 bytes little-endian, points as compressed bytes, so that each of them decodes
+//! both on its own.
+//!
+//! Available with `crosscheck` feature.
+
+use crate::artifact::{invalid, Artifact, G1_SIZE, G2_SIZE, OPENING_KEY_SIZE};
+use dusk_bytes::Serializable;
+use dusk_plonk::bls12_381::BlsScalar;
+use rand::{RngCore, SeedableRng};
+use rand_chacha::ChaCha20Rng;
+use std::fmt;
+use std::io;
+
+pub type Scalar = [u8; 32];
+
+/// Compressed tau powers, i.e. [τ^i]G for i in 0..=degree, along with [1]H & [τ]H.
+#[derive(Clone, Debug)]
+pub struct Powers {
+    pub g1s: Vec<[u8; G1_SIZE]>,
+    pub g2s: [[u8; G2_SIZE]; 2],
+}
+
+impl Powers {
+    /// Takes first `degree + 1` g1 powers & first two g2 points of artifact. For `pp_*` formats
+    /// those g2 points are ( h, beta_h ) of opening key.
+    pub fn from_artifact(a: &Artifact, degree: usize) -> io::Result<Powers> {
+        if a.g1s.len() <= degree || a.g2s.len() < 2 {
+            return Err(invalid(format!(
+                "need {} g1s & 2 g2s, artifact holds {} g1s, {} g2s",
+                degree + 1,
+                a.g1s.len(),
+                a.g2s.len()
+            )));
+        }
+
+        let g1s = a.g1s[..=degree]
+            .iter()
+            .enumerate()
+            .map(|(i, p)| {
+                p.point
+                    .map(|p| p.to_bytes())
+                    .ok_or_else(|| invalid(format!("g1 #{} isn't a valid point", i)))
+            })
+            .collect::<io::Result<Vec<_>>>()?;
+        let mut g2s = [[0u8; G2_SIZE]; 2];
+        for (i, p) in a.g2s[..2].iter().enumerate() {
+            g2s[i] = p
+                .point
+                .map(|p| p.to_bytes())
+                .ok_or_else(|| invalid(format!("g2 #{} isn't a valid point", i)))?;
+        }
+
+        Ok(Powers { g1s, g2s })
+    }
+
+    pub fn max_degree(&self) -> usize {
+        self.g1s.len() - 1
+    }
+}
+
+/// A library under test, committing to polynomials given by their coefficients ( lowest degree
+/// first ) & opening them at a point. Both return compressed g1 points.
+pub trait Backend {
+    fn name(&self) -> &'static str;
+
+    fn commit(&self, coeffs: &[Scalar]) -> io::Result<Vec<u8>>;
+
+    /// Commitment to witness polynomial ( f(X) - f(z) ) / ( X - z ).
+    fn open(&self, coeffs: &[Scalar], point: &Scalar) -> io::Result<Vec<u8>>;
+}
+
+/// Derives `count` polynomials of degree `degree`, each along with point it's opened at.
+pub fn polynomials(seed: u64, count: usize, degree: usize) -> Vec<(Vec<Scalar>, Scalar)> {
+    let mut rng = ChaCha20Rng::seed_from_u64(seed);
+    let mut buf = [0u8; 64];
+    let mut scalar = move || {
+        rng.fill_bytes(&mut buf);
+        BlsScalar::from_bytes_wide(&buf).to_bytes()
+    };
+
+    (0..count)
+        .map(|_| {
+            let coeffs = (0..=degree).map(|_| scalar()).collect();
+            (coeffs, scalar())
+        })
+        .collect()
+}
+
+/// Output of a backend which differs from that of reference backend.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct Mismatch {
+    pub backend: &'static str,
+    /// index of polynomial
+    pub polynomial: usize,
+    /// `commitment` or `proof`
+    pub what: &'static str,
+    pub expected: Vec<u8>,
+    pub found: Vec<u8>,
+}
+
+impl fmt::Display for Mismatch {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        write!(
+            f,
+            "{} {} of polynomial #{}: expected {}, found {}",
+            self.backend,
+            self.what,
+            self.polynomial,
+            hex::encode(&self.expected),
+            hex::encode(&self.found)
+        )
+    }
+}
+
+/// Runs every polynomial through all backends, comparing their outputs against first backend's.
+pub fn compare(
+    backends: &[&dyn Backend],
+    polys: &[(Vec<Scalar>, Scalar)],
+) -> io::Result<Vec<Mismatch>> {
+    let (reference, others) = backends
+        .split_first()
+        .ok_or_else(|| invalid("need at least one backend"))?;
+
+    let mut mismatches = Vec::new();
+    for (i, (coeffs, point)) in polys.iter().enumerate() {
+        let commitment = reference.commit(coeffs)?;
+        let proof = reference.open(coeffs, point)?;
+
+        for backend in others.iter() {
+            let outputs = [
+                ("commitment", &commitment, backend.commit(coeffs)?),
+                ("proof", &proof, backend.open(coeffs, point)?),
+            ];
+            for (what, expected, found) in outputs.iter() {
+                if *expected != found {
+                    mismatches.push(Mismatch {
+                        backend: backend.name(),
+                        polynomial: i,
+                        what: *what,
+                        expected: expected.to_vec(),
+                        found: found.clone(),
+                    });
+                }
+            }
+        }
+    }
+    Ok(mismatches)
+}
+
+/// dusk-plonk, i.e. what `pp_<N>.data` is read by.
+pub struct Dusk {
+    pp: dusk_plonk::commitment_scheme::kzg10::PublicParameters,
+}
+
+impl Dusk {
+    pub fn new(powers: &Powers) -> io::Result<Dusk> {
+        let mut bytes = Vec::with_capacity(OPENING_KEY_SIZE + powers.g1s.len() * G1_SIZE);
+        bytes.extend_from_slice(&powers.g1s[0]);
+        bytes.extend_from_slice(&powers.g2s[0]);
+        bytes.extend_from_slice(&powers.g2s[1]);
+        for p in powers.g1s.iter() {
+            bytes.extend_from_slice(p);
+        }
+
+        let pp = dusk_plonk::commitment_scheme::kzg10::PublicParameters::from_slice(&bytes)
+            .map_err(|e| invalid(format!("dusk-plonk rejects powers: {:?}", e)))?;
+        Ok(Dusk { pp })
+    }
+
+    fn polynomial(coeffs: &[Scalar]) -> io::Result<dusk_plonk::fft::Polynomial> {
+        let coeffs = coeffs
+            .iter()
+            .map(dusk_scalar)
+            .collect::<io::Result<Vec<_>>>()?;
+        Ok(dusk_plonk::fft::Polynomial::from_coefficients_vec(coeffs))
+    }
+}
+
+fn dusk_scalar(s: &Scalar) -> io::Result<BlsScalar> {
+    BlsScalar::from_bytes(s).map_err(|e| invalid(format!("dusk-plonk rejects scalar: {:?}", e)))
+}
+
+impl Backend for Dusk {
+    fn name(&self) -> &'static str {
+        "dusk-plonk"
+    }
+
+    fn commit(&self, coeffs: &[Scalar]) -> io::Result<Vec<u8>> {
+        let commitment = self
+            .pp
+            .commit_key()
+            .commit(&Dusk::polynomial(coeffs)?)
+            .map_err(|e| invalid(format!("dusk-plonk can't commit: {:?}", e)))?;
+        Ok(commitment.0.to_bytes().to_vec())
+    }
+
+    fn open(&self, coeffs: &[Scalar], point: &Scalar) -> io::Result<Vec<u8>> {
+        let poly = Dusk::polynomial(coeffs)?;
+        let point = dusk_scalar(point)?;
+        let proof = self
+            .pp
+            .commit_key()
+            .open_single(&poly, &poly.evaluate(&point), &point)
+            .map_err(|e| invalid(format!("dusk-plonk can't open: {:?}", e)))?;
+        Ok(proof.commitment_to_witness.0.to_bytes().to_vec())
+    }
+}
+
+fn ark_scalar(s: &Scalar) -> io::Result<ark_bls12_381::Fr> {
+    use ark_serialize::CanonicalDeserialize;
+
+    ark_bls12_381::Fr::deserialize_compressed(&s[..])
+        .map_err(|e| invalid(format!("arkworks rejects scalar: {:?}", e)))
+}
+
+fn ark_scalars(coeffs: &[Scalar]) -> io::Result<Vec<ark_bls12_381::Fr>> {
+    coeffs.iter().map(ark_scalar).collect()
+}
+
+fn ark_points<P: ark_serialize::CanonicalDeserialize>(
+    points: &[impl AsRef<[u8]>],
+) -> io::Result<Vec<P>> {
+    points
+        .iter()
+        .map(|p| {
+            P::deserialize_compressed(p.as_ref())
+                .map_err(|e| invalid(format!("arkworks rejects point: {:?}", e)))
+        })
+        .collect()
+}
+
+fn ark_compress<P: ark_serialize::CanonicalSerialize>(p: &P) -> io::Result<Vec<u8>> {
+    let mut bytes = Vec::new();
+    p.serialize_compressed(&mut bytes)
+        .map_err(|e| invalid(format!("arkworks can't serialise point: {:?}", e)))?;
+    Ok(bytes)
+}
+
+/// Plain arkworks, committing by multi-scalar multiplication over g1 powers, as arkworks based
+/// consumers do.
+pub struct Arkworks {
+    g1s: Vec<ark_bls12_381::G1Affine>,
+}
+
+impl Arkworks {
+    pub fn new(powers: &Powers) -> io::Result<Arkworks> {
+        Ok(Arkworks {
+            g1s: ark_points(&powers.g1s)?,
+        })
+    }
+
+    fn msm(&self, coeffs: &[ark_bls12_381::Fr]) -> io::Result<Vec<u8>> {
+        use ark_ec::{CurveGroup, VariableBaseMSM};
+
+        if coeffs.len() > self.g1s.len() {
+            return Err(invalid(format!(
+                "arkworks: {} coefficients, only {} powers",
+                coeffs.len(),
+                self.g1s.len()
+            )));
+        }
+        let p = ark_bls12_381::G1Projective::msm(&self.g1s[..coeffs.len()], coeffs)
+            .map_err(|_| invalid("arkworks: bases & scalars differ in length"))?;
+        ark_compress(&p.into_affine())
+    }
+}
+
+impl Backend for Arkworks {
+    fn name(&self) -> &'static str {
+        "arkworks"
+    }
+
+    fn commit(&self, coeffs: &[Scalar]) -> io::Result<Vec<u8>> {
+        self.msm(&ark_scalars(coeffs)?)
+    }
+
+    fn open(&self, coeffs: &[Scalar], point: &Scalar) -> io::Result<Vec<u8>> {
+        use ark_ff::Zero;
+
+        let coeffs = ark_scalars(coeffs)?;
+        let z = ark_scalar(point)?;
+
+        // synthetic division by ( X - z ), remainder being f(z)
+        let mut witness = vec![ark_bls12_381::Fr::zero(); coeffs.len().saturating_sub(1)];
+        let mut carry = ark_bls12_381::Fr::zero();
+        for i in (1..coeffs.len()).rev() {
+            carry = coeffs[i] + carry * z;
+            witness[i - 1] = carry;
+        }
+        self.msm(&witness)
+    }
+}
+
+/// poly-multiproof's method 1, opening a single polynomial at a single point, in which case its
+/// proof is plain KZG witness.
+pub struct PolyMultiproof {
+    inner: poly_multiproof::method1::M1NoPrecomp<ark_bls12_381::Bls12_381>,
+}
+
+impl PolyMultiproof {
+    pub fn new(powers: &Powers) -> io::Result<PolyMultiproof> {
+        let g1s: Vec<ark_bls12_381::G1Affine> = ark_points(&powers.g1s)?;
+        let g2s: Vec<ark_bls12_381::G2Affine> = ark_points(&powers.g2s)?;
+        Ok(PolyMultiproof {
+            inner: poly_multiproof::method1::M1NoPrecomp::new_from_powers(&g1s, &g2s),
+        })
+    }
+}
+
+impl Backend for PolyMultiproof {
+    fn name(&self) -> &'static str {
+        "poly-multiproof"
+    }
+
+    fn commit(&self, coeffs: &[Scalar]) -> io::Result<Vec<u8>> {
+        use poly_multiproof::traits::Committer;
+
+        let commitment = self
+            .inner
+            .commit(ark_scalars(coeffs)?)
+            .map_err(|e| invalid(format!("poly-multiproof can't commit: {:?}", e)))?;
+        ark_compress(&commitment.0)
+    }
+
+    fn open(&self, coeffs: &[Scalar], point: &Scalar) -> io::Result<Vec<u8>> {
+        use ark_ff::Zero;
+        use poly_multiproof::traits::PolyMultiProofNoPrecomp;
+
+        let coeffs = ark_scalars(coeffs)?;
+        let z = ark_scalar(point)?;
+        let eval = coeffs
+            .iter()
+            .rev()
+            .fold(ark_bls12_381::Fr::zero(), |acc, c| acc * z + c);
+
+        let mut transcript = merlin3::Transcript::new(b"srs-crosscheck");
+        let proof = self
+            .inner
+            .open(&mut transcript, &[vec![eval]], &[coeffs], &[z])
+            .map_err(|e| invalid(format!("poly-multiproof can't open: {:?}", e)))?;
+        ark_compress(&proof)
+    }
+}
+
+#[test]
+fn test_backends_agree() {
+    use dusk_plonk::commitment_scheme::kzg10::PublicParameters;
+    use rand::rngs::StdRng;
+
+    let mut rng = StdRng::seed_from_u64(34);
+    let pp = PublicParameters::setup(1 << 5, &mut rng).unwrap();
+    let artifact = Artifact::from_bytes(&pp.to_var_bytes()).unwrap();
+    let powers = Powers::from_artifact(&artifact, 1 << 5).unwrap();
+
+    let dusk = Dusk::new(&powers).unwrap();
+    let ark = Arkworks::new(&powers).unwrap();
+    let pmp = PolyMultiproof::new(&powers).unwrap();
+    let backends: [&dyn Backend; 3] = [&dusk, &ark, &pmp];
+
+    let mut polys = polynomials(34, 8, 1 << 5);
+    polys.extend(polynomials(35, 4, 3));
+    assert_eq!(compare(&backends, &polys).unwrap(), vec![]);
+
+    // a library reading scalars big-endian, i.e. with opposite byte order, must be caught
+    struct BigEndian<'a>(&'a dyn Backend);
+    impl<'a> BigEndian<'a> {
+        fn reread(s: &Scalar) -> Scalar {
+            use ark_ff::PrimeField;
+            use ark_serialize::CanonicalSerialize;
+
+            let mut le = [0u8; 32];
+            ark_bls12_381::Fr::from_be_bytes_mod_order(s)
+                .serialize_compressed(&mut le[..])
+                .unwrap();
+            le
+        }
+    }
+    impl<'a> Backend for BigEndian<'a> {
+        fn name(&self) -> &'static str {
+            "big-endian"
+        }
+        fn commit(&self, coeffs: &[Scalar]) -> io::Result<Vec<u8>> {
+            let coeffs: Vec<Scalar> = coeffs.iter().map(BigEndian::reread).collect();
+            self.0.commit(&coeffs)
+        }
+        fn open(&self, coeffs: &[Scalar], point: &Scalar) -> io::Result<Vec<u8>> {
+            let coeffs: Vec<Scalar> = coeffs.iter().map(BigEndian::reread).collect();
+            self.0.open(&coeffs, &BigEndian::reread(point))
+        }
+    }
+    let big_endian = BigEndian(&ark);
+    let mismatches = compare(&[&dusk, &big_endian], &polys[..2]).unwrap();
+    assert_eq!(mismatches.len(), 4);
+    assert!(mismatches.iter().all(|m| m.backend == "big-endian"));
+    assert!(mismatches.iter().any(|m| m.what == "commitment"));
+    assert!(mismatches.iter().any(|m| m.what == "proof"));
+}
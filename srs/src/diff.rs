@@ -0,0 +1,275 @@
+//! Point-by-point comparison of two SRS artifacts, possibly of different formats.
+//!
+//! G2 points are compared by role rather than position, as `pp_*` formats hold ( h, beta_h ) of
+//! opening key, while `extracted.data` & text form hold tau powers in g2. h is tau^0 in g2, and
+//! beta_h is beta in g2 of `extracted.data`, which text form doesn't carry.
+
+use crate::artifact::{Artifact, Encoded, Format};
+use dusk_plonk::bls12_381::G2Affine;
+use std::fmt;
+
+#[derive(Copy, Clone, Debug, PartialEq, Eq)]
+pub enum PointDiff {
+    /// same bytes, hence same point
+    Identical,
+    /// same point, encoded using different bytes
+    EncodingOnly,
+    /// genuinely different point, or at least one side failed to decode
+    Different,
+}
+
+impl PointDiff {
+    fn of<P: PartialEq>(a: &Encoded<P>, b: &Encoded<P>) -> PointDiff {
+        match (&a.point, &b.point) {
+            (Some(pa), Some(pb)) if pa == pb => {
+                if a.bytes == b.bytes {
+                    PointDiff::Identical
+                } else {
+                    PointDiff::EncodingOnly
+                }
+            }
+            _ => PointDiff::Different,
+        }
+    }
+}
+
+impl fmt::Display for PointDiff {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        match self {
+            PointDiff::Identical => write!(f, "identical"),
+            PointDiff::EncodingOnly => write!(f, "encoding-only ( same point, different bytes )"),
+            PointDiff::Different => write!(f, "different point"),
+        }
+    }
+}
+
+/// Comparison of one group of points, over common prefix of both artifacts.
+#[derive(Clone, Debug, Default)]
+pub struct GroupDiff {
+    pub compared: usize,
+    /// index & kind of first point which isn't byte-identical
+    pub first: Option<(usize, PointDiff)>,
+    pub encoding_only: usize,
+    pub different: usize,
+}
+
+impl GroupDiff {
+    fn of<P: PartialEq>(a: &[Encoded<P>], b: &[Encoded<P>]) -> GroupDiff {
+        let mut diff = GroupDiff::default();
+
+        for (i, (pa, pb)) in a.iter().zip(b.iter()).enumerate() {
+            diff.compared += 1;
+            let kind = PointDiff::of(pa, pb);
+            match kind {
+                PointDiff::Identical => continue,
+                PointDiff::EncodingOnly => diff.encoding_only += 1,
+                PointDiff::Different => diff.different += 1,
+            }
+            if diff.first.is_none() {
+                diff.first = Some((i, kind));
+            }
+        }
+
+        diff
+    }
+
+    pub fn is_identical(&self) -> bool {
+        self.first.is_none()
+    }
+}
+
+/// A header field which holds different values in two artifacts.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct HeaderDiff {
+    pub field: &'static str,
+    pub left: String,
+    pub right: String,
+}
+
+#[derive(Clone, Debug)]
+pub struct Diff {
+    pub header: Vec<HeaderDiff>,
+    pub g1: GroupDiff,
+    /// tau powers in g2, where h of opening key is tau^0
+    pub g2: GroupDiff,
+    /// beta_h of opening key, `None` unless both artifacts carry it
+    pub beta_h: Option<PointDiff>,
+    /// comparisons which couldn't be made, as artifacts don't hold same kinds of points
+    pub skipped: Vec<String>,
+}
+
+/// G2 points of an artifact by role: tau powers in g2, of which `pp_*` formats hold only h, and
+/// beta_h.
+fn g2_roles(a: &Artifact) -> (&[Encoded<G2Affine>], Option<&Encoded<G2Affine>>) {
+    match a.format {
+        Format::Compressed | Format::Raw => (&a.g2s[..a.g2s.len().min(1)], a.g2s.get(1)),
+        Format::Extracted => (&a.g2s[..], a.beta_g2.as_ref()),
+        Format::Text => (&a.g2s[..], None),
+    }
+}
+
+impl Diff {
+    pub fn new(a: &Artifact, b: &Artifact) -> Diff {
+        let mut header = Vec::new();
+        let mut field = |field: &'static str, left: String, right: String| {
+            if left != right {
+                header.push(HeaderDiff { field, left, right });
+            }
+        };
+
+        field("format", a.format.to_string(), b.format.to_string());
+        field("size", a.len.to_string(), b.len.to_string());
+        field("hash", describe_hash(a), describe_hash(b));
+        field("declared", describe_declared(a), describe_declared(b));
+        field(
+            "g1 points",
+            a.g1s.len().to_string(),
+            b.g1s.len().to_string(),
+        );
+        field(
+            "g2 points",
+            a.g2s.len().to_string(),
+            b.g2s.len().to_string(),
+        );
+        field(
+            "opening key g",
+            describe_optional(&a.opening_g),
+            describe_optional(&b.opening_g),
+        );
+        field(
+            "beta in g2",
+            describe_optional(&a.beta_g2),
+            describe_optional(&b.beta_g2),
+        );
+
+        let (a_taus, a_beta_h) = g2_roles(a);
+        let (b_taus, b_beta_h) = g2_roles(b);
+
+        let mut skipped = Vec::new();
+        if a_taus.len().min(b_taus.len()) < a_taus.len().max(b_taus.len()) {
+            skipped.push(format!(
+                "tau powers in g2 past #{}, {} holds {} & {} holds {}",
+                a_taus.len().min(b_taus.len()),
+                a.format,
+                a_taus.len(),
+                b.format,
+                b_taus.len()
+            ));
+        }
+        let beta_h = match (a_beta_h, b_beta_h) {
+            (Some(pa), Some(pb)) => Some(PointDiff::of(pa, pb)),
+            (None, None) => None,
+            (Some(_), None) | (None, Some(_)) => {
+                skipped.push("beta_h, text form doesn't carry it".to_owned());
+                None
+            }
+        };
+
+        Diff {
+            header,
+            g1: GroupDiff::of(&a.g1s, &b.g1s),
+            g2: GroupDiff::of(a_taus, b_taus),
+            beta_h,
+            skipped,
+        }
+    }
+
+    /// Whether both artifacts hold same points, ignoring how they're encoded.
+    pub fn same_points(&self) -> bool {
+        self.g1.different == 0
+            && self.g2.different == 0
+            && self.beta_h != Some(PointDiff::Different)
+    }
+
+    pub fn is_identical(&self) -> bool {
+        self.header.is_empty()
+            && self.g1.is_identical()
+            && self.g2.is_identical()
+            && self.beta_h.map_or(true, |d| d == PointDiff::Identical)
+    }
+}
+
+impl fmt::Display for Diff {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        if self.header.is_empty() {
+            writeln!(f, "header\tno differences")?;
+        }
+        for h in self.header.iter() {
+            writeln!(f, "header\t{}: {} <> {}", h.field, h.left, h.right)?;
+        }
+
+        for (name, group) in [("g1", &self.g1), ("g2", &self.g2)].iter() {
+            match group.first {
+                Some((index, kind)) => writeln!(
+                    f,
+                    "{}\tfirst difference at #{}: {}\t( compared {}, encoding-only {}, different {} )",
+                    name, index, kind, group.compared, group.encoding_only, group.different
+                )?,
+                None => writeln!(f, "{}\tno differences in {} points", name, group.compared)?,
+            }
+        }
+        if let Some(kind) = self.beta_h {
+            writeln!(f, "beta_h\t{}", kind)?;
+        }
+        for s in self.skipped.iter() {
+            writeln!(f, "skipped\t{}", s)?;
+        }
+
+        Ok(())
+    }
+}
+
+fn describe_hash(a: &Artifact) -> String {
+    a.hash.map_or("-".to_owned(), hex::encode)
+}
+
+fn describe_declared(a: &Artifact) -> String {
+    a.declared
+        .map_or("-".to_owned(), |(g1, g2)| format!("{} g1, {} g2", g1, g2))
+}
+
+fn describe_optional<P: fmt::Debug>(p: &Option<Encoded<P>>) -> String {
+    match p {
+        Some(Encoded {
+            point: Some(point), ..
+        }) => format!("{:?}", point),
+        Some(Encoded { bytes, .. }) => format!("undecodable {}", hex::encode(bytes)),
+        None => "-".to_owned(),
+    }
+}
+
+#[test]
+fn test_diff_across_formats() {
+    use crate::artifact::test_artifacts;
+
+    let (extracted, compressed, text) = test_artifacts(8);
+    let extracted = Artifact::from_bytes(&extracted).unwrap();
+    let pp = Artifact::from_bytes(&compressed).unwrap();
+    let text = Artifact::from_bytes(&text).unwrap();
+
+    let same = Diff::new(&pp, &pp);
+    assert!(same.is_identical());
+    assert_eq!(same.beta_h, Some(PointDiff::Identical));
+
+    // pp derived from `extracted.data` agrees with it on h & beta_h
+    let diff = Diff::new(&pp, &extracted);
+    assert!(diff.same_points(), "{}", diff);
+    assert_eq!(diff.g2.compared, 1);
+    assert_eq!(diff.beta_h, Some(PointDiff::Identical));
+    assert_eq!(diff.skipped.len(), 1);
+
+    // text form doesn't carry beta_h
+    let diff = Diff::new(&pp, &text);
+    assert!(diff.same_points(), "{}", diff);
+    assert_eq!(diff.beta_h, None);
+    assert_eq!(diff.skipped.len(), 2);
+
+    assert!(Diff::new(&extracted, &text).same_points());
+
+    // pp whose beta_h is tau in g2 instead
+    let mut forged = pp.clone();
+    forged.g2s[1] = extracted.g2s[1].clone();
+    let diff = Diff::new(&forged, &extracted);
+    assert!(!diff.same_points());
+    assert_eq!(diff.beta_h, Some(PointDiff::Different));
+}
@@ -0,0 +1,339 @@
+//! EIP-2537 encodings of opening key & proofs, along with a Solidity KZG verifier using BLS12-381
+//! precompiles, for checking Avail data attestations on Ethereum.
+//!
+//! EIP-2537 encodes a base field element as 64 bytes big-endian, top 16 bytes being zero. G1
+//! point is x || y ( 128 bytes ), G2 point is x.c0 || x.c1 || y.c0 || y.c1 ( 256 bytes ), point
+//! at infinity being all zeros. Scalars are 32 bytes big-endian, i.e. `uint256`.
+//!
+//! Proof of p(z) = y, committed to by C & witnessed by π, is checked as
+//!
+//! e(C - y·G + z·π, H) · e(π, -βH) = 1
+//!
+//! where ( G, H, βH ) is opening key: C - y·G + z·π is computed by G1 MSM precompile, while
+//! -βH is embedded in verifier, so that contract never negates a point.
+
+use crate::artifact::{invalid, G1_SIZE, G2_SIZE, OPENING_KEY_SIZE};
+use dusk_bytes::Serializable;
+use dusk_plonk::bls12_381::{
+    multi_miller_loop, BlsScalar, G1Affine, G1Projective, G2Affine, G2Prepared, Gt,
+};
+use dusk_plonk::commitment_scheme::kzg10::PublicParameters;
+use std::convert::TryInto;
+use std::io;
+
+/// base field element, padded
+pub const FP_SIZE: usize = 64;
+pub const G1_POINT_SIZE: usize = 2 * FP_SIZE;
+pub const G2_POINT_SIZE: usize = 4 * FP_SIZE;
+pub const SCALAR_SIZE: usize = 32;
+
+/// precompile addresses, as in EIP-2537
+pub const G1_MSM_ADDRESS: u8 = 0x0c;
+pub const PAIRING_CHECK_ADDRESS: u8 = 0x0f;
+
+// unpadded base field element, as in `to_uncompressed`
+const FP_RAW_SIZE: usize = 48;
+const FP_PADDING: usize = FP_SIZE - FP_RAW_SIZE;
+
+fn pad(fp: &[u8], out: &mut [u8]) {
+    out[FP_PADDING..FP_SIZE].copy_from_slice(fp);
+}
+
+fn unpad(fp: &[u8]) -> io::Result<&[u8]> {
+    if fp[..FP_PADDING].iter().any(|b| *b != 0) {
+        return Err(invalid("top 16 bytes of a base field element must be zero"));
+    }
+    Ok(&fp[FP_PADDING..])
+}
+
+pub fn g1_to_eip2537(p: &G1Affine) -> [u8; G1_POINT_SIZE] {
+    let mut out = [0u8; G1_POINT_SIZE];
+    if bool::from(p.is_identity()) {
+        return out;
+    }
+
+    // x || y, flags being unset for a finite point
+    let raw = p.to_uncompressed();
+    pad(&raw[..FP_RAW_SIZE], &mut out[..FP_SIZE]);
+    pad(&raw[FP_RAW_SIZE..], &mut out[FP_SIZE..]);
+    out
+}
+
+pub fn g1_from_eip2537(bytes: &[u8]) -> io::Result<G1Affine> {
+    if bytes.len() != G1_POINT_SIZE {
+        return Err(invalid(format!("g1 point must be {} bytes", G1_POINT_SIZE)));
+    }
+    if bytes.iter().all(|b| *b == 0) {
+        return Ok(G1Affine::identity());
+    }
+
+    let mut raw = [0u8; 2 * FP_RAW_SIZE];
+    raw[..FP_RAW_SIZE].copy_from_slice(unpad(&bytes[..FP_SIZE])?);
+    raw[FP_RAW_SIZE..].copy_from_slice(unpad(&bytes[FP_SIZE..])?);
+    Option::from(G1Affine::from_uncompressed(&raw))
+        .ok_or_else(|| invalid("not a g1 point in prime order subgroup"))
+}
+
+pub fn g2_to_eip2537(p: &G2Affine) -> [u8; G2_POINT_SIZE] {
+    let mut out = [0u8; G2_POINT_SIZE];
+    if bool::from(p.is_identity()) {
+        return out;
+    }
+
+    // x.c1 || x.c0 || y.c1 || y.c0, while EIP-2537 puts c0 first
+    let raw = p.to_uncompressed();
+    for (i, c) in [1, 0, 3, 2].iter().enumerate() {
+        pad(
+            &raw[(i * FP_RAW_SIZE)..((i + 1) * FP_RAW_SIZE)],
+            &mut out[(c * FP_SIZE)..((c + 1) * FP_SIZE)],
+        );
+    }
+    out
+}
+
+pub fn g2_from_eip2537(bytes: &[u8]) -> io::Result<G2Affine> {
+    if bytes.len() != G2_POINT_SIZE {
+        return Err(invalid(format!("g2 point must be {} bytes", G2_POINT_SIZE)));
+    }
+    if bytes.iter().all(|b| *b == 0) {
+        return Ok(G2Affine::identity());
+    }
+
+    let mut raw = [0u8; 4 * FP_RAW_SIZE];
+    for (i, c) in [1, 0, 3, 2].iter().enumerate() {
+        raw[(i * FP_RAW_SIZE)..((i + 1) * FP_RAW_SIZE)]
+            .copy_from_slice(unpad(&bytes[(c * FP_SIZE)..((c + 1) * FP_SIZE)])?);
+    }
+    Option::from(G2Affine::from_uncompressed(&raw))
+        .ok_or_else(|| invalid("not a g2 point in prime order subgroup"))
+}
+
+/// Scalar as `uint256`, i.e. big-endian.
+pub fn scalar_to_eip2537(s: &BlsScalar) -> [u8; SCALAR_SIZE] {
+    let mut out = s.to_bytes();
+    out.reverse();
+    out
+}
+
+pub fn scalar_from_eip2537(bytes: &[u8]) -> io::Result<BlsScalar> {
+    let mut le: [u8; SCALAR_SIZE] = bytes
+        .try_into()
+        .map_err(|_| invalid(format!("scalar must be {} bytes", SCALAR_SIZE)))?;
+    le.reverse();
+    BlsScalar::from_bytes(&le).map_err(|e| invalid(format!("scalar isn't canonical: {:?}", e)))
+}
+
+/// Opening key points, decoded from its 240 bytes compressed form.
+#[derive(Copy, Clone, Debug)]
+pub struct VerifierKey {
+    pub g: G1Affine,
+    pub h: G2Affine,
+    pub beta_h: G2Affine,
+}
+
+impl VerifierKey {
+    pub fn from_opening_key_bytes(bytes: &[u8]) -> io::Result<VerifierKey> {
+        if bytes.len() != OPENING_KEY_SIZE {
+            return Err(invalid(format!(
+                "opening key must be {} bytes",
+                OPENING_KEY_SIZE
+            )));
+        }
+
+        let g = G1Affine::from_bytes(bytes[..G1_SIZE].try_into().unwrap())
+            .map_err(|e| invalid(format!("opening key's g is invalid: {:?}", e)))?;
+        let h = G2Affine::from_bytes(bytes[G1_SIZE..(G1_SIZE + G2_SIZE)].try_into().unwrap())
+            .map_err(|e| invalid(format!("opening key's h is invalid: {:?}", e)))?;
+        let beta_h = G2Affine::from_bytes(bytes[(G1_SIZE + G2_SIZE)..].try_into().unwrap())
+            .map_err(|e| invalid(format!("opening key's beta_h is invalid: {:?}", e)))?;
+        Ok(VerifierKey { g, h, beta_h })
+    }
+
+    pub fn from_public_parameters(pp: &PublicParameters) -> io::Result<VerifierKey> {
+        VerifierKey::from_opening_key_bytes(&pp.opening_key().to_bytes())
+    }
+
+    /// Input of G1 MSM precompile, computing C - y·G + z·π.
+    pub fn msm_input(
+        &self,
+        commitment: &G1Affine,
+        point: &BlsScalar,
+        value: &BlsScalar,
+        witness: &G1Affine,
+    ) -> Vec<u8> {
+        let pairs = [
+            (*commitment, BlsScalar::one()),
+            (self.g, -value),
+            (*witness, *point),
+        ];
+
+        let mut input = Vec::with_capacity(pairs.len() * (G1_POINT_SIZE + SCALAR_SIZE));
+        for (p, s) in pairs.iter() {
+            input.extend_from_slice(&g1_to_eip2537(p));
+            input.extend_from_slice(&scalar_to_eip2537(s));
+        }
+        input
+    }
+
+    /// Input of pairing check precompile, given output of G1 MSM precompile.
+    pub fn pairing_input(&self, msm_output: &[u8], witness: &G1Affine) -> Vec<u8> {
+        let mut input = Vec::with_capacity(2 * (G1_POINT_SIZE + G2_POINT_SIZE));
+        input.extend_from_slice(msm_output);
+        input.extend_from_slice(&g2_to_eip2537(&self.h));
+        input.extend_from_slice(&g1_to_eip2537(witness));
+        input.extend_from_slice(&g2_to_eip2537(&-self.beta_h));
+        input
+    }
+
+    /// Solidity contract verifying KZG proofs against this key, via EIP-2537 precompiles.
+    pub fn solidity(&self) -> String {
+        SOLIDITY_TEMPLATE
+            .replace("{{G}}", &hex::encode(g1_to_eip2537(&self.g)))
+            .replace("{{H}}", &hex::encode(g2_to_eip2537(&self.h)))
+            .replace("{{NEG_BETA_H}}", &hex::encode(g2_to_eip2537(&-self.beta_h)))
+    }
+}
+
+/// What G1 MSM precompile computes, for checking inputs off-chain.
+pub fn g1_msm(input: &[u8]) -> io::Result<[u8; G1_POINT_SIZE]> {
+    let pair = G1_POINT_SIZE + SCALAR_SIZE;
+    if input.is_empty() || input.len() % pair != 0 {
+        return Err(invalid(format!(
+            "input must be a non-empty multiple of {} bytes",
+            pair
+        )));
+    }
+
+    let mut acc = G1Projective::identity();
+    for chunk in input.chunks(pair) {
+        let p = g1_from_eip2537(&chunk[..G1_POINT_SIZE])?;
+        let s = scalar_from_eip2537(&chunk[G1_POINT_SIZE..])?;
+        acc += G1Projective::from(p) * s;
+    }
+    Ok(g1_to_eip2537(&G1Affine::from(acc)))
+}
+
+/// What pairing check precompile computes, for checking inputs off-chain.
+pub fn pairing_check(input: &[u8]) -> io::Result<bool> {
+    let pair = G1_POINT_SIZE + G2_POINT_SIZE;
+    if input.is_empty() || input.len() % pair != 0 {
+        return Err(invalid(format!(
+            "input must be a non-empty multiple of {} bytes",
+            pair
+        )));
+    }
+
+    let mut pairs = Vec::with_capacity(input.len() / pair);
+    for chunk in input.chunks(pair) {
+        pairs.push((
+            g1_from_eip2537(&chunk[..G1_POINT_SIZE])?,
+            G2Prepared::from(g2_from_eip2537(&chunk[G1_POINT_SIZE..])?),
+        ));
+    }
+    let terms: Vec<(&G1Affine, &G2Prepared)> = pairs.iter().map(|(a, b)| (a, b)).collect();
+    Ok(multi_miller_loop(&terms).final_exponentiation() == Gt::identity())
+}
+
+const SOLIDITY_TEMPLATE: &str = r#"// SPDX-License-Identifier: Apache-2.0
+// Generated by `srs eip2537 solidity`, don't edit by hand.
+pragma solidity ^0.8.24;
+
+/// @notice Verifies KZG opening proofs against Avail's BLS12-381 reference string, using
+/// EIP-2537 precompiles. Points are EIP-2537 encoded, i.e. 128 bytes uncompressed g1 points.
+contract KzgVerifier {
+    /// @dev order of BLS12-381 scalar field
+    uint256 internal constant R = 0x73eda753299d7d483339d80809a1d80553bda402fffe5bfeffffffff00000001;
+
+    address internal constant G1_MSM = address(0x0c);
+    address internal constant PAIRING_CHECK = address(0x0f);
+
+    /// @dev opening key: g, h & negated beta_h
+    bytes internal constant G = hex"{{G}}";
+    bytes internal constant H = hex"{{H}}";
+    bytes internal constant NEG_BETA_H = hex"{{NEG_BETA_H}}";
+
+    /// @notice Checks that polynomial committed to by `commitment` evaluates to `value` at
+    /// `point`, as witnessed by `witness`.
+    function verify(bytes calldata commitment, uint256 point, uint256 value, bytes calldata witness)
+        external
+        view
+        returns (bool)
+    {
+        require(commitment.length == 128 && witness.length == 128, "points must be 128 bytes");
+        require(point < R && value < R, "scalars must be less than r");
+
+        // commitment - value * g + point * witness
+        bytes memory msmInput =
+            abi.encodePacked(commitment, uint256(1), G, value == 0 ? 0 : R - value, witness, point);
+        (bool success, bytes memory lhs) = G1_MSM.staticcall(msmInput);
+        require(success && lhs.length == 128, "g1 msm failed");
+
+        // e(lhs, h) * e(witness, -beta_h) == 1
+        bytes memory pairingInput = abi.encodePacked(lhs, H, witness, NEG_BETA_H);
+        bytes memory result;
+        (success, result) = PAIRING_CHECK.staticcall(pairingInput);
+        return success && result.length == 32 && uint256(bytes32(result)) == 1;
+    }
+}
+"#;
+
+#[test]
+fn test_precompile_inputs_verify() {
+    use rand::rngs::StdRng;
+    use rand::SeedableRng;
+
+    let pp = PublicParameters::setup(1023, &mut StdRng::seed_from_u64(40)).unwrap();
+    check_precompile_inputs(&pp);
+}
+
+/// Same over published `pp_1024.data`, from crate directory if it's been produced there, else
+/// fetched into a temporary cache; either way it must match pinned hash.
+#[test]
+#[ignore]
+fn test_precompile_inputs_verify_published() {
+    use crate::fetch::{Cache, Fetcher, Source};
+    use std::path::Path;
+
+    let dir = Path::new(env!("CARGO_MANIFEST_DIR"));
+    let source = if dir.join("pp_1024.data").exists() {
+        Source::Dir(dir.to_path_buf())
+    } else {
+        Source::default()
+    };
+    let cache_dir = std::env::temp_dir().join(format!("srs_eip2537_{}", std::process::id()));
+    let pp = Fetcher::new(source, Cache::new(&cache_dir))
+        .public_parameters("pp_1024.data")
+        .expect("`pp_1024.data` is needed, produce it or make it fetchable");
+    let _ = std::fs::remove_dir_all(&cache_dir);
+    check_precompile_inputs(&pp);
+}
+
+#[cfg(test)]
+fn check_precompile_inputs(pp: &PublicParameters) {
+    use dusk_plonk::fft::Polynomial;
+
+    let key = VerifierKey::from_public_parameters(pp).unwrap();
+
+    let poly = Polynomial::from_coefficients_vec((0..256u64).map(BlsScalar::from).collect());
+    let point = BlsScalar::from(0xa7a1);
+    let value = poly.evaluate(&point);
+    let proof = pp.commit_key().open_single(&poly, &value, &point).unwrap();
+    let commitment = proof.commitment_to_polynomial.0;
+    let witness = proof.commitment_to_witness.0;
+    assert!(pp.opening_key().check(point, proof));
+
+    let msm_input = key.msm_input(&commitment, &point, &value, &witness);
+    assert_eq!(msm_input.len(), 3 * 160);
+    let pairing_input = key.pairing_input(&g1_msm(&msm_input).unwrap(), &witness);
+    assert_eq!(pairing_input.len(), 2 * 384);
+    assert!(pairing_check(&pairing_input).unwrap());
+
+    let forged = key.msm_input(&commitment, &point, &(value + BlsScalar::one()), &witness);
+    let pairing_input = key.pairing_input(&g1_msm(&forged).unwrap(), &witness);
+    assert!(!pairing_check(&pairing_input).unwrap());
+
+    assert_eq!(g1_from_eip2537(&g1_to_eip2537(&witness)).unwrap(), witness);
+    assert_eq!(g2_from_eip2537(&g2_to_eip2537(&key.h)).unwrap(), key.h);
+    assert_eq!(g1_to_eip2537(&G1Affine::identity()), [0u8; G1_POINT_SIZE]);
+    assert!(key.solidity().contains(&hex::encode(g1_to_eip2537(&key.g))));
+}
@@ -0,0 +1,162 @@
+//! Fetches published artifacts, either over HTTP(S) or from a local directory, checks each of them
+//! against SHA256 hashes pinned in this crate & keeps those which match in a content-addressed
+//! cache, so that they're never downloaded twice.
+//!
+//! Cache holds one file per artifact, named after hex encoded SHA256 of its content, see `cache`.
+//! Cached files are hashed again when read, so a corrupted cache entry is refetched, never used.
+
+use crate::artifact::invalid;
+pub use crate::cache::Cache;
+use crate::manifest::sha256;
+use dusk_plonk::commitment_scheme::kzg10::PublicParameters;
+use std::collections::BTreeMap;
+use std::fs;
+use std::io::{self, Read};
+use std::path::PathBuf;
+
+/// Where artifacts are published.
+pub const DEFAULT_SOURCE: &str = "https://srs.availproject.org";
+
+/// SHA256 hashes of published artifacts, same as in README. `g1_g2_1024.txt` isn't pinned, as
+/// hash listed for it duplicates that of `extracted.data`.
+pub const PINNED: &[(&str, &str)] = &[
+    (
+        "extracted.data",
+        "942d0579b83c70dcec7eec2075ff5a13ff7d72a99c21bbcb96a4a1c1865d71fd",
+    ),
+    (
+        "pp_1024.data",
+        "6f2a6fc74dd09fb70969a0843ca9fa971c26f224cb2bf11ce18d3c9c2b385a84",
+    ),
+];
+
+/// Pinned hash of an artifact, if there's one.
+pub fn pinned(name: &str) -> Option<&'static str> {
+    PINNED.iter().find(|(n, _)| *n == name).map(|(_, h)| *h)
+}
+
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub enum Source {
+    /// base URL, artifacts being at `<base>/<name>`
+    Http(String),
+    /// directory holding artifacts
+    Dir(PathBuf),
+}
+
+impl Source {
+    /// URLs start with `http://` or `https://`, anything else is taken as a directory.
+    pub fn parse(s: &str) -> Source {
+        if s.starts_with("http://") || s.starts_with("https://") {
+            Source::Http(s.trim_end_matches('/').to_owned())
+        } else {
+            Source::Dir(PathBuf::from(s))
+        }
+    }
+
+    pub fn read(&self, name: &str) -> io::Result<Vec<u8>> {
+        match self {
+            Source::Http(base) => {
+                let url = format!("{}/{}", base, name);
+                let response = ureq::get(&url)
+                    .call()
+                    .map_err(|e| io::Error::new(io::ErrorKind::Other, format!("{}: {}", url, e)))?;
+
+                let mut bytes = Vec::new();
+                response.into_reader().read_to_end(&mut bytes)?;
+                Ok(bytes)
+            }
+            Source::Dir(dir) => fs::read(dir.join(name)),
+        }
+    }
+}
+
+impl Default for Source {
+    fn default() -> Source {
+        Source::Http(DEFAULT_SOURCE.to_owned())
+    }
+}
+
+/// Fetches artifacts from a source, refusing any which doesn't match its pinned hash.
+pub struct Fetcher {
+    pub source: Source,
+    pub cache: Cache,
+    pins: BTreeMap<String, String>,
+}
+
+impl Fetcher {
+    /// Fetcher trusting hashes pinned in this crate.
+    pub fn new(source: Source, cache: Cache) -> Fetcher {
+        let pins = PINNED
+            .iter()
+            .map(|(n, h)| (n.to_string(), h.to_string()))
+            .collect();
+        Fetcher::with_pins(source, cache, pins)
+    }
+
+    /// Fetcher trusting given hashes instead, keyed by artifact name, e.g. those of a manifest.
+    pub fn with_pins(source: Source, cache: Cache, pins: BTreeMap<String, String>) -> Fetcher {
+        Fetcher {
+            source,
+            cache,
+            pins,
+        }
+    }
+
+    pub fn pins(&self) -> &BTreeMap<String, String> {
+        &self.pins
+    }
+
+    /// Fetches an artifact, from cache if it's there, else from source. Fetched content is
+    /// cached only after matching pinned hash.
+    pub fn fetch(&self, name: &str) -> io::Result<Vec<u8>> {
+        let hash = self
+            .pins
+            .get(name)
+            .ok_or_else(|| invalid(format!("no hash pinned for `{}`", name)))?;
+
+        if let Some(bytes) = self.cache.get(hash)? {
+            return Ok(bytes);
+        }
+
+        let bytes = self.source.read(name)?;
+        let found = sha256(&bytes);
+        if &found != hash {
+            return Err(invalid(format!(
+                "`{}` has sha256 {}, expected pinned {}",
+                name, found, hash
+            )));
+        }
+
+        self.cache.put(&bytes)?;
+        Ok(bytes)
+    }
+
+    /// Fetches & decodes `pp_<N>.data`.
+    pub fn public_parameters(&self, name: &str) -> io::Result<PublicParameters> {
+        PublicParameters::from_slice(&self.fetch(name)?)
+            .map_err(|e| invalid(format!("unable to decode public parameters: {:?}", e)))
+    }
+}
+
+impl Default for Fetcher {
+    fn default() -> Fetcher {
+        Fetcher::new(Source::default(), Cache::default())
+    }
+}
+
+#[test]
+fn test_pinned_hashes() {
+    assert_eq!(
+        pinned("pp_1024.data"),
+        Some("6f2a6fc74dd09fb70969a0843ca9fa971c26f224cb2bf11ce18d3c9c2b385a84")
+    );
+    assert_eq!(pinned("g1_g2_1024.txt"), None);
+    assert_eq!(
+        Source::parse("https://srs.availproject.org/"),
+        Source::default()
+    );
+    assert_eq!(
+        Source::parse("./artifacts"),
+        Source::Dir(PathBuf::from("./artifacts"))
+    );
+}
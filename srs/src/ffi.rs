@@ -0,0 +1,492 @@
+//! C interface over dusk-plonk's public parameters & KZG operations, built into `libsrs.so` &
+//! `libsrs.a`. Header is `include/srs.h`, generated by cbindgen ( see `cbindgen.toml` ).
+//!
+//! Scalars are 32 bytes little-endian, g1 points are 48 bytes compressed, same as everywhere else
+//! in this crate. Every function returns `SRS_OK` on success or a negative status, in which case
+//! `srs_last_error` describes what went wrong. Handles are owned by caller, who must release them
+//! using matching `*_free` function.
+
+use crate::artifact::Artifact;
+use crate::convert::to_public_parameters;
+use dusk_bytes::Serializable;
+use dusk_plonk::bls12_381::{BlsScalar, G1Affine};
+use dusk_plonk::commitment_scheme::kzg10::{
+    CommitKey, Commitment, OpeningKey, Proof, PublicParameters,
+};
+use dusk_plonk::fft::Polynomial;
+use merlin::Transcript;
+use std::cell::RefCell;
+use std::convert::TryInto;
+use std::ffi::CString;
+use std::io;
+use std::os::raw::c_char;
+use std::panic::{self, AssertUnwindSafe};
+use std::ptr;
+use std::slice;
+
+/// Call succeeded.
+pub const SRS_OK: i32 = 0;
+/// A required pointer was null.
+pub const SRS_ERR_NULL: i32 = -1;
+/// Input bytes don't decode, e.g. a non-canonical scalar or an invalid point.
+pub const SRS_ERR_INVALID: i32 = -2;
+/// Requested degree is out of range of public parameters or commit key.
+pub const SRS_ERR_DEGREE: i32 = -3;
+/// Proof, or batch of proofs, doesn't verify.
+pub const SRS_ERR_PROOF: i32 = -4;
+/// Rust code panicked, which is a bug.
+pub const SRS_ERR_PANIC: i32 = -5;
+
+/// Size of a scalar, in bytes.
+pub const SRS_SCALAR_SIZE: usize = 32;
+/// Size of a compressed g1 point, in bytes.
+pub const SRS_G1_SIZE: usize = 48;
+
+/// Public parameters, as returned by `srs_load`.
+pub struct SrsPublicParameters(PublicParameters);
+
+/// Commit key, as returned by `srs_trim`.
+pub struct SrsCommitKey(CommitKey);
+
+/// Opening key, as returned by `srs_trim`.
+pub struct SrsOpeningKey(OpeningKey);
+
+thread_local! {
+    static LAST_ERROR: RefCell<CString> = RefCell::new(CString::default());
+}
+
+fn set_last_error(msg: String) {
+    let msg = CString::new(msg.replace('\0', " ")).unwrap_or_default();
+    LAST_ERROR.with(|e| *e.borrow_mut() = msg);
+}
+
+struct Error(i32, String);
+
+impl From<io::Error> for Error {
+    fn from(e: io::Error) -> Error {
+        Error(SRS_ERR_INVALID, e.to_string())
+    }
+}
+
+/// Runs `f`, turning its errors & panics into a status, recording their message.
+fn status<F: FnOnce() -> Result<(), Error>>(f: F) -> i32 {
+    match panic::catch_unwind(AssertUnwindSafe(f)) {
+        Ok(Ok(())) => SRS_OK,
+        Ok(Err(Error(code, msg))) => {
+            set_last_error(msg);
+            code
+        }
+        Err(_) => {
+            set_last_error("srs panicked".to_owned());
+            SRS_ERR_PANIC
+        }
+    }
+}
+
+fn non_null<T>(p: *const T, name: &str) -> Result<(), Error> {
+    if p.is_null() {
+        return Err(Error(SRS_ERR_NULL, format!("`{}` is null", name)));
+    }
+    Ok(())
+}
+
+unsafe fn bytes<'a>(p: *const u8, len: usize, name: &str) -> Result<&'a [u8], Error> {
+    if len == 0 {
+        return Ok(&[]);
+    }
+    non_null(p, name)?;
+    Ok(slice::from_raw_parts(p, len))
+}
+
+fn scalar(bytes: &[u8]) -> Result<BlsScalar, Error> {
+    let bytes: [u8; SRS_SCALAR_SIZE] = bytes.try_into().unwrap();
+    BlsScalar::from_bytes(&bytes)
+        .map_err(|e| Error(SRS_ERR_INVALID, format!("scalar isn't canonical: {:?}", e)))
+}
+
+fn g1(bytes: &[u8]) -> Result<G1Affine, Error> {
+    let bytes: [u8; SRS_G1_SIZE] = bytes.try_into().unwrap();
+    G1Affine::from_bytes(&bytes)
+        .map_err(|e| Error(SRS_ERR_INVALID, format!("g1 point is invalid: {:?}", e)))
+}
+
+unsafe fn proof(
+    commitment: *const u8,
+    point: *const u8,
+    value: *const u8,
+    witness: *const u8,
+) -> Result<(BlsScalar, Proof), Error> {
+    let proof = Proof {
+        commitment_to_witness: Commitment(g1(bytes(witness, SRS_G1_SIZE, "witness")?)?),
+        evaluated_point: scalar(bytes(value, SRS_SCALAR_SIZE, "value")?)?,
+        commitment_to_polynomial: Commitment(g1(bytes(commitment, SRS_G1_SIZE, "commitment")?)?),
+    };
+    Ok((scalar(bytes(point, SRS_SCALAR_SIZE, "point")?)?, proof))
+}
+
+unsafe fn polynomial(coeffs: *const u8, len: usize) -> Result<Polynomial, Error> {
+    if len == 0 {
+        return Err(Error(
+            SRS_ERR_INVALID,
+            "polynomial must have at least one coefficient".to_owned(),
+        ));
+    }
+    let coeffs = bytes(coeffs, len * SRS_SCALAR_SIZE, "coeffs")?
+        .chunks(SRS_SCALAR_SIZE)
+        .map(scalar)
+        .collect::<Result<Vec<_>, _>>()?;
+    Ok(Polynomial::from_coefficients_vec(coeffs))
+}
+
+unsafe fn write(out: *mut u8, bytes: &[u8]) {
+    ptr::copy_nonoverlapping(bytes.as_ptr(), out, bytes.len());
+}
+
+/// Message describing last error on calling thread, valid until next failing call on it.
+#[no_mangle]
+pub extern "C" fn srs_last_error() -> *const c_char {
+    LAST_ERROR.with(|e| e.borrow().as_ptr())
+}
+
+/// Decodes public parameters from content of `pp_<N>.data` or `pp_raw_<N>.data`, checking every
+/// point. `g1_g2_<N>.txt` is refused with `SRS_ERR_INVALID`, holding tau·G2 in place of beta_h.
+///
+/// # Safety
+///
+/// `bytes` must point to `len` readable bytes, `out` must be writable.
+#[no_mangle]
+pub unsafe extern "C" fn srs_load(
+    bytes: *const u8,
+    len: usize,
+    out: *mut *mut SrsPublicParameters,
+) -> i32 {
+    status(|| {
+        non_null(out, "out")?;
+        let artifact = Artifact::from_bytes(self::bytes(bytes, len, "bytes")?)?;
+        let pp = to_public_parameters(&artifact)?;
+        *out = Box::into_raw(Box::new(SrsPublicParameters(pp)));
+        Ok(())
+    })
+}
+
+/// Maximum degree of polynomials public parameters can commit to, zero if `pp` is null.
+///
+/// # Safety
+///
+/// `pp` must be null or returned by `srs_load`.
+#[no_mangle]
+pub unsafe extern "C" fn srs_max_degree(pp: *const SrsPublicParameters) -> usize {
+    pp.as_ref().map(|pp| pp.0.max_degree()).unwrap_or(0)
+}
+
+/// Trims public parameters into a commit key for polynomials of degree ≤ `degree` & an
+/// opening key.
+///
+/// # Safety
+///
+/// `pp` must be returned by `srs_load`, `commit_key` & `opening_key` must be writable.
+#[no_mangle]
+pub unsafe extern "C" fn srs_trim(
+    pp: *const SrsPublicParameters,
+    degree: usize,
+    commit_key: *mut *mut SrsCommitKey,
+    opening_key: *mut *mut SrsOpeningKey,
+) -> i32 {
+    status(|| {
+        non_null(pp, "pp")?;
+        non_null(commit_key, "commit_key")?;
+        non_null(opening_key, "opening_key")?;
+
+        let pp = &(*pp).0;
+        if degree == 0 || degree > pp.max_degree() {
+            return Err(Error(
+                SRS_ERR_DEGREE,
+                format!("degree must be within 1..={}", pp.max_degree()),
+            ));
+        }
+        let (ck, ok) = pp
+            .trim(degree)
+            .map_err(|e| Error(SRS_ERR_DEGREE, format!("unable to trim: {:?}", e)))?;
+        *commit_key = Box::into_raw(Box::new(SrsCommitKey(ck)));
+        *opening_key = Box::into_raw(Box::new(SrsOpeningKey(ok)));
+        Ok(())
+    })
+}
+
+/// Commits to polynomial given by `len` coefficients, lowest degree first, writing 48 bytes
+/// compressed commitment into `out`.
+///
+/// # Safety
+///
+/// `commit_key` must be returned by `srs_trim`, `coeffs` must point to `len * 32` readable bytes
+/// & `out` to 48 writable bytes.
+#[no_mangle]
+pub unsafe extern "C" fn kzg_commit(
+    commit_key: *const SrsCommitKey,
+    coeffs: *const u8,
+    len: usize,
+    out: *mut u8,
+) -> i32 {
+    status(|| {
+        non_null(commit_key, "commit_key")?;
+        non_null(out, "out")?;
+
+        let poly = polynomial(coeffs, len)?;
+        let commitment = (*commit_key)
+            .0
+            .commit(&poly)
+            .map_err(|e| Error(SRS_ERR_DEGREE, format!("unable to commit: {:?}", e)))?;
+        write(out, &commitment.0.to_bytes());
+        Ok(())
+    })
+}
+
+/// Opens polynomial given by `len` coefficients at `point`, writing its 32 bytes evaluation into
+/// `value` & 48 bytes compressed witness into `witness`.
+///
+/// # Safety
+///
+/// `commit_key` must be returned by `srs_trim`, `coeffs` must point to `len * 32` readable bytes,
+/// `point` to 32 readable bytes, `value` to 32 writable bytes & `witness` to 48 writable bytes.
+#[no_mangle]
+pub unsafe extern "C" fn kzg_open(
+    commit_key: *const SrsCommitKey,
+    coeffs: *const u8,
+    len: usize,
+    point: *const u8,
+    value: *mut u8,
+    witness: *mut u8,
+) -> i32 {
+    status(|| {
+        non_null(commit_key, "commit_key")?;
+        non_null(value, "value")?;
+        non_null(witness, "witness")?;
+
+        let poly = polynomial(coeffs, len)?;
+        let point = scalar(bytes(point, SRS_SCALAR_SIZE, "point")?)?;
+        let evaluation = poly.evaluate(&point);
+        let proof = (*commit_key)
+            .0
+            .open_single(&poly, &evaluation, &point)
+            .map_err(|e| Error(SRS_ERR_DEGREE, format!("unable to open: {:?}", e)))?;
+        write(value, &evaluation.to_bytes());
+        write(witness, &proof.commitment_to_witness.0.to_bytes());
+        Ok(())
+    })
+}
+
+/// Verifies that polynomial committed to by `commitment` evaluates to `value` at `point`,
+/// returning `SRS_OK` if `witness` proves so & `SRS_ERR_PROOF` if it doesn't.
+///
+/// # Safety
+///
+/// `opening_key` must be returned by `srs_trim`, `commitment` & `witness` must point to 48
+/// readable bytes, `point` & `value` to 32 readable bytes.
+#[no_mangle]
+pub unsafe extern "C" fn kzg_verify(
+    opening_key: *const SrsOpeningKey,
+    commitment: *const u8,
+    point: *const u8,
+    value: *const u8,
+    witness: *const u8,
+) -> i32 {
+    status(|| {
+        non_null(opening_key, "opening_key")?;
+
+        let (point, proof) = proof(commitment, point, value, witness)?;
+        if !(*opening_key).0.check(point, proof) {
+            return Err(Error(SRS_ERR_PROOF, "proof doesn't verify".to_owned()));
+        }
+        Ok(())
+    })
+}
+
+/// Verifies `len` proofs at once, i-th proof being made of i-th commitment, point, value &
+/// witness of given arrays. Returns `SRS_OK` only if every proof verifies.
+///
+/// # Safety
+///
+/// `opening_key` must be returned by `srs_trim`, `commitments` & `witnesses` must point to
+/// `len * 48` readable bytes, `points` & `values` to `len * 32` readable bytes.
+#[no_mangle]
+pub unsafe extern "C" fn kzg_batch_verify(
+    opening_key: *const SrsOpeningKey,
+    commitments: *const u8,
+    points: *const u8,
+    values: *const u8,
+    witnesses: *const u8,
+    len: usize,
+) -> i32 {
+    status(|| {
+        non_null(opening_key, "opening_key")?;
+        if len > 0 {
+            non_null(commitments, "commitments")?;
+            non_null(points, "points")?;
+            non_null(values, "values")?;
+            non_null(witnesses, "witnesses")?;
+        }
+
+        let mut ps = Vec::with_capacity(len);
+        let mut proofs = Vec::with_capacity(len);
+        for i in 0..len {
+            let (p, proof) = proof(
+                commitments.add(i * SRS_G1_SIZE),
+                points.add(i * SRS_SCALAR_SIZE),
+                values.add(i * SRS_SCALAR_SIZE),
+                witnesses.add(i * SRS_G1_SIZE),
+            )?;
+            ps.push(p);
+            proofs.push(proof);
+        }
+
+        (*opening_key)
+            .0
+            .batch_check(&ps, &proofs, &mut Transcript::new(b"srs-kzg-batch"))
+            .map_err(|e| Error(SRS_ERR_PROOF, format!("batch doesn't verify: {:?}", e)))
+    })
+}
+
+/// Releases public parameters, null is ignored.
+///
+/// # Safety
+///
+/// `pp` must be null or returned by `srs_load`, and not released before.
+#[no_mangle]
+pub unsafe extern "C" fn srs_free(pp: *mut SrsPublicParameters) {
+    if !pp.is_null() {
+        drop(Box::from_raw(pp));
+    }
+}
+
+/// Releases a commit key, null is ignored.
+///
+/// # Safety
+///
+/// `commit_key` must be null or returned by `srs_trim`, and not released before.
+#[no_mangle]
+pub unsafe extern "C" fn srs_commit_key_free(commit_key: *mut SrsCommitKey) {
+    if !commit_key.is_null() {
+        drop(Box::from_raw(commit_key));
+    }
+}
+
+/// Releases an opening key, null is ignored.
+///
+/// # Safety
+///
+/// `opening_key` must be null or returned by `srs_trim`, and not released before.
+#[no_mangle]
+pub unsafe extern "C" fn srs_opening_key_free(opening_key: *mut SrsOpeningKey) {
+    if !opening_key.is_null() {
+        drop(Box::from_raw(opening_key));
+    }
+}
+
+#[test]
+fn test_ffi_roundtrip() {
+    use rand::rngs::StdRng;
+    use rand::SeedableRng;
+
+    let mut rng = StdRng::seed_from_u64(38);
+    let bytes = PublicParameters::setup(1 << 4, &mut rng)
+        .unwrap()
+        .to_var_bytes();
+
+    unsafe {
+        let mut pp = ptr::null_mut();
+        assert_eq!(srs_load(bytes.as_ptr(), bytes.len(), &mut pp), SRS_OK);
+        assert_eq!(srs_max_degree(pp), 1 << 4);
+
+        let (mut ck, mut ok) = (ptr::null_mut(), ptr::null_mut());
+        assert_eq!(srs_trim(pp, 1 << 5, &mut ck, &mut ok), SRS_ERR_DEGREE);
+        assert_eq!(srs_trim(pp, 8, &mut ck, &mut ok), SRS_OK);
+
+        let coeffs: Vec<u8> = (1..=8u64)
+            .flat_map(|c| BlsScalar::from(c).to_bytes().to_vec())
+            .collect();
+        let point = BlsScalar::from(5).to_bytes();
+        let mut commitment = [0u8; SRS_G1_SIZE];
+        let mut value = [0u8; SRS_SCALAR_SIZE];
+        let mut witness = [0u8; SRS_G1_SIZE];
+        assert_eq!(
+            kzg_commit(ck, coeffs.as_ptr(), 8, commitment.as_mut_ptr()),
+            SRS_OK
+        );
+        assert_eq!(
+            kzg_open(
+                ck,
+                coeffs.as_ptr(),
+                8,
+                point.as_ptr(),
+                value.as_mut_ptr(),
+                witness.as_mut_ptr()
+            ),
+            SRS_OK
+        );
+        assert_eq!(
+            kzg_verify(
+                ok,
+                commitment.as_ptr(),
+                point.as_ptr(),
+                value.as_ptr(),
+                witness.as_ptr()
+            ),
+            SRS_OK
+        );
+        assert_eq!(
+            kzg_batch_verify(
+                ok,
+                commitment.as_ptr(),
+                point.as_ptr(),
+                value.as_ptr(),
+                witness.as_ptr(),
+                1
+            ),
+            SRS_OK
+        );
+
+        value[0] ^= 1;
+        assert_eq!(
+            kzg_verify(
+                ok,
+                commitment.as_ptr(),
+                point.as_ptr(),
+                value.as_ptr(),
+                witness.as_ptr()
+            ),
+            SRS_ERR_PROOF
+        );
+        assert_eq!(
+            kzg_verify(
+                ptr::null(),
+                commitment.as_ptr(),
+                point.as_ptr(),
+                value.as_ptr(),
+                witness.as_ptr()
+            ),
+            SRS_ERR_NULL
+        );
+        assert!(!std::ffi::CStr::from_ptr(srs_last_error())
+            .to_bytes()
+            .is_empty());
+
+        srs_commit_key_free(ck);
+        srs_opening_key_free(ok);
+        srs_free(pp);
+    }
+}
+
+#[test]
+fn test_ffi_load_refuses_text() {
+    use crate::artifact::test_artifacts;
+
+    let (_, _, text) = test_artifacts(8);
+    unsafe {
+        let mut pp = ptr::null_mut();
+        assert_eq!(
+            srs_load(text.as_ptr(), text.len(), &mut pp),
+            SRS_ERR_INVALID
+        );
+        assert!(pp.is_null());
+    }
+}
@@ -0,0 +1,175 @@
+//! Human and machine readable summaries of SRS artifacts.
+
+use crate::artifact::{Artifact, Encoded, Format};
+use serde_json::{json, Value};
+use std::fmt;
+
+/// Everything worth knowing about an artifact, without dumping all of its points.
+#[derive(Clone, Debug)]
+pub struct Summary {
+    pub format: Format,
+    pub len: usize,
+    pub max_degree: usize,
+    pub g1_count: usize,
+    pub g2_count: usize,
+    pub hash: Option<String>,
+    pub first_g1: Option<Point>,
+    pub last_g1: Option<Point>,
+    pub first_g2: Option<Point>,
+    pub last_g2: Option<Point>,
+    pub invalid_points: usize,
+}
+
+/// Hex encoded bytes of a point, as found in artifact.
+#[derive(Clone, Debug)]
+pub struct Point {
+    pub index: usize,
+    pub hex: String,
+    pub valid: bool,
+}
+
+impl Point {
+    fn of<P>(index: usize, p: &Encoded<P>) -> Point {
+        Point {
+            index,
+            hex: hex::encode(&p.bytes),
+            valid: p.point.is_some(),
+        }
+    }
+
+    fn first<P>(ps: &[Encoded<P>]) -> Option<Point> {
+        ps.first().map(|p| Point::of(0, p))
+    }
+
+    fn last<P>(ps: &[Encoded<P>]) -> Option<Point> {
+        ps.last().map(|p| Point::of(ps.len() - 1, p))
+    }
+
+    fn to_json(&self) -> Value {
+        json!({
+            "index": self.index,
+            "hex": self.hex,
+            "valid": self.valid,
+        })
+    }
+}
+
+impl Summary {
+    pub fn new(a: &Artifact) -> Summary {
+        Summary {
+            format: a.format,
+            len: a.len,
+            max_degree: a.max_degree(),
+            g1_count: a.g1s.len(),
+            g2_count: a.g2s.len(),
+            hash: a.hash.map(hex::encode),
+            first_g1: Point::first(&a.g1s),
+            last_g1: Point::last(&a.g1s),
+            first_g2: Point::first(&a.g2s),
+            last_g2: Point::last(&a.g2s),
+            invalid_points: a.invalid_points(),
+        }
+    }
+
+    /// Which `PublicParameters` serialiser produced the artifact, if any.
+    pub fn variant(&self) -> &'static str {
+        match self.format {
+            Format::Compressed => "to_var_bytes",
+            Format::Raw => "to_raw_var_bytes",
+            Format::Extracted | Format::Text => "-",
+        }
+    }
+
+    pub fn to_json(&self) -> Value {
+        json!({
+            "format": self.format.name(),
+            "variant": self.variant(),
+            "size": self.len,
+            "max_degree": self.max_degree,
+            "g1_count": self.g1_count,
+            "g2_count": self.g2_count,
+            "hash": self.hash,
+            "first_g1": self.first_g1.as_ref().map(Point::to_json),
+            "last_g1": self.last_g1.as_ref().map(Point::to_json),
+            "first_g2": self.first_g2.as_ref().map(Point::to_json),
+            "last_g2": self.last_g2.as_ref().map(Point::to_json),
+            "invalid_points": self.invalid_points,
+        })
+    }
+}
+
+impl fmt::Display for Summary {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        writeln!(f, "format\t\t{}", self.format)?;
+        writeln!(f, "variant\t\t{}", self.variant())?;
+        writeln!(f, "size\t\t{}b", self.len)?;
+        writeln!(f, "max degree\t{}", self.max_degree)?;
+        writeln!(f, "g1 points\t{}", self.g1_count)?;
+        writeln!(f, "g2 points\t{}", self.g2_count)?;
+        writeln!(f, "hash\t\t{}", self.hash.as_deref().unwrap_or("-"))?;
+
+        let points = [
+            ("first g1", &self.first_g1),
+            ("last g1", &self.last_g1),
+            ("first g2", &self.first_g2),
+            ("last g2", &self.last_g2),
+        ];
+        for (name, point) in points.iter() {
+            match point {
+                Some(p) => writeln!(
+                    f,
+                    "{}\t#{} {}{}",
+                    name,
+                    p.index,
+                    p.hex,
+                    if p.valid { "" } else { "\t[INVALID]" }
+                )?,
+                None => writeln!(f, "{}\t-", name)?,
+            }
+        }
+
+        writeln!(f, "invalid points\t{}", self.invalid_points)
+    }
+}
+
+#[test]
+fn test_inspect_generated_pp() {
+    use dusk_plonk::commitment_scheme::kzg10::PublicParameters;
+    use rand::rngs::StdRng;
+    use rand::SeedableRng;
+
+    let pp = PublicParameters::setup(15, &mut StdRng::seed_from_u64(27)).unwrap();
+    let bytes = pp.to_var_bytes();
+    let summary = Summary::new(&Artifact::from_bytes(&bytes).unwrap());
+
+    assert_eq!(summary.format, Format::Compressed);
+    assert_eq!(summary.variant(), "to_var_bytes");
+    assert_eq!(summary.len, bytes.len());
+    assert_eq!((summary.g1_count, summary.g2_count), (16, 2));
+    assert_eq!(summary.max_degree, 15);
+    assert_eq!(summary.invalid_points, 0);
+
+    let json = summary.to_json();
+    assert_eq!(json["format"], "compressed");
+    assert_eq!(json["variant"], "to_var_bytes");
+    assert_eq!(json["size"], bytes.len());
+    assert_eq!(json["g1_count"], 16);
+    assert_eq!(json["g2_count"], 2);
+    assert_eq!(json["max_degree"], 15);
+    assert_eq!(json["hash"], Value::Null);
+    assert_eq!(json["last_g1"]["index"], 15);
+    assert_eq!(json["last_g1"]["valid"], true);
+    assert_eq!(
+        json["first_g2"]["hex"],
+        hex::encode(&bytes[crate::artifact::G1_SIZE..][..crate::artifact::G2_SIZE])
+    );
+    assert_eq!(json["invalid_points"], 0);
+
+    // a corrupted point is reported, not rejected
+    let mut corrupted = bytes.clone();
+    let last = corrupted.len() - 1;
+    corrupted[last] ^= 1;
+    let summary = Summary::new(&Artifact::from_bytes(&corrupted).unwrap());
+    assert_eq!(summary.invalid_points, 1);
+    assert!(!summary.last_g1.unwrap().valid);
+}
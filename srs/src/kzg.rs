@@ -0,0 +1,191 @@
+//! Hex & JSON encodings of polynomials, commitments and opening proofs, so that KZG
+//! operations over the reference string can be driven from plain files.
+//!
+//! Scalars are 32 bytes little-endian, as in `BlsScalar::to_bytes`, while g1 points are
+//! 48 bytes compressed, as in `G1Affine::to_bytes`. Both are hex encoded.
+
+use crate::artifact::{invalid, G1_SIZE};
+use dusk_bytes::Serializable;
+use dusk_plonk::bls12_381::{BlsScalar, G1Affine};
+use dusk_plonk::commitment_scheme::kzg10::{Commitment, Proof};
+use dusk_plonk::fft::{EvaluationDomain, Evaluations, Polynomial};
+use serde::{Deserialize, Serialize};
+use std::convert::TryInto;
+use std::fs;
+use std::io;
+use std::path::Path;
+
+pub fn scalar_from_hex(s: &str) -> io::Result<BlsScalar> {
+    let bytes = hex::decode(s.trim().trim_start_matches("0x")).map_err(invalid)?;
+    let bytes: [u8; 32] = bytes
+        .try_into()
+        .map_err(|_| invalid(format!("scalar `{}` must be 32 bytes", s)))?;
+    BlsScalar::from_bytes(&bytes)
+        .map_err(|e| invalid(format!("scalar `{}` isn't canonical: {:?}", s, e)))
+}
+
+pub fn scalar_to_hex(s: &BlsScalar) -> String {
+    hex::encode(s.to_bytes())
+}
+
+pub fn g1_from_hex(s: &str) -> io::Result<G1Affine> {
+    let bytes = hex::decode(s.trim().trim_start_matches("0x")).map_err(invalid)?;
+    let bytes: [u8; G1_SIZE] = bytes
+        .try_into()
+        .map_err(|_| invalid(format!("g1 point `{}` must be {} bytes", s, G1_SIZE)))?;
+    G1Affine::from_bytes(&bytes)
+        .map_err(|e| invalid(format!("g1 point `{}` is invalid: {:?}", s, e)))
+}
+
+pub fn g1_to_hex(p: &G1Affine) -> String {
+    hex::encode(p.to_bytes())
+}
+
+/// Polynomial, given either by its coefficients ( lowest degree first ) or by its evaluations
+/// over an evaluation domain, as in `{"coefficients": [..]}` or `{"evaluations": [..]}`.
+#[derive(Clone, Debug, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum PolynomialFile {
+    Coefficients(Vec<String>),
+    Evaluations(Vec<String>),
+}
+
+impl PolynomialFile {
+    /// Reads a JSON polynomial file, or a plain one holding a hex scalar per line. Plain files
+    /// are read as coefficients, unless `evaluations` is set.
+    pub fn open<P: AsRef<Path>>(path: P, evaluations: bool) -> io::Result<PolynomialFile> {
+        let bytes = fs::read(path)?;
+        let text = std::str::from_utf8(&bytes).map_err(invalid)?;
+
+        if text.trim_start().starts_with('{') {
+            return serde_json::from_str(text).map_err(invalid);
+        }
+
+        let scalars = text
+            .lines()
+            .map(str::trim)
+            .filter(|l| !l.is_empty())
+            .map(String::from)
+            .collect();
+        if evaluations {
+            Ok(PolynomialFile::Evaluations(scalars))
+        } else {
+            Ok(PolynomialFile::Coefficients(scalars))
+        }
+    }
+
+    pub fn to_polynomial(&self) -> io::Result<Polynomial> {
+        match self {
+            PolynomialFile::Coefficients(coeffs) => {
+                Ok(Polynomial::from_coefficients_vec(parse_scalars(coeffs)?))
+            }
+            PolynomialFile::Evaluations(evals) => {
+                let evals = parse_scalars(evals)?;
+                interpolate(evals)
+            }
+        }
+    }
+}
+
+/// Interpolates evaluations over smallest evaluation domain which fits them, missing
+/// evaluations are taken to be zero.
+pub fn interpolate(mut evals: Vec<BlsScalar>) -> io::Result<Polynomial> {
+    let domain = EvaluationDomain::new(evals.len()).map_err(|e| {
+        invalid(format!(
+            "no evaluation domain of size {}: {:?}",
+            evals.len(),
+            e
+        ))
+    })?;
+    evals.resize(domain.size(), BlsScalar::zero());
+    Ok(Evaluations::from_vec_and_domain(evals, domain).interpolate())
+}
+
+fn parse_scalars(scalars: &[String]) -> io::Result<Vec<BlsScalar>> {
+    if scalars.is_empty() {
+        return Err(invalid("polynomial must have at least one scalar"));
+    }
+    scalars.iter().map(|s| scalar_from_hex(s)).collect()
+}
+
+/// Opening proof of a committed polynomial at a point, all hex encoded.
+#[derive(Clone, Debug, PartialEq, Eq, Serialize, Deserialize)]
+pub struct OpeningProof {
+    /// commitment to polynomial
+    pub commitment: String,
+    /// point polynomial is opened at
+    pub point: String,
+    /// evaluation of polynomial at point
+    pub value: String,
+    /// commitment to witness polynomial
+    pub witness: String,
+}
+
+impl OpeningProof {
+    pub fn new(point: &BlsScalar, proof: &Proof) -> OpeningProof {
+        OpeningProof {
+            commitment: g1_to_hex(&proof.commitment_to_polynomial.0),
+            point: scalar_to_hex(point),
+            value: scalar_to_hex(&proof.evaluated_point),
+            witness: g1_to_hex(&proof.commitment_to_witness.0),
+        }
+    }
+
+    /// Decodes into a point & a proof, ready for `OpeningKey::check`.
+    pub fn decode(&self) -> io::Result<(BlsScalar, Proof)> {
+        let proof = Proof {
+            commitment_to_witness: Commitment(g1_from_hex(&self.witness)?),
+            evaluated_point: scalar_from_hex(&self.value)?,
+            commitment_to_polynomial: Commitment(g1_from_hex(&self.commitment)?),
+        };
+        Ok((scalar_from_hex(&self.point)?, proof))
+    }
+
+    /// Reads a file holding either a single proof or an array of them.
+    pub fn open_all<P: AsRef<Path>>(path: P) -> io::Result<Vec<OpeningProof>> {
+        #[derive(Deserialize)]
+        #[serde(untagged)]
+        enum OneOrMany {
+            One(OpeningProof),
+            Many(Vec<OpeningProof>),
+        }
+
+        match serde_json::from_slice(&fs::read(path)?).map_err(invalid)? {
+            OneOrMany::One(proof) => Ok(vec![proof]),
+            OneOrMany::Many(proofs) => Ok(proofs),
+        }
+    }
+}
+
+#[test]
+fn test_opening_proof_roundtrip() {
+    use dusk_plonk::commitment_scheme::kzg10::PublicParameters;
+    use rand::rngs::StdRng;
+    use rand::SeedableRng;
+
+    let mut rng = StdRng::seed_from_u64(32);
+    let pp = PublicParameters::setup(1 << 5, &mut rng).unwrap();
+
+    let evals = (0..20u64).map(BlsScalar::from).collect::<Vec<_>>();
+    let poly = interpolate(evals.clone()).unwrap();
+    let file = PolynomialFile::Evaluations(evals.iter().map(scalar_to_hex).collect());
+    assert_eq!(file.to_polynomial().unwrap(), poly);
+
+    let point = BlsScalar::from(7);
+    let value = poly.evaluate(&point);
+    let proof = pp.commit_key().open_single(&poly, &value, &point).unwrap();
+    assert_eq!(
+        proof.commitment_to_polynomial.0,
+        pp.commit_key().commit(&poly).unwrap().0
+    );
+
+    let encoded = OpeningProof::new(&point, &proof);
+    let (decoded_point, decoded) = encoded.decode().unwrap();
+    assert_eq!(decoded_point, point);
+    assert!(pp.opening_key().check(decoded_point, decoded));
+
+    let mut forged = encoded.clone();
+    forged.value = scalar_to_hex(&(value + BlsScalar::one()));
+    let (forged_point, forged) = forged.decode().unwrap();
+    assert!(!pp.opening_key().check(forged_point, forged));
+}
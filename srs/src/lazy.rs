@@ -0,0 +1,211 @@
+//! Memory mapped view over `pp_raw_<N>.data`, decoding g1 powers only when they're asked for.
+//!
+//! For large reference strings, `PublicParameters::from_slice_unchecked` spends most of its time
+//! & memory decoding powers which may never be used. `LazyPublicParameters` decodes opening key
+//! eagerly, but keeps commit key in mapped file until a power is requested, caching it afterwards.
+
+use crate::artifact::{invalid, Format, G1_RAW_SIZE, OPENING_KEY_SIZE, RAW_LENGTH_SIZE};
+use dusk_bytes::Serializable;
+use dusk_plonk::bls12_381::G1Affine;
+use dusk_plonk::commitment_scheme::kzg10::{CommitKey, OpeningKey, PublicParameters};
+use memmap::Mmap;
+use std::collections::HashMap;
+use std::convert::TryInto;
+use std::fs::File;
+use std::io;
+use std::path::Path;
+use std::sync::RwLock;
+
+pub struct LazyPublicParameters {
+    map: Mmap,
+    opening_key: OpeningKey,
+    len: usize,
+    cache: RwLock<HashMap<usize, G1Affine>>,
+}
+
+impl LazyPublicParameters {
+    /// Maps a `pp_raw_<N>.data` file.
+    ///
+    /// # Safety
+    ///
+    /// Same as `PublicParameters::from_slice_unchecked`, g1 powers aren't checked, so file must be
+    /// trusted. It also must not be modified while it's mapped.
+    pub unsafe fn open<P: AsRef<Path>>(path: P) -> io::Result<LazyPublicParameters> {
+        let file = File::open(path)?;
+        let map = Mmap::map(&file)?;
+
+        if Format::detect(&map[..]) != Some(Format::Raw) {
+            return Err(invalid(
+                "expected raw public parameters, as in `pp_raw_<N>.data`",
+            ));
+        }
+
+        let opening_key = OpeningKey::from_bytes(&map[..OPENING_KEY_SIZE].try_into().unwrap())
+            .map_err(|e| invalid(format!("unable to decode opening key: {:?}", e)))?;
+        let len = (map.len() - OPENING_KEY_SIZE - RAW_LENGTH_SIZE) / G1_RAW_SIZE;
+
+        Ok(LazyPublicParameters {
+            map,
+            opening_key,
+            len,
+            cache: RwLock::new(HashMap::new()),
+        })
+    }
+
+    pub fn max_degree(&self) -> usize {
+        self.len - 1
+    }
+
+    pub fn opening_key(&self) -> &OpeningKey {
+        &self.opening_key
+    }
+
+    /// Raw bytes of i-th g1 power, straight from mapped file.
+    pub fn raw_g1(&self, index: usize) -> &[u8] {
+        assert!(
+            index < self.len,
+            "g1 power #{} out of {} powers",
+            index,
+            self.len
+        );
+        let start = OPENING_KEY_SIZE + RAW_LENGTH_SIZE + index * G1_RAW_SIZE;
+        &self.map[start..(start + G1_RAW_SIZE)]
+    }
+
+    /// Decodes i-th g1 power, or returns it from cache if it was decoded before.
+    pub fn g1(&self, index: usize) -> G1Affine {
+        if let Some(p) = self.cache.read().unwrap().get(&index) {
+            return *p;
+        }
+
+        // safe, trust in file was established when opening it
+        let p = unsafe { G1Affine::from_slice_unchecked(self.raw_g1(index)) };
+        self.cache.write().unwrap().insert(index, p);
+        p
+    }
+
+    /// Number of g1 powers decoded so far.
+    pub fn cached(&self) -> usize {
+        self.cache.read().unwrap().len()
+    }
+
+    /// Same as `PublicParameters::trim`, but commit key is a view into mapped file.
+    pub fn trim(&self, truncated_degree: usize) -> io::Result<(LazyCommitKey<'_>, OpeningKey)> {
+        if truncated_degree == 0 {
+            return Err(invalid("truncated degree can't be zero"));
+        }
+        // `CommitKey::truncate` never truncates to degree 1, refusing parameters of max degree 1
+        let degree = truncated_degree.max(2);
+        if degree > self.max_degree() {
+            return Err(invalid(format!(
+                "truncated degree {} is larger than max degree {}",
+                degree,
+                self.max_degree()
+            )));
+        }
+        Ok((
+            LazyCommitKey {
+                params: self,
+                len: degree + 1,
+            },
+            self.opening_key.clone(),
+        ))
+    }
+
+    /// Eagerly decodes everything, same as `PublicParameters::from_slice_unchecked` over whole file.
+    pub fn to_public_parameters(&self) -> PublicParameters {
+        // safe, trust in file was established when opening it
+        unsafe { PublicParameters::from_slice_unchecked(&self.map[..]) }
+    }
+}
+
+/// First `len` g1 powers of a `LazyPublicParameters`, without copying them.
+#[derive(Copy, Clone)]
+pub struct LazyCommitKey<'a> {
+    params: &'a LazyPublicParameters,
+    len: usize,
+}
+
+impl<'a> LazyCommitKey<'a> {
+    pub fn max_degree(&self) -> usize {
+        self.len - 1
+    }
+
+    pub fn g1(&self, index: usize) -> G1Affine {
+        assert!(
+            index < self.len,
+            "g1 power #{} out of {} powers",
+            index,
+            self.len
+        );
+        self.params.g1(index)
+    }
+
+    /// Raw bytes of all powers in this view, borrowed from mapped file.
+    pub fn raw_bytes(&self) -> &'a [u8] {
+        let start = OPENING_KEY_SIZE + RAW_LENGTH_SIZE;
+        &self.params.map[start..(start + self.len * G1_RAW_SIZE)]
+    }
+
+    /// Materialises this view into a commit key, for use with dusk-plonk.
+    pub fn to_commit_key(&self) -> CommitKey {
+        let mut bytes = Vec::with_capacity(RAW_LENGTH_SIZE + self.len * G1_RAW_SIZE);
+        bytes.extend_from_slice(&(self.len as u64).to_ne_bytes());
+        bytes.extend_from_slice(self.raw_bytes());
+
+        // safe, trust in file was established when opening it
+        unsafe { CommitKey::from_slice_unchecked(&bytes) }
+    }
+}
+
+#[test]
+fn test_lazy_matches_eager() {
+    use rand::rngs::StdRng;
+    use rand::SeedableRng;
+    use std::fs;
+
+    let mut rng = StdRng::seed_from_u64(0xdead_beef);
+    let pp = PublicParameters::setup(1 << 6, &mut rng).unwrap();
+    let raw = pp.to_raw_var_bytes();
+
+    let path = std::env::temp_dir().join(format!("srs_lazy_{}.data", std::process::id()));
+    fs::write(&path, &raw).unwrap();
+    let lazy = unsafe { LazyPublicParameters::open(&path).unwrap() };
+    let eager = unsafe { PublicParameters::from_slice_unchecked(&raw) };
+
+    assert_eq!(lazy.max_degree(), eager.max_degree());
+    assert_eq!(
+        lazy.opening_key().to_bytes(),
+        eager.opening_key().to_bytes()
+    );
+    assert_eq!(lazy.cached(), 0);
+
+    for degree in [1, 2, 17, 1 << 6].iter() {
+        let (lazy_ck, lazy_ok) = lazy.trim(*degree).unwrap();
+        let (eager_ck, eager_ok) = eager.trim(*degree).unwrap();
+        assert_eq!(lazy_ck.to_commit_key(), eager_ck);
+        assert_eq!(lazy_ok.to_bytes(), eager_ok.to_bytes());
+    }
+
+    let eager_powers = eager.commit_key().to_var_bytes();
+    for i in [0, 1, 33, 1 << 6].iter() {
+        assert_eq!(
+            &lazy.g1(*i).to_bytes()[..],
+            &eager_powers[(i * 48)..((i + 1) * 48)]
+        );
+    }
+    assert_eq!(lazy.cached(), 4);
+    assert_eq!(lazy.to_public_parameters().commit_key(), eager.commit_key());
+    assert!(lazy.trim(0).is_err());
+    assert!(lazy.trim((1 << 6) + 1).is_err());
+
+    // degree 1 is refused as eagerly, there being no degree 2 to truncate to
+    drop(lazy);
+    let pp = PublicParameters::setup(1, &mut rng).unwrap();
+    fs::write(&path, pp.to_raw_var_bytes()).unwrap();
+    let lazy = unsafe { LazyPublicParameters::open(&path).unwrap() };
+    assert!(pp.trim(1).is_err());
+    assert!(lazy.trim(1).is_err());
+
+    fs::remove_file(&path).unwrap();
+}
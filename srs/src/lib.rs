@@ -0,0 +1,43 @@
+#[cfg(feature = "crosscheck")]
+extern crate ark_bls12_381;
+#[cfg(feature = "crosscheck")]
+extern crate ark_ec;
+#[cfg(feature = "crosscheck")]
+extern crate ark_ff;
+#[cfg(feature = "crosscheck")]
+extern crate ark_serialize;
+extern crate codec;
+extern crate dusk_bytes;
+extern crate dusk_plonk;
+extern crate ed25519_dalek;
+extern crate hex;
+extern crate memmap;
+#[cfg(feature = "crosscheck")]
+extern crate merlin3;
+#[cfg(feature = "crosscheck")]
+extern crate poly_multiproof;
+extern crate rand;
+extern crate rand_chacha;
+extern crate serde;
+extern crate serde_json;
+extern crate sha2;
+extern crate ureq;
+
+pub mod artifact;
+pub mod cache;
+pub mod convert;
+#[cfg(feature = "crosscheck")]
+pub mod crosscheck;
+pub mod diff;
+pub mod eip2537;
+pub mod fetch;
+pub mod ffi;
+pub mod inspect;
+pub mod kzg;
+pub mod lazy;
+pub mod manifest;
+pub mod merkle;
+pub mod scale;
+pub mod shard;
+pub mod signed;
+pub mod vectors;
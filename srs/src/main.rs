@@ -3,10 +3,15 @@ extern crate hex;
 extern crate merlin;
 extern crate num_cpus;
 extern crate rand;
+extern crate srs;
+
+mod commands;
 
 use dusk_bytes::Serializable;
 use dusk_plonk::bls12_381::{G1Affine, G2Affine};
 use dusk_plonk::commitment_scheme::kzg10::{CommitKey, OpeningKey, PublicParameters};
+use srs::manifest::Manifest;
+use srs::merkle;
 // use dusk_plonk::bls12_381::{BlsScalar, G2Prepared};
 // use dusk_plonk::fft::{EvaluationDomain as PlonkED, Evaluations as PlonkEV};
 // use merlin::Transcript;
@@ -14,7 +19,7 @@ use dusk_plonk::commitment_scheme::kzg10::{CommitKey, OpeningKey, PublicParamete
 // use rand::thread_rng;
 use std::convert::TryInto;
 use std::env;
-use std::fs::{File, OpenOptions};
+use std::fs::{self, File, OpenOptions};
 use std::io::{Read, Seek, SeekFrom, Write};
 use std::sync::mpsc::sync_channel;
 use std::thread;
@@ -155,6 +160,24 @@ fn convert_g2s(g2s: Vec<u8>) -> std::io::Result<Vec<G2Affine>> {
 
 fn main() -> std::io::Result<()> {
     let cli_args: Vec<String> = env::args().collect();
+    match cli_args.get(1).map(String::as_str) {
+        Some("cache") => return commands::cache::run(&cli_args[2..]),
+        Some("convert") => return commands::convert::run(&cli_args[2..]),
+        #[cfg(feature = "crosscheck")]
+        Some("crosscheck") => return commands::crosscheck::run(&cli_args[2..]),
+        Some("diff") => return commands::diff::run(&cli_args[2..]),
+        Some("eip2537") => return commands::eip2537::run(&cli_args[2..]),
+        Some("fetch") => return commands::fetch::run(&cli_args[2..]),
+        Some("inspect") => return commands::inspect::run(&cli_args[2..]),
+        Some("kzg") => return commands::kzg::run(&cli_args[2..]),
+        Some("manifest") => return commands::manifest::run(&cli_args[2..]),
+        Some("merkle") => return commands::merkle::run(&cli_args[2..]),
+        Some("scale") => return commands::scale::run(&cli_args[2..]),
+        Some("shard") => return commands::shard::run(&cli_args[2..]),
+        Some("vectors") => return commands::vectors::run(&cli_args[2..]),
+        _ => {}
+    }
+
     assert_eq!(
         cli_args.len(),
         2,
@@ -202,6 +225,18 @@ fn main() -> std::io::Result<()> {
         N, N
     );
 
+    let g1_g2_name = format!("g1_g2_{}.txt", N);
+    let mut manifest = Manifest::new(N);
+    manifest.add_artifact(&g1_g2_name, &fs::read(&g1_g2_name)?);
+    manifest.add_artifact(&format!("pp_{}.data", N), &pp_bytes);
+    manifest.add_artifact(&format!("pp_raw_{}.data", N), &pp_raw_bytes);
+    manifest.merkle = Some(merkle::tree(&pp_bytes)?.commitment());
+    manifest.write(format!("manifest_{}.json", N))?;
+    println!(
+        "exported artifact hashes & merkle root into `./manifest_{}.json`\t✅",
+        N
+    );
+
     Ok(())
 }
 
@@ -0,0 +1,52 @@
+//! Release manifest of an SRS, listing its artifacts along with what they commit to.
+
+use crate::artifact::invalid;
+use crate::merkle::MerkleCommitment;
+use serde::{Deserialize, Serialize};
+use sha2::{Digest, Sha256};
+use std::collections::BTreeMap;
+use std::fs;
+use std::io;
+use std::path::Path;
+
+#[derive(Clone, Debug, PartialEq, Eq, Serialize, Deserialize)]
+pub struct Manifest {
+    /// number of g1 powers in commit key
+    pub points: usize,
+    /// hex encoded sha256 of each artifact, keyed by file name
+    pub artifacts: BTreeMap<String, String>,
+    /// Merkle tree over points of `pp_<N>.data`
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub merkle: Option<MerkleCommitment>,
+}
+
+impl Manifest {
+    pub fn new(points: usize) -> Manifest {
+        Manifest {
+            points,
+            artifacts: BTreeMap::new(),
+            merkle: None,
+        }
+    }
+
+    /// Records sha256 of an artifact under given file name.
+    pub fn add_artifact(&mut self, name: &str, bytes: &[u8]) {
+        self.artifacts.insert(name.to_owned(), sha256(bytes));
+    }
+
+    pub fn open<P: AsRef<Path>>(path: P) -> io::Result<Manifest> {
+        let bytes = fs::read(path)?;
+        serde_json::from_slice(&bytes).map_err(invalid)
+    }
+
+    pub fn write<P: AsRef<Path>>(&self, path: P) -> io::Result<()> {
+        let mut bytes = serde_json::to_vec_pretty(self)?;
+        bytes.push(b'\n');
+        fs::write(path, bytes)
+    }
+}
+
+/// Hex encoded sha256, same as `sha256sum` prints.
+pub fn sha256(bytes: &[u8]) -> String {
+    hex::encode(Sha256::digest(bytes))
+}
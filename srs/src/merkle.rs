@@ -0,0 +1,386 @@
+//! Merkle commitment over points of `pp_<N>.data`, letting light clients fetch & verify
+//! only those points they need.
+//!
+//! Leaves are encoded points, in the same order they appear in `pp_<N>.data`: g, h & beta_h of
+//! opening key, followed by g1 tau powers. So any prefix of leaves is a prefix of the file, and
+//! parameters for degree d are covered by leaves `0..(3 + d + 1)`.
+//!
+//! Leaf count is padded to next power of two using zeroed hashes. Leaves are hashed as
+//! sha256(0x00 || point), inner nodes as sha256(0x01 || left || right).
+
+use crate::artifact::{invalid, Format, G1_SIZE, G2_SIZE};
+use dusk_plonk::commitment_scheme::kzg10::PublicParameters;
+use serde::{Deserialize, Serialize};
+use sha2::{Digest, Sha256};
+use std::io;
+use std::ops::Range;
+
+pub type Hash = [u8; 32];
+
+/// g, h & beta_h
+pub const OPENING_KEY_LEAVES: usize = 3;
+
+const LEAF_PREFIX: u8 = 0;
+const NODE_PREFIX: u8 = 1;
+const PADDING: Hash = [0u8; 32];
+/// Largest tree a proof may claim, far beyond any reference string, so that a hostile leaf count
+/// can't overflow while working out depth of tree.
+pub const MAX_LEAVES: u64 = 1 << 32;
+
+fn hash_leaf(bytes: &[u8]) -> Hash {
+    let mut h = Sha256::new();
+    h.update([LEAF_PREFIX]);
+    h.update(bytes);
+    h.finalize().into()
+}
+
+fn hash_node(left: &Hash, right: &Hash) -> Hash {
+    let mut h = Sha256::new();
+    h.update([NODE_PREFIX]);
+    h.update(left);
+    h.update(right);
+    h.finalize().into()
+}
+
+fn hash_level(level: &[Hash]) -> Vec<Hash> {
+    level
+        .chunks(2)
+        .map(|pair| hash_node(&pair[0], &pair[1]))
+        .collect()
+}
+
+pub struct MerkleTree {
+    leaves: usize,
+    /// levels[0] holds ( padded ) leaf hashes, last level holds root
+    levels: Vec<Vec<Hash>>,
+}
+
+impl MerkleTree {
+    pub fn new<T: AsRef<[u8]>>(leaves: &[T]) -> MerkleTree {
+        assert!(!leaves.is_empty(), "can't build a tree without leaves");
+
+        let mut level: Vec<Hash> = leaves.iter().map(|l| hash_leaf(l.as_ref())).collect();
+        level.resize(leaves.len().next_power_of_two(), PADDING);
+
+        let mut levels = vec![level];
+        while levels[levels.len() - 1].len() > 1 {
+            let next = hash_level(&levels[levels.len() - 1]);
+            levels.push(next);
+        }
+
+        MerkleTree {
+            leaves: leaves.len(),
+            levels,
+        }
+    }
+
+    pub fn leaves(&self) -> usize {
+        self.leaves
+    }
+
+    pub fn root(&self) -> Hash {
+        self.levels[self.levels.len() - 1][0]
+    }
+
+    pub fn commitment(&self) -> MerkleCommitment {
+        MerkleCommitment {
+            root: self.root(),
+            leaves: self.leaves,
+        }
+    }
+
+    /// Proves inclusion of leaves in `range`, carrying only siblings on both edges of range.
+    pub fn prove(&self, range: Range<usize>) -> io::Result<RangeProof> {
+        if range.start >= range.end || range.end > self.leaves {
+            return Err(invalid(format!(
+                "range {:?} must be non-empty & within {} leaves",
+                range, self.leaves
+            )));
+        }
+
+        let (mut lo, mut hi) = (range.start, range.end);
+        let mut hashes = Vec::new();
+        for level in self.levels[..(self.levels.len() - 1)].iter() {
+            if lo % 2 == 1 {
+                hashes.push(level[lo - 1]);
+            }
+            if hi % 2 == 1 {
+                hashes.push(level[hi]);
+            }
+            lo /= 2;
+            hi = hi.div_ceil(2);
+        }
+
+        Ok(RangeProof {
+            leaves: self.leaves,
+            start: range.start,
+            end: range.end,
+            hashes,
+        })
+    }
+}
+
+/// Inclusion proof of leaves `start..end` in a tree of `leaves` leaves.
+#[derive(Clone, Debug, PartialEq, Eq, Serialize, Deserialize)]
+pub struct RangeProof {
+    pub leaves: usize,
+    pub start: usize,
+    pub end: usize,
+    #[serde(with = "hex_hashes")]
+    pub hashes: Vec<Hash>,
+}
+
+impl RangeProof {
+    /// Recomputes root from leaves being proven, failing if proof is malformed.
+    pub fn root<T: AsRef<[u8]>>(&self, leaves: &[T]) -> io::Result<Hash> {
+        if self.leaves as u64 > MAX_LEAVES {
+            return Err(invalid(format!(
+                "proof claims a tree of {} leaves, at most {} are allowed",
+                self.leaves, MAX_LEAVES
+            )));
+        }
+        if self.start >= self.end || self.end > self.leaves || leaves.len() != self.end - self.start
+        {
+            return Err(invalid(format!(
+                "proof of leaves {}..{} in a tree of {} doesn't fit {} leaves",
+                self.start,
+                self.end,
+                self.leaves,
+                leaves.len()
+            )));
+        }
+        let malformed = || invalid("proof holds wrong number of hashes");
+
+        let depth = self.leaves.next_power_of_two().trailing_zeros();
+        let mut level: Vec<Hash> = leaves.iter().map(|l| hash_leaf(l.as_ref())).collect();
+        let (mut lo, mut hi) = (self.start, self.end);
+        let mut hashes = self.hashes.iter();
+
+        for _ in 0..depth {
+            if lo % 2 == 1 {
+                level.insert(0, *hashes.next().ok_or_else(malformed)?);
+                lo -= 1;
+            }
+            if hi % 2 == 1 {
+                level.push(*hashes.next().ok_or_else(malformed)?);
+                hi += 1;
+            }
+            level = hash_level(&level);
+            lo /= 2;
+            hi /= 2;
+        }
+
+        if hashes.next().is_some() {
+            return Err(malformed());
+        }
+        Ok(level[0])
+    }
+}
+
+/// Root of a tree along with its leaf count, as pinned in manifest.
+#[derive(Copy, Clone, Debug, PartialEq, Eq, Serialize, Deserialize)]
+pub struct MerkleCommitment {
+    #[serde(with = "hex_hash")]
+    pub root: Hash,
+    pub leaves: usize,
+}
+
+impl MerkleCommitment {
+    /// Checks leaves against pinned root, using given proof.
+    pub fn verify<T: AsRef<[u8]>>(&self, proof: &RangeProof, leaves: &[T]) -> io::Result<()> {
+        if proof.leaves != self.leaves {
+            return Err(invalid(format!(
+                "proof is for a tree of {} leaves, expected {}",
+                proof.leaves, self.leaves
+            )));
+        }
+        if proof.root(leaves)? != self.root {
+            return Err(invalid(format!(
+                "leaves {}..{} don't match pinned root {}",
+                proof.start,
+                proof.end,
+                hex::encode(self.root)
+            )));
+        }
+        Ok(())
+    }
+}
+
+/// Size of i-th leaf of `pp_<N>.data`.
+pub fn leaf_size(index: usize) -> usize {
+    if index == 1 || index == 2 {
+        G2_SIZE
+    } else {
+        G1_SIZE
+    }
+}
+
+/// Splits encoded points of leaves `start..` into leaves.
+pub fn split_leaves(start: usize, bytes: &[u8]) -> io::Result<Vec<&[u8]>> {
+    let mut leaves = Vec::new();
+    let mut rest = bytes;
+    while !rest.is_empty() {
+        let size = leaf_size(start + leaves.len());
+        if rest.len() < size {
+            return Err(invalid(format!(
+                "leaf #{} is truncated, expected {}b, found {}b",
+                start + leaves.len(),
+                size,
+                rest.len()
+            )));
+        }
+        let (leaf, tail) = rest.split_at(size);
+        leaves.push(leaf);
+        rest = tail;
+    }
+    Ok(leaves)
+}
+
+/// Builds tree over points of `pp_<N>.data`.
+pub fn tree(pp_bytes: &[u8]) -> io::Result<MerkleTree> {
+    if Format::detect(pp_bytes) != Some(Format::Compressed) {
+        return Err(invalid(
+            "expected compressed public parameters, as in `pp_<N>.data`",
+        ));
+    }
+    Ok(MerkleTree::new(&split_leaves(0, pp_bytes)?))
+}
+
+/// Leaves needed for committing to polynomials of degree ≤ `degree`.
+pub fn degree_range(degree: usize) -> Range<usize> {
+    0..degree.saturating_add(OPENING_KEY_LEAVES + 1)
+}
+
+/// Number of bytes of `pp_<N>.data` leaves `0..end` occupy.
+pub fn prefix_len(end: usize) -> usize {
+    (0..end).map(leaf_size).sum()
+}
+
+/// Loads public parameters from a prefix of `pp_<N>.data`, after checking it against pinned root.
+pub fn load_prefix(
+    commitment: &MerkleCommitment,
+    proof: &RangeProof,
+    prefix: &[u8],
+) -> io::Result<PublicParameters> {
+    if proof.start != 0 || proof.end <= OPENING_KEY_LEAVES {
+        return Err(invalid(format!(
+            "proof of leaves {}..{} doesn't cover opening key & at least one g1 power",
+            proof.start, proof.end
+        )));
+    }
+
+    commitment.verify(proof, &split_leaves(0, prefix)?)?;
+    PublicParameters::from_slice(prefix)
+        .map_err(|e| invalid(format!("unable to decode public parameters: {:?}", e)))
+}
+
+mod hex_hash {
+    use super::Hash;
+    use serde::{Deserialize, Deserializer, Serializer};
+    use std::convert::TryInto;
+
+    pub fn serialize<S: Serializer>(hash: &Hash, s: S) -> Result<S::Ok, S::Error> {
+        s.serialize_str(&hex::encode(hash))
+    }
+
+    pub fn deserialize<'de, D: Deserializer<'de>>(d: D) -> Result<Hash, D::Error> {
+        let s = String::deserialize(d)?;
+        let bytes = hex::decode(&s).map_err(serde::de::Error::custom)?;
+        bytes
+            .try_into()
+            .map_err(|_| serde::de::Error::custom("expected 32 bytes hash"))
+    }
+}
+
+mod hex_hashes {
+    use super::Hash;
+    use serde::{Deserialize, Deserializer, Serialize, Serializer};
+    use std::convert::TryInto;
+
+    pub fn serialize<S: Serializer>(hashes: &[Hash], s: S) -> Result<S::Ok, S::Error> {
+        hashes
+            .iter()
+            .map(hex::encode)
+            .collect::<Vec<_>>()
+            .serialize(s)
+    }
+
+    pub fn deserialize<'de, D: Deserializer<'de>>(d: D) -> Result<Vec<Hash>, D::Error> {
+        Vec::<String>::deserialize(d)?
+            .iter()
+            .map(|s| {
+                let bytes = hex::decode(s).map_err(serde::de::Error::custom)?;
+                bytes
+                    .try_into()
+                    .map_err(|_| serde::de::Error::custom("expected 32 bytes hash"))
+            })
+            .collect()
+    }
+}
+
+#[test]
+fn test_range_proofs() {
+    let leaves: Vec<Vec<u8>> = (0..13u8).map(|i| vec![i; 48]).collect();
+    let tree = MerkleTree::new(&leaves);
+
+    for start in 0..leaves.len() {
+        for end in (start + 1)..=leaves.len() {
+            let proof = tree.prove(start..end).unwrap();
+            assert_eq!(proof.root(&leaves[start..end]).unwrap(), tree.root());
+            assert!(tree
+                .commitment()
+                .verify(&proof, &leaves[start..end])
+                .is_ok());
+        }
+    }
+}
+
+#[test]
+fn test_range_proof_rejects_tampering() {
+    let leaves: Vec<Vec<u8>> = (0..13u8).map(|i| vec![i; 48]).collect();
+    let tree = MerkleTree::new(&leaves);
+    let commitment = tree.commitment();
+
+    let proof = tree.prove(3..9).unwrap();
+    let mut tampered = leaves[3..9].to_vec();
+    tampered[2][0] ^= 1;
+    assert!(commitment.verify(&proof, &tampered).is_err());
+
+    // same leaves, claimed at a different position
+    let mut shifted = tree.prove(3..9).unwrap();
+    shifted.start = 4;
+    shifted.end = 10;
+    assert!(commitment.verify(&shifted, &leaves[3..9]).is_err());
+
+    let mut short = tree.prove(3..9).unwrap();
+    short.hashes.pop();
+    assert!(commitment.verify(&short, &leaves[3..9]).is_err());
+
+    let single = MerkleTree::new(&leaves[..1]);
+    assert_eq!(
+        single.prove(0..1).unwrap().root(&leaves[..1]).unwrap(),
+        single.root()
+    );
+
+    // hostile leaf count is refused rather than overflowing
+    let mut huge = tree.prove(3..9).unwrap();
+    huge.leaves = usize::MAX;
+    assert!(huge.root(&leaves[3..9]).is_err());
+
+    // ranges outside tree can't be proven
+    assert!(tree.prove(9..9).is_err());
+    assert!(tree.prove(3..14).is_err());
+    assert!(tree.prove(degree_range(usize::MAX)).is_err());
+}
+
+#[test]
+fn test_split_leaves() {
+    use crate::artifact::OPENING_KEY_SIZE;
+
+    let prefix = vec![0u8; prefix_len(degree_range(4).end)];
+    assert_eq!(prefix.len(), OPENING_KEY_SIZE + 5 * G1_SIZE);
+
+    let leaves = split_leaves(0, &prefix).unwrap();
+    assert_eq!(leaves.len(), degree_range(4).end);
+    assert!(split_leaves(0, &prefix[..(prefix.len() - 1)]).is_err());
+}
@@ -0,0 +1,127 @@
+//! SCALE encoding of public parameters, for Substrate runtimes embedding or referencing the SRS
+//! in their native encoding.
+//!
+//! Encoded form is a versioned enum, so that layout can change without breaking stored values.
+//! `V1` holds same compressed points as `pp_<N>.data`, in same order, so converting between both
+//! is lossless.
+
+use crate::artifact::{invalid, Format, G1_SIZE, G2_SIZE, OPENING_KEY_SIZE};
+use codec::{Decode, Encode};
+use dusk_plonk::commitment_scheme::kzg10::PublicParameters;
+use std::convert::TryInto;
+use std::io;
+
+#[derive(Clone, Debug, PartialEq, Eq, Encode, Decode)]
+pub enum VersionedSrs {
+    #[codec(index = 1)]
+    V1(SrsV1),
+}
+
+#[derive(Clone, Debug, PartialEq, Eq, Encode, Decode)]
+pub struct SrsV1 {
+    /// opening key, compressed
+    pub opening_key: OpeningKeyV1,
+    /// commit key, i.e. g1 tau powers, compressed
+    pub powers_of_g: Vec<[u8; G1_SIZE]>,
+}
+
+#[derive(Clone, Debug, PartialEq, Eq, Encode, Decode)]
+pub struct OpeningKeyV1 {
+    pub g: [u8; G1_SIZE],
+    pub h: [u8; G2_SIZE],
+    pub beta_h: [u8; G2_SIZE],
+}
+
+impl VersionedSrs {
+    /// Takes points of `pp_<N>.data` as they are, without decoding them.
+    pub fn from_pp_bytes(pp_bytes: &[u8]) -> io::Result<VersionedSrs> {
+        if Format::detect(pp_bytes) != Some(Format::Compressed) {
+            return Err(invalid(
+                "expected compressed public parameters, as in `pp_<N>.data`",
+            ));
+        }
+
+        let (key, g1s) = pp_bytes.split_at(OPENING_KEY_SIZE);
+        let opening_key = OpeningKeyV1 {
+            g: key[..G1_SIZE].try_into().unwrap(),
+            h: key[G1_SIZE..(G1_SIZE + G2_SIZE)].try_into().unwrap(),
+            beta_h: key[(G1_SIZE + G2_SIZE)..].try_into().unwrap(),
+        };
+        let powers_of_g = g1s.chunks(G1_SIZE).map(|p| p.try_into().unwrap()).collect();
+
+        Ok(VersionedSrs::V1(SrsV1 {
+            opening_key,
+            powers_of_g,
+        }))
+    }
+
+    pub fn from_public_parameters(pp: &PublicParameters) -> VersionedSrs {
+        // `to_var_bytes` always yields `pp_<N>.data` layout
+        VersionedSrs::from_pp_bytes(&pp.to_var_bytes()).unwrap()
+    }
+
+    /// Same bytes as `pp_<N>.data`.
+    pub fn to_pp_bytes(&self) -> Vec<u8> {
+        match self {
+            VersionedSrs::V1(srs) => {
+                let mut bytes =
+                    Vec::with_capacity(OPENING_KEY_SIZE + srs.powers_of_g.len() * G1_SIZE);
+                bytes.extend_from_slice(&srs.opening_key.g);
+                bytes.extend_from_slice(&srs.opening_key.h);
+                bytes.extend_from_slice(&srs.opening_key.beta_h);
+                for p in srs.powers_of_g.iter() {
+                    bytes.extend_from_slice(p);
+                }
+                bytes
+            }
+        }
+    }
+
+    /// Decodes points, checking every one of them.
+    pub fn to_public_parameters(&self) -> io::Result<PublicParameters> {
+        PublicParameters::from_slice(&self.to_pp_bytes())
+            .map_err(|e| invalid(format!("unable to decode public parameters: {:?}", e)))
+    }
+
+    /// Decodes SCALE encoded bytes, refusing any trailing bytes.
+    pub fn decode_all(mut bytes: &[u8]) -> io::Result<VersionedSrs> {
+        let srs = VersionedSrs::decode(&mut bytes)
+            .map_err(|e| invalid(format!("not a SCALE encoded SRS: {}", e)))?;
+        if !bytes.is_empty() {
+            return Err(invalid(format!(
+                "{}b left after SCALE encoded SRS",
+                bytes.len()
+            )));
+        }
+        Ok(srs)
+    }
+}
+
+#[test]
+fn test_scale_roundtrip() {
+    use rand::rngs::StdRng;
+    use rand::SeedableRng;
+
+    let mut rng = StdRng::seed_from_u64(39);
+    let pp = PublicParameters::setup(1 << 5, &mut rng).unwrap();
+    let pp_bytes = pp.to_var_bytes();
+
+    let encoded = VersionedSrs::from_public_parameters(&pp).encode();
+    // variant index, compact length of 33 powers & points
+    assert_eq!(encoded[0], 1);
+    assert_eq!(encoded.len(), 1 + 1 + pp_bytes.len());
+
+    let decoded = VersionedSrs::decode_all(&encoded).unwrap();
+    assert_eq!(decoded.to_pp_bytes(), pp_bytes);
+    assert_eq!(
+        decoded.to_public_parameters().unwrap().to_var_bytes(),
+        pp_bytes
+    );
+
+    let mut unknown = encoded.clone();
+    unknown[0] = 2;
+    assert!(VersionedSrs::decode_all(&unknown).is_err());
+    let mut trailing = encoded;
+    trailing.push(0);
+    assert!(VersionedSrs::decode_all(&trailing).is_err());
+}
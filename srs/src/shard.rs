@@ -0,0 +1,284 @@
+//! Sharded distribution of `pp_<N>.data`: commit key is split into fixed-size shards of
+//! compressed g1 powers, while a small index carries opening key, degree range & sha256 of
+//! every shard. Loaders fetch only those shards needed for requested degree.
+//!
+//! Index vouches for shards, so it's only opened against its own sha256, pinned or listed in a
+//! signed manifest, see `signed::open_shard_index`.
+
+use crate::artifact::{invalid, Format, G1_SIZE, OPENING_KEY_SIZE};
+use crate::manifest::sha256;
+use dusk_plonk::commitment_scheme::kzg10::PublicParameters;
+use serde::{Deserialize, Serialize};
+use std::fs;
+use std::io;
+use std::path::{Component, Path};
+
+#[derive(Clone, Debug, PartialEq, Eq, Serialize, Deserialize)]
+pub struct ShardIndex {
+    /// number of g1 powers across all shards
+    pub points: usize,
+    /// number of g1 powers per shard, last shard may hold fewer
+    pub shard_size: usize,
+    /// hex encoded, compressed opening key
+    pub opening_key: String,
+    pub shards: Vec<Shard>,
+}
+
+#[derive(Clone, Debug, PartialEq, Eq, Serialize, Deserialize)]
+pub struct Shard {
+    pub file: String,
+    /// first g1 power, i.e. lowest degree, held by this shard
+    pub start: usize,
+    /// one past last g1 power held by this shard
+    pub end: usize,
+    /// hex encoded sha256 of shard file
+    pub sha256: String,
+}
+
+impl ShardIndex {
+    /// Splits `pp_<N>.data` into shards of `shard_size` g1 powers, returning index & shard files.
+    pub fn split(pp_bytes: &[u8], shard_size: usize) -> io::Result<(ShardIndex, Vec<Vec<u8>>)> {
+        if Format::detect(pp_bytes) != Some(Format::Compressed) {
+            return Err(invalid(
+                "expected compressed public parameters, as in `pp_<N>.data`",
+            ));
+        }
+        assert!(shard_size > 0, "shard must hold at least one g1 power");
+
+        let g1s = &pp_bytes[OPENING_KEY_SIZE..];
+        let points = g1s.len() / G1_SIZE;
+
+        let mut shards = Vec::new();
+        let mut files = Vec::new();
+        for (i, chunk) in g1s.chunks(shard_size * G1_SIZE).enumerate() {
+            let start = i * shard_size;
+            shards.push(Shard {
+                file: format!("pp_{}_shard_{}.data", points, i),
+                start,
+                end: start + chunk.len() / G1_SIZE,
+                sha256: sha256(chunk),
+            });
+            files.push(chunk.to_vec());
+        }
+
+        let index = ShardIndex {
+            points,
+            shard_size,
+            opening_key: hex::encode(&pp_bytes[..OPENING_KEY_SIZE]),
+            shards,
+        };
+        Ok((index, files))
+    }
+
+    /// Reads an index, accepting it only if its sha256 is `expected`, as `split` prints it.
+    pub fn open_pinned<P: AsRef<Path>>(path: P, expected: &str) -> io::Result<ShardIndex> {
+        let path = path.as_ref();
+        let bytes = fs::read(path)?;
+        if sha256(&bytes) != expected.trim().to_ascii_lowercase() {
+            return Err(invalid(format!(
+                "`{}` doesn't match pinned hash {}",
+                path.display(),
+                expected
+            )));
+        }
+        ShardIndex::from_bytes(&bytes)
+    }
+
+    /// Parses an index whose bytes have already been checked against a trusted hash.
+    pub(crate) fn from_bytes(bytes: &[u8]) -> io::Result<ShardIndex> {
+        let index: ShardIndex = serde_json::from_slice(bytes).map_err(invalid)?;
+        index.check()?;
+        Ok(index)
+    }
+
+    /// Refuses an index of no points, or naming shards by anything but a plain file name.
+    fn check(&self) -> io::Result<()> {
+        if self.points == 0 || self.shard_size == 0 {
+            return Err(invalid(format!(
+                "index must hold points in non-empty shards, found {} points in shards of {}",
+                self.points, self.shard_size
+            )));
+        }
+        for shard in self.shards.iter() {
+            let mut components = Path::new(&shard.file).components();
+            let plain = match (components.next(), components.next()) {
+                (Some(Component::Normal(name)), None) => name == shard.file.as_str(),
+                _ => false,
+            };
+            if !plain || shard.file.contains('\\') {
+                return Err(invalid(format!(
+                    "shard `{}` must be named by a plain file name",
+                    shard.file
+                )));
+            }
+        }
+        Ok(())
+    }
+
+    pub fn write<P: AsRef<Path>>(&self, path: P) -> io::Result<()> {
+        let mut bytes = serde_json::to_vec_pretty(self)?;
+        bytes.push(b'\n');
+        fs::write(path, bytes)
+    }
+
+    pub fn max_degree(&self) -> usize {
+        self.points.saturating_sub(1)
+    }
+
+    /// Shards holding g1 powers `0..=degree`.
+    pub fn shards_for(&self, degree: usize) -> impl Iterator<Item = &Shard> {
+        self.shards.iter().filter(move |s| s.start <= degree)
+    }
+
+    /// Loads public parameters of max degree `degree`, reading only required shards from `dir`.
+    /// Every shard is checked against its hash before being used.
+    pub fn load<P: AsRef<Path>>(&self, dir: P, degree: usize) -> io::Result<PublicParameters> {
+        self.load_with(degree, |shard| fs::read(dir.as_ref().join(&shard.file)))
+    }
+
+    /// Same as `load`, but shards are fetched by `fetch`.
+    pub fn load_with<F>(&self, degree: usize, mut fetch: F) -> io::Result<PublicParameters>
+    where
+        F: FnMut(&Shard) -> io::Result<Vec<u8>>,
+    {
+        self.check()?;
+        if degree == 0 || degree > self.max_degree() {
+            return Err(invalid(format!(
+                "degree must be within 1..={}, found {}",
+                self.max_degree(),
+                degree
+            )));
+        }
+        // `PublicParameters::trim` never truncates below degree 2
+        let degree = degree.max(2).min(self.max_degree());
+
+        let mut bytes = hex::decode(&self.opening_key).map_err(invalid)?;
+        if bytes.len() != OPENING_KEY_SIZE {
+            return Err(invalid(format!(
+                "opening key must be {}b, found {}b",
+                OPENING_KEY_SIZE,
+                bytes.len()
+            )));
+        }
+
+        let mut next = 0;
+        for shard in self.shards_for(degree) {
+            if shard.start != next || shard.end <= shard.start {
+                return Err(invalid(format!(
+                    "shard `{}` covers {}..{}, expected it to start at {}",
+                    shard.file, shard.start, shard.end, next
+                )));
+            }
+
+            let content = fetch(shard)?;
+            if sha256(&content) != shard.sha256 {
+                return Err(invalid(format!(
+                    "shard `{}` doesn't match its hash {}",
+                    shard.file, shard.sha256
+                )));
+            }
+            if content.len() != (shard.end - shard.start) * G1_SIZE {
+                return Err(invalid(format!(
+                    "shard `{}` must hold {} g1 powers",
+                    shard.file,
+                    shard.end - shard.start
+                )));
+            }
+
+            let needed = shard.end.min(degree + 1) - shard.start;
+            bytes.extend_from_slice(&content[..(needed * G1_SIZE)]);
+            next = shard.end;
+        }
+
+        if next <= degree {
+            return Err(invalid(format!(
+                "shards cover powers up to {}, can't load degree {}",
+                next, degree
+            )));
+        }
+
+        PublicParameters::from_slice(&bytes)
+            .map_err(|e| invalid(format!("unable to decode public parameters: {:?}", e)))
+    }
+}
+
+#[test]
+fn test_shards_reassemble() {
+    use dusk_bytes::Serializable;
+    use rand::rngs::StdRng;
+    use rand::SeedableRng;
+
+    let mut rng = StdRng::seed_from_u64(0x5a4d);
+    let pp = PublicParameters::setup(99, &mut rng).unwrap();
+    let pp_bytes = pp.to_var_bytes();
+
+    let (index, files) = ShardIndex::split(&pp_bytes, 16).unwrap();
+    assert_eq!(index.points, 100);
+    assert_eq!(index.shards.len(), 7);
+    assert_eq!(index.shards[6].end, 100);
+
+    let fetch = |shard: &Shard| -> io::Result<Vec<u8>> { Ok(files[shard.start / 16].clone()) };
+    for degree in [1, 15, 16, 50, 99].iter() {
+        let loaded = index.load_with(*degree, fetch).unwrap();
+        let (ck, ok) = pp.trim(*degree).unwrap();
+        let (loaded_ck, loaded_ok) = loaded.trim(*degree).unwrap();
+        assert_eq!(loaded_ck, ck);
+        assert_eq!(loaded_ok.to_bytes(), ok.to_bytes());
+    }
+
+    let mut fetched = Vec::new();
+    index
+        .load_with(20, |shard| {
+            fetched.push(shard.start);
+            Ok(files[shard.start / 16].clone())
+        })
+        .unwrap();
+    assert_eq!(fetched, vec![0, 16]);
+
+    let tampered = |shard: &Shard| -> io::Result<Vec<u8>> {
+        let mut content = files[shard.start / 16].clone();
+        content[0] ^= 1;
+        Ok(content)
+    };
+    assert!(index.load_with(50, tampered).is_err());
+    assert!(index.load_with(100, fetch).is_err());
+}
+
+#[test]
+fn test_hostile_index() {
+    let index = ShardIndex {
+        points: 4,
+        shard_size: 4,
+        opening_key: String::new(),
+        shards: vec![Shard {
+            file: "pp_4_shard_0.data".to_owned(),
+            start: 0,
+            end: 4,
+            sha256: String::new(),
+        }],
+    };
+    let bytes = serde_json::to_vec(&index).unwrap();
+    assert_eq!(ShardIndex::from_bytes(&bytes).unwrap(), index);
+
+    let empty = ShardIndex {
+        points: 0,
+        ..index.clone()
+    };
+    assert!(ShardIndex::from_bytes(&serde_json::to_vec(&empty).unwrap()).is_err());
+    assert!(empty.load_with(1, |_| Ok(vec![])).is_err());
+
+    for file in ["../pp_4_shard_0.data", "/etc/passwd", "shards/x", "..", ""].iter() {
+        let mut escaping = index.clone();
+        escaping.shards[0].file = file.to_string();
+        assert!(ShardIndex::from_bytes(&serde_json::to_vec(&escaping).unwrap()).is_err());
+    }
+
+    let path = std::env::temp_dir().join(format!("srs_index_{}.json", std::process::id()));
+    fs::write(&path, &bytes).unwrap();
+    assert_eq!(
+        ShardIndex::open_pinned(&path, &sha256(&bytes)).unwrap(),
+        index
+    );
+    assert!(ShardIndex::open_pinned(&path, &sha256(b"other index")).is_err());
+    fs::remove_file(path).unwrap();
+}
@@ -0,0 +1,309 @@
+//! Ed25519 signed release manifests. Maintainers sign a manifest independently, each signature
+//! being appended to it, while consumers accept a manifest only once signatures of at least
+//! `threshold` distinct trusted keys verify.
+//!
+//! Signed message is `MESSAGE_PREFIX` followed by compact JSON of manifest, which is
+//! deterministic as artifacts are kept sorted by name.
+
+use crate::artifact::invalid;
+use crate::cache::Cache;
+use crate::fetch::{Fetcher, Source};
+use crate::manifest::{sha256, Manifest};
+use crate::shard::ShardIndex;
+use dusk_plonk::commitment_scheme::kzg10::PublicParameters;
+use ed25519_dalek::{Signature, Signer, SigningKey, VerifyingKey};
+use serde::{Deserialize, Serialize};
+use std::collections::{BTreeMap, BTreeSet};
+use std::convert::TryInto;
+use std::fs;
+use std::io;
+use std::path::Path;
+
+/// Domain separation, so that a manifest signature is never valid for anything else.
+pub const MESSAGE_PREFIX: &[u8] = b"avail-srs manifest v1\n";
+
+#[derive(Clone, Debug, PartialEq, Eq, Serialize, Deserialize)]
+pub struct ManifestSignature {
+    /// hex encoded ed25519 public key
+    pub key: String,
+    /// hex encoded ed25519 signature
+    pub signature: String,
+}
+
+#[derive(Clone, Debug, PartialEq, Eq, Serialize, Deserialize)]
+pub struct SignedManifest {
+    pub manifest: Manifest,
+    pub signatures: Vec<ManifestSignature>,
+}
+
+/// Keys of maintainers, by name, along with how many of them must've signed.
+#[derive(Clone, Debug, PartialEq, Eq, Serialize, Deserialize)]
+pub struct TrustedKeys {
+    pub threshold: usize,
+    /// hex encoded ed25519 public keys, keyed by maintainer name
+    pub keys: BTreeMap<String, String>,
+}
+
+/// Outcome of checking every signature of a manifest against trusted keys.
+#[derive(Clone, Debug, Default, PartialEq, Eq)]
+pub struct Verification {
+    /// names of trusted keys with a valid signature
+    pub valid: BTreeSet<String>,
+    /// names of trusted keys whose signature doesn't verify
+    pub invalid: BTreeSet<String>,
+    /// hex encoded keys which aren't trusted, their signatures aren't even checked
+    pub untrusted: BTreeSet<String>,
+}
+
+pub fn message(manifest: &Manifest) -> io::Result<Vec<u8>> {
+    let mut msg = MESSAGE_PREFIX.to_vec();
+    msg.extend(serde_json::to_vec(manifest)?);
+    Ok(msg)
+}
+
+/// Reads a hex encoded, 32 bytes ed25519 secret key.
+pub fn open_signing_key<P: AsRef<Path>>(path: P) -> io::Result<SigningKey> {
+    let bytes = hex::decode(fs::read_to_string(path)?.trim()).map_err(invalid)?;
+    let bytes: [u8; 32] = bytes
+        .try_into()
+        .map_err(|_| invalid("ed25519 secret key must be 32 bytes"))?;
+    Ok(SigningKey::from_bytes(&bytes))
+}
+
+pub fn verifying_key_from_hex(s: &str) -> io::Result<VerifyingKey> {
+    let bytes = hex::decode(s.trim()).map_err(invalid)?;
+    let bytes: [u8; 32] = bytes
+        .try_into()
+        .map_err(|_| invalid(format!("ed25519 public key `{}` must be 32 bytes", s)))?;
+    VerifyingKey::from_bytes(&bytes)
+        .map_err(|e| invalid(format!("ed25519 public key `{}` is invalid: {}", s, e)))
+}
+
+fn signature_from_hex(s: &str) -> io::Result<Signature> {
+    let bytes = hex::decode(s.trim()).map_err(invalid)?;
+    let bytes: [u8; 64] = bytes
+        .try_into()
+        .map_err(|_| invalid("ed25519 signature must be 64 bytes"))?;
+    Ok(Signature::from_bytes(&bytes))
+}
+
+impl SignedManifest {
+    pub fn new(manifest: Manifest) -> SignedManifest {
+        SignedManifest {
+            manifest,
+            signatures: Vec::new(),
+        }
+    }
+
+    /// Reads a signed manifest, or a plain one, which is taken as signed by nobody.
+    pub fn open<P: AsRef<Path>>(path: P) -> io::Result<SignedManifest> {
+        let bytes = fs::read(path)?;
+        match serde_json::from_slice(&bytes) {
+            Ok(signed) => Ok(signed),
+            Err(_) => Ok(SignedManifest::new(
+                serde_json::from_slice(&bytes).map_err(invalid)?,
+            )),
+        }
+    }
+
+    pub fn write<P: AsRef<Path>>(&self, path: P) -> io::Result<()> {
+        let mut bytes = serde_json::to_vec_pretty(self)?;
+        bytes.push(b'\n');
+        fs::write(path, bytes)
+    }
+
+    /// Appends a signature, replacing an earlier one made with same key.
+    pub fn sign(&mut self, key: &SigningKey) -> io::Result<()> {
+        let signature = key.sign(&message(&self.manifest)?);
+        let public = hex::encode(key.verifying_key().to_bytes());
+
+        self.signatures.retain(|s| s.key != public);
+        self.signatures.push(ManifestSignature {
+            key: public,
+            signature: hex::encode(signature.to_bytes()),
+        });
+        Ok(())
+    }
+
+    /// Checks every signature, without applying threshold.
+    pub fn check(&self, trusted: &TrustedKeys) -> io::Result<Verification> {
+        let msg = message(&self.manifest)?;
+        let mut verification = Verification::default();
+
+        for s in self.signatures.iter() {
+            let name = trusted
+                .keys
+                .iter()
+                .find(|(_, key)| key.trim().eq_ignore_ascii_case(s.key.trim()))
+                .map(|(name, _)| name.clone());
+            let name = match name {
+                Some(name) => name,
+                None => {
+                    verification.untrusted.insert(s.key.clone());
+                    continue;
+                }
+            };
+
+            let key = verifying_key_from_hex(&s.key)?;
+            let valid = signature_from_hex(&s.signature)
+                .map(|sig| key.verify_strict(&msg, &sig).is_ok())
+                .unwrap_or(false);
+            if valid {
+                verification.valid.insert(name);
+            } else {
+                verification.invalid.insert(name);
+            }
+        }
+
+        // a key with both a valid & an invalid signature still vouched for manifest
+        let valid = verification.valid.clone();
+        verification.invalid.retain(|n| !valid.contains(n));
+        Ok(verification)
+    }
+
+    /// Manifest, if signatures of at least `threshold` distinct trusted keys verify.
+    pub fn verify(&self, trusted: &TrustedKeys) -> io::Result<&Manifest> {
+        if trusted.threshold == 0 {
+            return Err(invalid("threshold of trusted keys must be at least 1"));
+        }
+
+        let verification = self.check(trusted)?;
+        if verification.valid.len() < trusted.threshold {
+            return Err(invalid(format!(
+                "manifest is signed by {} of required {} trusted keys",
+                verification.valid.len(),
+                trusted.threshold
+            )));
+        }
+        Ok(&self.manifest)
+    }
+
+    /// Checks an artifact against verified manifest, refusing it unless it's listed under given
+    /// name with same hash.
+    pub fn accept(&self, trusted: &TrustedKeys, name: &str, bytes: &[u8]) -> io::Result<()> {
+        let manifest = self.verify(trusted)?;
+        match manifest.artifacts.get(name) {
+            Some(hash) if *hash == sha256(bytes) => Ok(()),
+            Some(hash) => Err(invalid(format!(
+                "`{}` doesn't match hash {} in signed manifest",
+                name, hash
+            ))),
+            None => Err(invalid(format!(
+                "`{}` isn't listed in signed manifest",
+                name
+            ))),
+        }
+    }
+
+    /// Fetcher trusting only hashes of verified manifest.
+    pub fn fetcher(
+        &self,
+        trusted: &TrustedKeys,
+        source: Source,
+        cache: Cache,
+    ) -> io::Result<Fetcher> {
+        let manifest = self.verify(trusted)?;
+        Ok(Fetcher::with_pins(
+            source,
+            cache,
+            manifest.artifacts.clone(),
+        ))
+    }
+}
+
+impl TrustedKeys {
+    pub fn open<P: AsRef<Path>>(path: P) -> io::Result<TrustedKeys> {
+        let trusted: TrustedKeys = serde_json::from_slice(&fs::read(path)?).map_err(invalid)?;
+        for key in trusted.keys.values() {
+            verifying_key_from_hex(key)?;
+        }
+        Ok(trusted)
+    }
+}
+
+/// Reads `pp_<N>.data`, accepting it only if it's listed in a manifest signed by enough trusted
+/// keys. Artifact is looked up by its file name.
+pub fn open_public_parameters<P: AsRef<Path>>(
+    path: P,
+    signed: &SignedManifest,
+    trusted: &TrustedKeys,
+) -> io::Result<PublicParameters> {
+    let path = path.as_ref();
+    let name = path
+        .file_name()
+        .and_then(|n| n.to_str())
+        .ok_or_else(|| invalid(format!("`{}` has no file name", path.display())))?;
+
+    let bytes = fs::read(path)?;
+    signed.accept(trusted, name, &bytes)?;
+    PublicParameters::from_slice(&bytes)
+        .map_err(|e| invalid(format!("unable to decode public parameters: {:?}", e)))
+}
+
+/// Reads a shard index, accepting it only if it's listed in a manifest signed by enough trusted
+/// keys, so that its opening key & shard hashes can be relied on.
+pub fn open_shard_index<P: AsRef<Path>>(
+    path: P,
+    signed: &SignedManifest,
+    trusted: &TrustedKeys,
+) -> io::Result<ShardIndex> {
+    let path = path.as_ref();
+    let name = path
+        .file_name()
+        .and_then(|n| n.to_str())
+        .ok_or_else(|| invalid(format!("`{}` has no file name", path.display())))?;
+
+    let bytes = fs::read(path)?;
+    signed.accept(trusted, name, &bytes)?;
+    ShardIndex::from_bytes(&bytes)
+}
+
+#[test]
+fn test_threshold_signatures() {
+    let keys: Vec<SigningKey> = (1..=3u8)
+        .map(|i| SigningKey::from_bytes(&[i; 32]))
+        .collect();
+    let mut trusted = TrustedKeys {
+        threshold: 2,
+        keys: BTreeMap::new(),
+    };
+    for (name, key) in ["alice", "bob", "carol"].iter().zip(keys.iter()) {
+        trusted.keys.insert(
+            name.to_string(),
+            hex::encode(key.verifying_key().to_bytes()),
+        );
+    }
+
+    let mut manifest = Manifest::new(1024);
+    manifest.add_artifact("pp_1024.data", b"points");
+    let mut signed = SignedManifest::new(manifest);
+
+    signed.sign(&keys[0]).unwrap();
+    assert!(signed.verify(&trusted).is_err());
+
+    // signing twice with same key doesn't count twice
+    signed.sign(&keys[0]).unwrap();
+    assert_eq!(signed.signatures.len(), 1);
+    assert!(signed.verify(&trusted).is_err());
+
+    // nor does a key nobody trusts
+    signed.sign(&SigningKey::from_bytes(&[9; 32])).unwrap();
+    assert!(signed.verify(&trusted).is_err());
+
+    signed.sign(&keys[2]).unwrap();
+    assert!(signed.verify(&trusted).is_ok());
+    let verification = signed.check(&trusted).unwrap();
+    assert_eq!(verification.valid.len(), 2);
+    assert_eq!(verification.untrusted.len(), 1);
+
+    assert!(signed.accept(&trusted, "pp_1024.data", b"points").is_ok());
+    assert!(signed.accept(&trusted, "pp_1024.data", b"pointz").is_err());
+    assert!(signed
+        .accept(&trusted, "pp_raw_1024.data", b"points")
+        .is_err());
+
+    let mut tampered = signed.clone();
+    tampered.manifest.add_artifact("pp_1024.data", b"pointz");
+    assert!(tampered.verify(&trusted).is_err());
+    assert_eq!(tampered.check(&trusted).unwrap().invalid.len(), 2);
+}
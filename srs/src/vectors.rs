@@ -0,0 +1,185 @@
+//! Golden test vectors for Avail light-client & validator implementations.
+//!
+//! A data matrix of `rows` x `cols` scalars is derived from a seed: cell (i, j), in row-major
+//! order, is `BlsScalar::from_bytes_wide` over next 64 bytes of ChaCha20 keystream seeded with
+//! `ChaCha20Rng::seed_from_u64(seed)`. Each row is interpolated over evaluation domain of size
+//! `cols`, committed to & opened at every domain element, so cell (i, j) is proven to be
+//! evaluation of row i's polynomial at j-th domain element.
+
+use crate::artifact::invalid;
+use crate::kzg::{g1_to_hex, interpolate, scalar_to_hex, OpeningProof};
+use dusk_plonk::bls12_381::BlsScalar;
+use dusk_plonk::commitment_scheme::kzg10::PublicParameters;
+use dusk_plonk::fft::EvaluationDomain;
+use rand::{RngCore, SeedableRng};
+use rand_chacha::ChaCha20Rng;
+use serde::{Deserialize, Serialize};
+use std::fs;
+use std::io;
+use std::path::Path;
+
+#[derive(Clone, Debug, PartialEq, Eq, Serialize, Deserialize)]
+pub struct TestVector {
+    pub seed: u64,
+    pub rows: usize,
+    pub cols: usize,
+    /// hex encoded sha256 of reference string vectors were produced with
+    pub srs: String,
+    /// commitment to each row's polynomial
+    pub commitments: Vec<String>,
+    pub cells: Vec<Cell>,
+}
+
+#[derive(Clone, Debug, PartialEq, Eq, Serialize, Deserialize)]
+pub struct Cell {
+    pub row: usize,
+    pub col: usize,
+    pub proof: OpeningProof,
+}
+
+/// Derives data matrix from seed, row by row.
+pub fn matrix(seed: u64, rows: usize, cols: usize) -> Vec<Vec<BlsScalar>> {
+    let mut rng = ChaCha20Rng::seed_from_u64(seed);
+    let mut buf = [0u8; 64];
+
+    (0..rows)
+        .map(|_| {
+            (0..cols)
+                .map(|_| {
+                    rng.fill_bytes(&mut buf);
+                    BlsScalar::from_bytes_wide(&buf)
+                })
+                .collect()
+        })
+        .collect()
+}
+
+impl TestVector {
+    /// Generates vectors, `srs` being hex encoded sha256 of file `pp` was read from.
+    pub fn generate(
+        pp: &PublicParameters,
+        srs: &str,
+        seed: u64,
+        rows: usize,
+        cols: usize,
+    ) -> io::Result<TestVector> {
+        if rows == 0 || !cols.is_power_of_two() || cols > pp.max_degree() + 1 {
+            return Err(invalid(format!(
+                "expected non-zero rows & power of two columns ≤ {}, found {} x {}",
+                pp.max_degree() + 1,
+                rows,
+                cols
+            )));
+        }
+
+        let domain = EvaluationDomain::new(cols)
+            .map_err(|e| invalid(format!("no evaluation domain of size {}: {:?}", cols, e)))?;
+        let points: Vec<BlsScalar> = domain.elements().collect();
+
+        let mut commitments = Vec::with_capacity(rows);
+        let mut cells = Vec::with_capacity(rows * cols);
+        for (i, row) in matrix(seed, rows, cols).into_iter().enumerate() {
+            let poly = interpolate(row.clone())?;
+            let commitment = pp
+                .commit_key()
+                .commit(&poly)
+                .map_err(|e| invalid(format!("unable to commit to row {}: {:?}", i, e)))?;
+            commitments.push(g1_to_hex(&commitment.0));
+
+            for (j, (point, value)) in points.iter().zip(row.iter()).enumerate() {
+                let proof = pp
+                    .commit_key()
+                    .open_single(&poly, value, point)
+                    .map_err(|e| invalid(format!("unable to open cell ({}, {}): {:?}", i, j, e)))?;
+                cells.push(Cell {
+                    row: i,
+                    col: j,
+                    proof: OpeningProof::new(point, &proof),
+                });
+            }
+        }
+
+        Ok(TestVector {
+            seed,
+            rows,
+            cols,
+            srs: srs.to_owned(),
+            commitments,
+            cells,
+        })
+    }
+
+    pub fn open<P: AsRef<Path>>(path: P) -> io::Result<TestVector> {
+        serde_json::from_slice(&fs::read(path)?).map_err(invalid)
+    }
+
+    pub fn write<P: AsRef<Path>>(&self, path: P) -> io::Result<()> {
+        let mut bytes = serde_json::to_vec_pretty(self)?;
+        bytes.push(b'\n');
+        fs::write(path, bytes)
+    }
+
+    /// Checks every cell proof against opening key, then regenerates vectors from seed and
+    /// requires them to be identical, so that both proofs & data derivation are covered.
+    pub fn verify(&self, pp: &PublicParameters, srs: &str) -> io::Result<()> {
+        if self.srs != srs {
+            return Err(invalid(format!(
+                "vectors were produced with srs {}, not {}",
+                self.srs, srs
+            )));
+        }
+
+        for cell in self.cells.iter() {
+            if self.commitments.get(cell.row) != Some(&cell.proof.commitment) {
+                return Err(invalid(format!(
+                    "cell ({}, {}) isn't opened against commitment of row {}",
+                    cell.row, cell.col, cell.row
+                )));
+            }
+
+            let (point, proof) = cell.proof.decode()?;
+            if !pp.opening_key().check(point, proof) {
+                return Err(invalid(format!(
+                    "proof of cell ({}, {}) doesn't verify",
+                    cell.row, cell.col
+                )));
+            }
+        }
+
+        let expected = TestVector::generate(pp, srs, self.seed, self.rows, self.cols)?;
+        if &expected != self {
+            return Err(invalid(
+                "vectors differ from those regenerated from their seed",
+            ));
+        }
+
+        Ok(())
+    }
+}
+
+#[test]
+fn test_vectors_roundtrip() {
+    use rand::rngs::StdRng;
+
+    let mut rng = StdRng::seed_from_u64(33);
+    let pp = PublicParameters::setup(1 << 4, &mut rng).unwrap();
+
+    let vector = TestVector::generate(&pp, "00", 7, 2, 8).unwrap();
+    assert_eq!(vector.commitments.len(), 2);
+    assert_eq!(vector.cells.len(), 16);
+    assert_eq!(matrix(7, 2, 8), matrix(7, 2, 8));
+    assert_eq!(
+        vector.cells[9].proof.value,
+        scalar_to_hex(&matrix(7, 2, 8)[1][1])
+    );
+    assert!(vector.verify(&pp, "00").is_ok());
+    assert!(vector.verify(&pp, "01").is_err());
+
+    let mut tampered = vector.clone();
+    tampered.cells[3].proof.value = scalar_to_hex(&BlsScalar::one());
+    assert!(tampered.verify(&pp, "00").is_err());
+
+    let mut reseeded = vector;
+    reseeded.seed = 8;
+    assert!(reseeded.verify(&pp, "00").is_err());
+}
@@ -0,0 +1,118 @@
+/*
+ * Exercises C interface of `srs`, linked against `libsrs.a`.
+ *
+ * usage: test_srs <path-to-pp.data>
+ */
+
+#include <stdio.h>
+#include <string.h>
+
+#include "srs.h"
+
+#define DEGREE 8
+#define PROOFS 2
+
+#define CHECK(call, expected)                                                  \
+    do {                                                                       \
+        int32_t status = (call);                                               \
+        if (status != (expected)) {                                            \
+            fprintf(stderr, "%s:%d: `%s` returned %d, expected %d: %s\n",      \
+                    __FILE__, __LINE__, #call, status, (expected),             \
+                    srs_last_error());                                         \
+            return 1;                                                          \
+        }                                                                      \
+    } while (0)
+
+static uint8_t *read_file(const char *path, size_t *len) {
+    FILE *f = fopen(path, "rb");
+    if (f == NULL) {
+        return NULL;
+    }
+    fseek(f, 0, SEEK_END);
+    *len = (size_t)ftell(f);
+    fseek(f, 0, SEEK_SET);
+
+    uint8_t *bytes = malloc(*len);
+    if (bytes != NULL && fread(bytes, 1, *len, f) != *len) {
+        free(bytes);
+        bytes = NULL;
+    }
+    fclose(f);
+    return bytes;
+}
+
+/* little-endian scalar holding a small integer */
+static void scalar(uint64_t v, uint8_t out[SRS_SCALAR_SIZE]) {
+    memset(out, 0, SRS_SCALAR_SIZE);
+    for (int i = 0; i < 8; i++) {
+        out[i] = (uint8_t)(v >> (8 * i));
+    }
+}
+
+int main(int argc, char **argv) {
+    if (argc != 2) {
+        fprintf(stderr, "usage: %s <path-to-pp.data>\n", argv[0]);
+        return 2;
+    }
+
+    size_t len = 0;
+    uint8_t *bytes = read_file(argv[1], &len);
+    if (bytes == NULL) {
+        fprintf(stderr, "unable to read `%s`\n", argv[1]);
+        return 2;
+    }
+
+    SrsPublicParameters *pp = NULL;
+    CHECK(srs_load(bytes, len, &pp), SRS_OK);
+    free(bytes);
+    CHECK(srs_load(NULL, 0, NULL), SRS_ERR_NULL);
+    if (srs_max_degree(pp) < DEGREE) {
+        fprintf(stderr, "max degree %zu < %d\n", srs_max_degree(pp), DEGREE);
+        return 1;
+    }
+
+    SrsCommitKey *ck = NULL;
+    SrsOpeningKey *ok = NULL;
+    CHECK(srs_trim(pp, srs_max_degree(pp) + 1, &ck, &ok), SRS_ERR_DEGREE);
+    CHECK(srs_trim(pp, DEGREE, &ck, &ok), SRS_OK);
+
+    uint8_t commitments[PROOFS * SRS_G1_SIZE];
+    uint8_t points[PROOFS * SRS_SCALAR_SIZE];
+    uint8_t values[PROOFS * SRS_SCALAR_SIZE];
+    uint8_t witnesses[PROOFS * SRS_G1_SIZE];
+
+    for (int p = 0; p < PROOFS; p++) {
+        uint8_t coeffs[DEGREE * SRS_SCALAR_SIZE];
+        for (int i = 0; i < DEGREE; i++) {
+            scalar((uint64_t)(p * DEGREE + i + 1), coeffs + i * SRS_SCALAR_SIZE);
+        }
+        scalar((uint64_t)(p + 5), points + p * SRS_SCALAR_SIZE);
+
+        CHECK(kzg_commit(ck, coeffs, DEGREE, commitments + p * SRS_G1_SIZE), SRS_OK);
+        CHECK(kzg_open(ck, coeffs, DEGREE, points + p * SRS_SCALAR_SIZE,
+                       values + p * SRS_SCALAR_SIZE, witnesses + p * SRS_G1_SIZE),
+              SRS_OK);
+        CHECK(kzg_verify(ok, commitments + p * SRS_G1_SIZE, points + p * SRS_SCALAR_SIZE,
+                         values + p * SRS_SCALAR_SIZE, witnesses + p * SRS_G1_SIZE),
+              SRS_OK);
+    }
+    CHECK(kzg_batch_verify(ok, commitments, points, values, witnesses, PROOFS), SRS_OK);
+
+    /* claim a different evaluation for second proof */
+    values[SRS_SCALAR_SIZE] ^= 1;
+    CHECK(kzg_verify(ok, commitments + SRS_G1_SIZE, points + SRS_SCALAR_SIZE,
+                     values + SRS_SCALAR_SIZE, witnesses + SRS_G1_SIZE),
+          SRS_ERR_PROOF);
+    CHECK(kzg_batch_verify(ok, commitments, points, values, witnesses, PROOFS), SRS_ERR_PROOF);
+
+    /* not a canonical scalar */
+    memset(values, 0xff, SRS_SCALAR_SIZE);
+    CHECK(kzg_verify(ok, commitments, points, values, witnesses), SRS_ERR_INVALID);
+
+    srs_commit_key_free(ck);
+    srs_opening_key_free(ok);
+    srs_free(pp);
+
+    printf("c interface\tok\n");
+    return 0;
+}
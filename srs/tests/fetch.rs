@@ -0,0 +1,154 @@
+//! Fetches artifacts from a local HTTP server standing in for https://srs.availproject.org.
+
+use dusk_plonk::commitment_scheme::kzg10::PublicParameters;
+use rand::rngs::StdRng;
+use rand::SeedableRng;
+use srs::fetch::{Cache, Fetcher, Source};
+use srs::manifest::sha256;
+use std::collections::{BTreeMap, HashMap};
+use std::fs;
+use std::io::{BufRead, BufReader, Write};
+use std::net::TcpListener;
+use std::path::PathBuf;
+use std::sync::{Arc, Mutex};
+use std::thread;
+
+/// Serves `files` until test process exits, returning base URL & number of requests served.
+fn serve(files: HashMap<String, Vec<u8>>) -> (String, Arc<Mutex<usize>>) {
+    let listener = TcpListener::bind("127.0.0.1:0").unwrap();
+    let base = format!("http://{}", listener.local_addr().unwrap());
+    let served = Arc::new(Mutex::new(0));
+
+    let counter = served.clone();
+    thread::spawn(move || {
+        for stream in listener.incoming() {
+            let mut stream = stream.unwrap();
+            let mut reader = BufReader::new(stream.try_clone().unwrap());
+
+            let mut request = String::new();
+            reader.read_line(&mut request).unwrap();
+            loop {
+                let mut header = String::new();
+                if reader.read_line(&mut header).unwrap() == 0 || header == "\r\n" {
+                    break;
+                }
+            }
+
+            let path = request.split_whitespace().nth(1).unwrap_or("/");
+            let response = match files.get(path.trim_start_matches('/')) {
+                Some(body) => {
+                    let mut r = format!(
+                        "HTTP/1.1 200 OK\r\nContent-Length: {}\r\nConnection: close\r\n\r\n",
+                        body.len()
+                    )
+                    .into_bytes();
+                    r.extend_from_slice(body);
+                    r
+                }
+                None => b"HTTP/1.1 404 Not Found\r\nContent-Length: 0\r\nConnection: close\r\n\r\n"
+                    .to_vec(),
+            };
+            *counter.lock().unwrap() += 1;
+            stream.write_all(&response).unwrap();
+        }
+    });
+
+    (base, served)
+}
+
+fn temp_dir(name: &str) -> PathBuf {
+    let dir = std::env::temp_dir().join(format!("srs_{}_{}", name, std::process::id()));
+    let _ = fs::remove_dir_all(&dir);
+    dir
+}
+
+fn pp_bytes() -> Vec<u8> {
+    let mut rng = StdRng::seed_from_u64(35);
+    PublicParameters::setup(1 << 4, &mut rng)
+        .unwrap()
+        .to_var_bytes()
+}
+
+#[test]
+fn test_fetch_over_http_and_cache() {
+    let pp = pp_bytes();
+    let mut files = HashMap::new();
+    files.insert("pp_16.data".to_owned(), pp.clone());
+    let (base, served) = serve(files);
+
+    let cache_dir = temp_dir("fetch_cache");
+    let mut pins = BTreeMap::new();
+    pins.insert("pp_16.data".to_owned(), sha256(&pp));
+    let fetcher = Fetcher::with_pins(Source::parse(&base), Cache::new(&cache_dir), pins);
+
+    let loaded = fetcher.public_parameters("pp_16.data").unwrap();
+    assert_eq!(loaded.to_var_bytes(), pp);
+    assert!(cache_dir.join(sha256(&pp)).exists());
+    assert_eq!(*served.lock().unwrap(), 1);
+
+    // second fetch is served from cache
+    assert_eq!(fetcher.fetch("pp_16.data").unwrap(), pp);
+    assert_eq!(*served.lock().unwrap(), 1);
+
+    // corrupted cache entry isn't used, artifact is fetched again
+    fs::write(cache_dir.join(sha256(&pp)), b"corrupted").unwrap();
+    assert_eq!(fetcher.fetch("pp_16.data").unwrap(), pp);
+    assert_eq!(*served.lock().unwrap(), 2);
+
+    // nothing is pinned for it
+    assert!(fetcher.fetch("extracted.data").is_err());
+
+    fs::remove_dir_all(&cache_dir).unwrap();
+}
+
+#[test]
+fn test_fetch_refuses_mismatch() {
+    let pp = pp_bytes();
+    let mut tampered = pp.clone();
+    let last = tampered.len() - 1;
+    tampered[last] ^= 1;
+
+    let mut files = HashMap::new();
+    files.insert("pp_16.data".to_owned(), tampered.clone());
+    files.insert("pp_1024.data".to_owned(), tampered.clone());
+    let (base, _) = serve(files);
+
+    let cache_dir = temp_dir("fetch_mismatch");
+    let mut pins = BTreeMap::new();
+    pins.insert("pp_16.data".to_owned(), sha256(&pp));
+    let fetcher = Fetcher::with_pins(Source::parse(&base), Cache::new(&cache_dir), pins);
+
+    assert!(fetcher.fetch("pp_16.data").is_err());
+    assert!(fetcher.public_parameters("pp_16.data").is_err());
+    assert!(!cache_dir.join(sha256(&tampered)).exists());
+
+    // published hashes are pinned, so default fetcher refuses served artifact too
+    let fetcher = Fetcher::new(Source::parse(&base), Cache::new(&cache_dir));
+    assert!(fetcher.fetch("pp_1024.data").is_err());
+
+    let _ = fs::remove_dir_all(&cache_dir);
+}
+
+#[test]
+fn test_fetch_from_dir() {
+    let pp = pp_bytes();
+    let source_dir = temp_dir("fetch_source");
+    fs::create_dir_all(&source_dir).unwrap();
+    fs::write(source_dir.join("pp_16.data"), &pp).unwrap();
+
+    let cache_dir = temp_dir("fetch_dir_cache");
+    let mut pins = BTreeMap::new();
+    pins.insert("pp_16.data".to_owned(), sha256(&pp));
+    let fetcher = Fetcher::with_pins(
+        Source::parse(source_dir.to_str().unwrap()),
+        Cache::new(&cache_dir),
+        pins,
+    );
+
+    assert_eq!(fetcher.fetch("pp_16.data").unwrap(), pp);
+    fs::remove_dir_all(&source_dir).unwrap();
+    // source is gone, cache still has it
+    assert_eq!(fetcher.fetch("pp_16.data").unwrap(), pp);
+
+    fs::remove_dir_all(&cache_dir).unwrap();
+}
@@ -0,0 +1,77 @@
+//! Builds `tests/c/test_srs.c` against `libsrs.a` & runs it, so that C interface is exercised
+//! the way C, C++ & cgo consumers use it.
+
+use dusk_plonk::commitment_scheme::kzg10::PublicParameters;
+use rand::rngs::StdRng;
+use rand::SeedableRng;
+use std::env;
+use std::fs;
+use std::path::{Path, PathBuf};
+use std::process::Command;
+
+/// `target/<profile>`, where cargo places `libsrs.a`, next to `deps` holding this test binary.
+fn target_dir() -> PathBuf {
+    let exe = env::current_exe().unwrap();
+    exe.parent().unwrap().parent().unwrap().to_path_buf()
+}
+
+#[test]
+fn test_header_is_up_to_date() {
+    let root = Path::new(env!("CARGO_MANIFEST_DIR"));
+    let config = cbindgen::Config::from_file(root.join("cbindgen.toml")).unwrap();
+
+    let mut generated = Vec::new();
+    cbindgen::Builder::new()
+        .with_config(config)
+        .with_src(root.join("src").join("ffi.rs"))
+        .generate()
+        .unwrap()
+        .write(&mut generated);
+
+    let shipped = fs::read(root.join("include").join("srs.h")).unwrap();
+    assert!(
+        generated == shipped,
+        "include/srs.h is stale, regenerate it using `cbindgen --config cbindgen.toml --output include/srs.h src/ffi.rs`"
+    );
+}
+
+#[test]
+fn test_c_program() {
+    let root = Path::new(env!("CARGO_MANIFEST_DIR"));
+    let lib = target_dir().join("libsrs.a");
+    assert!(lib.exists(), "expected `{}` to be built", lib.display());
+
+    let out = env::temp_dir().join(format!("srs_ffi_{}", std::process::id()));
+    fs::create_dir_all(&out).unwrap();
+    let exe = out.join("test_srs");
+
+    let compiler = env::var("CC").unwrap_or_else(|_| "cc".to_owned());
+    let status = Command::new(compiler)
+        .arg("-std=c99")
+        .arg("-Wall")
+        .arg("-Werror")
+        .arg("-I")
+        .arg(root.join("include"))
+        .arg(root.join("tests").join("c").join("test_srs.c"))
+        .arg(&lib)
+        .args(["-lpthread", "-ldl", "-lm"])
+        .arg("-o")
+        .arg(&exe)
+        .status()
+        .unwrap();
+    assert!(status.success(), "unable to build C test program");
+
+    let mut rng = StdRng::seed_from_u64(38);
+    let pp = PublicParameters::setup(1 << 4, &mut rng).unwrap();
+    let pp_path = out.join("pp_16.data");
+    fs::write(&pp_path, pp.to_var_bytes()).unwrap();
+
+    let output = Command::new(&exe).arg(&pp_path).output().unwrap();
+    assert!(
+        output.status.success(),
+        "C test program failed: {}",
+        String::from_utf8_lossy(&output.stderr)
+    );
+
+    fs::remove_dir_all(&out).unwrap();
+}